@@ -0,0 +1,792 @@
+//! Win probability for a whole batch of tickets.
+//!
+//! The probability that at least one ticket of a batch matches `k` or more
+//! balls is easy for the jackpot tier, where the events are disjoint and
+//! simply add up. For lower tiers the tickets overlap and a draw can win on
+//! several of them at once, so this module offers three ways to get the
+//! answer:
+//!
+//! - **Enumeration**: Visit every possible draw (exact, small games only)
+//! - **Inclusion–exclusion**: Sum over subsets of tickets, counting the draws
+//!   that hit every ticket of a subset from the Venn regions of their
+//!   `TicketKey` bitmaps (exact, small batches only)
+//! - **Monte Carlo**: Sample random draws and report a 95% confidence interval
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallRange, PickCount, Ticket};
+use crate::probability::{Probability, combination, combination_big};
+use crate::rng::RandomNumberGenerator;
+use crate::ticket_bitwise::BitwiseStrategy;
+use crate::ticket_key::TicketKey;
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of draws visited by exact enumeration.
+pub const ENUMERATION_LIMIT: u128 = 1_000_000;
+
+/// Maximum number of unique tickets handled by inclusion–exclusion.
+pub const INCLUSION_EXCLUSION_LIMIT: usize = 8;
+
+/// Upper bound on the work of inclusion–exclusion, estimated as
+/// `(min_matches + 2) ^ tickets` dynamic-programming states.
+pub const INCLUSION_EXCLUSION_BUDGET: u128 = 50_000_000;
+
+/// Number of sampled draws used by the automatic Monte Carlo fallback.
+pub const DEFAULT_SAMPLES: usize = 100_000;
+
+/// Method used to compute a batch win probability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMethod {
+    /// Jackpot tier: the events are disjoint, so the probabilities add up
+    Disjoint,
+    /// Exact enumeration over every possible draw
+    Enumeration,
+    /// Exact inclusion–exclusion over ticket subsets
+    InclusionExclusion,
+    /// Random sampling of draws
+    MonteCarlo {
+        /// Number of sampled draws
+        samples: usize,
+    },
+}
+
+/// Probability that at least one ticket of a batch wins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchWinProbability {
+    method: BatchMethod,
+    exact: Option<(BigUint, BigUint)>,
+    estimate: f64,
+    lower: f64,
+    upper: f64,
+}
+
+impl BatchWinProbability {
+    fn exact(method: BatchMethod, favorable: BigUint, total: BigUint) -> Self {
+        let estimate = favorable.to_f64() / total.to_f64();
+        Self {
+            method,
+            exact: Some((favorable, total)),
+            estimate,
+            lower: estimate,
+            upper: estimate,
+        }
+    }
+
+    /// Get the method used to compute the probability.
+    pub fn method(&self) -> BatchMethod {
+        self.method
+    }
+
+    /// Get the exact probability as (favorable_draws, total_draws).
+    ///
+    /// Returns None for Monte Carlo estimates.
+    pub fn exact_odds(&self) -> Option<(&BigUint, &BigUint)> {
+        self.exact
+            .as_ref()
+            .map(|(favorable, total)| (favorable, total))
+    }
+
+    /// Get the exact probability, if it was computed exactly.
+    pub fn exact_probability(&self) -> Option<Probability> {
+        self.exact
+            .as_ref()
+            .map(|(favorable, total)| Probability::reduced(favorable.clone(), total.clone()))
+    }
+
+    /// Get the probability (exact or estimated).
+    pub fn probability(&self) -> f64 {
+        self.estimate
+    }
+
+    /// Get the 95% confidence interval as (lower, upper).
+    ///
+    /// Both bounds equal the probability for exact methods.
+    pub fn bounds(&self) -> (f64, f64) {
+        (self.lower, self.upper)
+    }
+}
+
+/// Calculate the probability that at least one ticket matches `min_matches`
+/// or more balls, choosing the best available method.
+///
+/// The jackpot tier is solved directly. Otherwise exact enumeration is used
+/// when the game has at most [`ENUMERATION_LIMIT`] draws, inclusion–exclusion
+/// when the batch has at most [`INCLUSION_EXCLUSION_LIMIT`] unique tickets and
+/// fits the [`INCLUSION_EXCLUSION_BUDGET`], and Monte Carlo with [`DEFAULT_SAMPLES`] draws for everything else.
+///
+/// Duplicate tickets are ignored, since they cannot add new winning draws.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator (only used by the Monte Carlo fallback)
+/// * `tickets` - The ticket batch
+/// * `range` - The range of ball numbers
+/// * `pick` - Number of balls per ticket and per draw
+/// * `min_matches` - Minimum number of matched balls to win
+///
+/// # Errors
+///
+/// Returns `LottoError::InvalidMatchCount` if `min_matches` exceeds the pick
+/// size, or a ticket validation error if a ticket does not fit the game.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::batch::{BatchMethod, batch_win_probability};
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let tickets = vec![
+///     Ticket::new((1..=6).map(BallNumber::new).collect()),
+///     Ticket::new((7..=12).map(BallNumber::new).collect()),
+/// ];
+///
+/// let mut rng = rand::rng();
+/// let result = batch_win_probability(&mut rng, &tickets, &range, &pick, 6).unwrap();
+/// assert_eq!(result.method(), BatchMethod::Disjoint);
+/// let (favorable, total) = result.exact_odds().unwrap();
+/// assert_eq!((favorable.to_u128(), total.to_u128()), (Some(2), Some(50_063_860)));
+/// ```
+pub fn batch_win_probability<R: RandomNumberGenerator>(
+    rng: &mut R,
+    tickets: &[Ticket],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+) -> Result<BatchWinProbability> {
+    let keys = unique_keys(tickets, range, pick, min_matches)?;
+    let total = combination_big(range.size(), pick.value());
+
+    if keys.is_empty() {
+        // No tickets win nothing, whatever the tier
+        return Ok(BatchWinProbability::exact(
+            BatchMethod::Disjoint,
+            BigUint::zero(),
+            total,
+        ));
+    }
+    if min_matches == 0 {
+        return Ok(BatchWinProbability::exact(
+            BatchMethod::Disjoint,
+            total.clone(),
+            total,
+        ));
+    }
+    if min_matches == pick.value() {
+        return Ok(BatchWinProbability::exact(
+            BatchMethod::Disjoint,
+            BigUint::from(keys.len()),
+            total,
+        ));
+    }
+    if enumeration_fits(&total) {
+        let favorable = count_by_enumeration(&keys, range, pick.value(), min_matches);
+        return Ok(BatchWinProbability::exact(
+            BatchMethod::Enumeration,
+            BigUint::from(favorable),
+            total,
+        ));
+    }
+    if inclusion_exclusion_fits(keys.len(), min_matches) {
+        // Counts beyond u128 fall through to sampling
+        match count_by_inclusion_exclusion(&keys, range, pick.value(), min_matches) {
+            Ok(favorable) => {
+                return Ok(BatchWinProbability::exact(
+                    BatchMethod::InclusionExclusion,
+                    BigUint::from(favorable),
+                    total,
+                ));
+            }
+            Err(LottoError::CalculationOverflow { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    estimate_by_sampling(rng, &keys, range, pick, min_matches, DEFAULT_SAMPLES)
+}
+
+/// Calculate the exact batch win probability by visiting every draw.
+///
+/// # Errors
+///
+/// Returns `LottoError::CalculationOverflow` if the game has more than
+/// [`ENUMERATION_LIMIT`] possible draws.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::batch::batch_win_probability_by_enumeration;
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::new(BallNumber::new(1), BallNumber::new(10)).unwrap();
+/// let pick = PickCount::new(3, &range).unwrap();
+/// let tickets = vec![Ticket::new(vec![
+///     BallNumber::new(1),
+///     BallNumber::new(2),
+///     BallNumber::new(3),
+/// ])];
+///
+/// let result = batch_win_probability_by_enumeration(&tickets, &range, &pick, 2).unwrap();
+/// let (favorable, total) = result.exact_odds().unwrap();
+/// assert_eq!((favorable.to_u128(), total.to_u128()), (Some(22), Some(120)));
+/// ```
+pub fn batch_win_probability_by_enumeration(
+    tickets: &[Ticket],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+) -> Result<BatchWinProbability> {
+    let keys = unique_keys(tickets, range, pick, min_matches)?;
+    let total = combination_big(range.size(), pick.value());
+
+    if !enumeration_fits(&total) {
+        return Err(LottoError::CalculationOverflow {
+            operation: format!(
+                "enumeration of {} draws exceeds limit of {}",
+                total, ENUMERATION_LIMIT
+            ),
+        });
+    }
+
+    let favorable = count_by_enumeration(&keys, range, pick.value(), min_matches);
+    Ok(BatchWinProbability::exact(
+        BatchMethod::Enumeration,
+        BigUint::from(favorable),
+        total,
+    ))
+}
+
+/// Calculate the exact batch win probability by inclusion–exclusion.
+///
+/// # Errors
+///
+/// Returns `LottoError::CalculationOverflow` if the batch has more than
+/// [`INCLUSION_EXCLUSION_LIMIT`] unique tickets or exceeds the
+/// [`INCLUSION_EXCLUSION_BUDGET`].
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::batch::batch_win_probability_by_inclusion_exclusion;
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let tickets = vec![Ticket::new((1..=6).map(BallNumber::new).collect())];
+///
+/// // A single ticket gives the plain "at least 5" odds: 325 / C(60, 6)
+/// let result =
+///     batch_win_probability_by_inclusion_exclusion(&tickets, &range, &pick, 5).unwrap();
+/// let (favorable, _total) = result.exact_odds().unwrap();
+/// assert_eq!(favorable.to_u128(), Some(325));
+/// ```
+pub fn batch_win_probability_by_inclusion_exclusion(
+    tickets: &[Ticket],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+) -> Result<BatchWinProbability> {
+    let keys = unique_keys(tickets, range, pick, min_matches)?;
+    let total = combination_big(range.size(), pick.value());
+
+    if !inclusion_exclusion_fits(keys.len(), min_matches) {
+        return Err(LottoError::CalculationOverflow {
+            operation: format!(
+                "inclusion-exclusion over {} tickets with {} matches exceeds limits",
+                keys.len(),
+                min_matches
+            ),
+        });
+    }
+
+    let favorable = count_by_inclusion_exclusion(&keys, range, pick.value(), min_matches)?;
+    Ok(BatchWinProbability::exact(
+        BatchMethod::InclusionExclusion,
+        BigUint::from(favorable),
+        total,
+    ))
+}
+
+/// Estimate the batch win probability by sampling random draws.
+///
+/// The confidence bounds are a 95% Wilson score interval.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::batch::batch_win_probability_by_sampling;
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::new(BallNumber::new(1), BallNumber::new(10)).unwrap();
+/// let pick = PickCount::new(3, &range).unwrap();
+/// let tickets = vec![Ticket::new(vec![
+///     BallNumber::new(1),
+///     BallNumber::new(2),
+///     BallNumber::new(3),
+/// ])];
+///
+/// let mut rng = rand::rng();
+/// let result =
+///     batch_win_probability_by_sampling(&mut rng, &tickets, &range, &pick, 2, 10_000).unwrap();
+/// let (lower, upper) = result.bounds();
+/// assert!(lower <= result.probability() && result.probability() <= upper);
+/// ```
+pub fn batch_win_probability_by_sampling<R: RandomNumberGenerator>(
+    rng: &mut R,
+    tickets: &[Ticket],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+    samples: usize,
+) -> Result<BatchWinProbability> {
+    let keys = unique_keys(tickets, range, pick, min_matches)?;
+    estimate_by_sampling(rng, &keys, range, pick, min_matches, samples)
+}
+
+/// Check that enumeration stays within [`ENUMERATION_LIMIT`] draws.
+fn enumeration_fits(total: &BigUint) -> bool {
+    total
+        .to_u128()
+        .is_some_and(|total| total <= ENUMERATION_LIMIT)
+}
+
+/// Check that inclusion–exclusion stays within the ticket limit and budget.
+fn inclusion_exclusion_fits(tickets: usize, min_matches: usize) -> bool {
+    tickets <= INCLUSION_EXCLUSION_LIMIT
+        && (min_matches as u128 + 2)
+            .checked_pow(tickets as u32)
+            .is_some_and(|states| states <= INCLUSION_EXCLUSION_BUDGET)
+}
+
+/// Validate the batch and convert it to unique ticket bitmaps.
+fn unique_keys(
+    tickets: &[Ticket],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+) -> Result<Vec<TicketKey>> {
+    if min_matches > pick.value() {
+        return Err(LottoError::InvalidMatchCount {
+            match_count: min_matches,
+            pick_count: pick.value(),
+        });
+    }
+
+    let mut seen = HashSet::with_capacity(tickets.len());
+    let mut keys = Vec::with_capacity(tickets.len());
+    for ticket in tickets {
+        let ticket = Ticket::try_new(ticket.balls().to_vec(), range, pick)?;
+        let key = TicketKey::from_balls(ticket.balls(), range);
+        if seen.insert(key) {
+            keys.push(key);
+        }
+    }
+    Ok(keys)
+}
+
+fn any_wins(keys: &[TicketKey], draw: &TicketKey, min_matches: usize) -> bool {
+    keys.iter().any(|key| key.matches(draw) >= min_matches)
+}
+
+/// Count winning draws by visiting every combination of `pick` offsets.
+fn count_by_enumeration(
+    keys: &[TicketKey],
+    range: &BallRange,
+    pick: usize,
+    min_matches: usize,
+) -> u128 {
+    fn visit(
+        keys: &[TicketKey],
+        size: usize,
+        next: usize,
+        remaining: usize,
+        draw: &mut TicketKey,
+        min_matches: usize,
+        favorable: &mut u128,
+    ) {
+        if remaining == 0 {
+            if any_wins(keys, draw, min_matches) {
+                *favorable += 1;
+            }
+            return;
+        }
+        for offset in next..=(size - remaining) {
+            draw.toggle(offset);
+            visit(
+                keys,
+                size,
+                offset + 1,
+                remaining - 1,
+                draw,
+                min_matches,
+                favorable,
+            );
+            draw.toggle(offset);
+        }
+    }
+
+    let mut favorable = 0;
+    let mut draw = TicketKey::from_balls(&[], range);
+    visit(
+        keys,
+        range.size(),
+        0,
+        pick,
+        &mut draw,
+        min_matches,
+        &mut favorable,
+    );
+    favorable
+}
+
+/// Count winning draws as the alternating sum, over every non-empty subset
+/// of tickets, of the draws that hit all tickets of the subset.
+fn count_by_inclusion_exclusion(
+    keys: &[TicketKey],
+    range: &BallRange,
+    pick: usize,
+    min_matches: usize,
+) -> Result<u128> {
+    let overflow = || LottoError::CalculationOverflow {
+        operation: "inclusion-exclusion sum".to_string(),
+    };
+
+    let mut sum: i128 = 0;
+    for subset in 1u32..(1u32 << keys.len()) {
+        let members: Vec<&TicketKey> = (0..keys.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .map(|i| &keys[i])
+            .collect();
+        let count = i128::try_from(count_hitting_all(&members, range, pick, min_matches)?)
+            .map_err(|_| overflow())?;
+
+        sum = if members.len() % 2 == 1 {
+            sum.checked_add(count)
+        } else {
+            sum.checked_sub(count)
+        }
+        .ok_or_else(overflow)?;
+    }
+
+    u128::try_from(sum).map_err(|_| overflow())
+}
+
+/// Count draws that match at least `min_matches` balls of every given ticket.
+///
+/// Balls are grouped into Venn regions by which tickets contain them. A
+/// dynamic program then distributes the `pick` drawn balls over the regions,
+/// tracking each ticket's match count (capped at `min_matches`).
+fn count_hitting_all(
+    members: &[&TicketKey],
+    range: &BallRange,
+    pick: usize,
+    min_matches: usize,
+) -> Result<u128> {
+    let mut region_sizes: HashMap<u32, usize> = HashMap::new();
+    for ball in range.iter() {
+        let mask = members
+            .iter()
+            .enumerate()
+            .filter(|(_, key)| key.contains(ball, range))
+            .fold(0u32, |mask, (i, _)| mask | (1 << i));
+        *region_sizes.entry(mask).or_insert(0) += 1;
+    }
+    let outside = region_sizes.remove(&0).unwrap_or(0);
+
+    // State: (matches per ticket, capped) and number of balls drawn so far.
+    // Balls are u8, so every count fits in a byte.
+    type State = ([u8; INCLUSION_EXCLUSION_LIMIT], u8);
+    let min_matches = min_matches as u8;
+    let mut states: HashMap<State, u128> = HashMap::new();
+    states.insert(([0; INCLUSION_EXCLUSION_LIMIT], 0), 1);
+
+    for (&mask, &size) in &region_sizes {
+        let ways_to_take = (0..=size.min(pick))
+            .map(|take| combination(size, take))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut next_states: HashMap<State, u128> = HashMap::with_capacity(states.len());
+        for ((matches, drawn), ways) in states {
+            for (take, &choices) in ways_to_take
+                .iter()
+                .enumerate()
+                .take(pick - drawn as usize + 1)
+            {
+                let mut next = matches;
+                for (i, count) in next.iter_mut().enumerate().take(members.len()) {
+                    if mask & (1 << i) != 0 {
+                        *count = count.saturating_add(take as u8).min(min_matches);
+                    }
+                }
+                let step = mul_checked(ways, choices)?;
+                let entry = next_states.entry((next, drawn + take as u8)).or_insert(0);
+                *entry = add_checked(*entry, step)?;
+            }
+        }
+        states = next_states;
+    }
+
+    let mut total = 0u128;
+    for ((matches, drawn), ways) in states {
+        if matches[..members.len()].iter().all(|&m| m >= min_matches) {
+            let rest = combination(outside, pick - drawn as usize)?;
+            total = add_checked(total, mul_checked(ways, rest)?)?;
+        }
+    }
+    Ok(total)
+}
+
+fn estimate_by_sampling<R: RandomNumberGenerator>(
+    rng: &mut R,
+    keys: &[TicketKey],
+    range: &BallRange,
+    pick: &PickCount,
+    min_matches: usize,
+    samples: usize,
+) -> Result<BatchWinProbability> {
+    let strategy = BitwiseStrategy::select(range)?;
+    let mut hits = 0usize;
+    for _ in 0..samples {
+        let draw = strategy.generate(range, *pick, rng)?;
+        if any_wins(keys, &draw, min_matches) {
+            hits += 1;
+        }
+    }
+
+    let (estimate, lower, upper) = wilson_interval(hits, samples);
+    Ok(BatchWinProbability {
+        method: BatchMethod::MonteCarlo { samples },
+        exact: None,
+        estimate,
+        lower,
+        upper,
+    })
+}
+
+/// 95% Wilson score interval for `hits` successes out of `samples` trials.
+fn wilson_interval(hits: usize, samples: usize) -> (f64, f64, f64) {
+    if samples == 0 {
+        return (0.0, 0.0, 1.0);
+    }
+    const Z: f64 = 1.96;
+    let n = samples as f64;
+    let p = hits as f64 / n;
+    let denominator = 1.0 + Z * Z / n;
+    let center = (p + Z * Z / (2.0 * n)) / denominator;
+    let margin = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt() / denominator;
+    (p, (center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+fn mul_checked(a: u128, b: u128) -> Result<u128> {
+    a.checked_mul(b)
+        .ok_or_else(|| LottoError::CalculationOverflow {
+            operation: format!("batch draw count: {} * {}", a, b),
+        })
+}
+
+fn add_checked(a: u128, b: u128) -> Result<u128> {
+    a.checked_add(b)
+        .ok_or_else(|| LottoError::CalculationOverflow {
+            operation: format!("batch draw count: {} + {}", a, b),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newtypes::BallNumber;
+
+    fn ticket(values: &[u8]) -> Ticket {
+        Ticket::new(values.iter().copied().map(BallNumber::new).collect())
+    }
+
+    fn small_game() -> (BallRange, PickCount) {
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(12)).unwrap();
+        let pick = PickCount::new(4, &range).unwrap();
+        (range, pick)
+    }
+
+    #[test]
+    fn test_jackpot_is_disjoint_sum() {
+        let range = BallRange::mega_sena();
+        let pick = PickCount::new(6, &range).unwrap();
+        let tickets = vec![
+            ticket(&[1, 2, 3, 4, 5, 6]),
+            ticket(&[1, 2, 3, 4, 5, 7]),
+            ticket(&[1, 2, 3, 4, 5, 6]), // Duplicate
+        ];
+
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &tickets, &range, &pick, 6).unwrap();
+        assert_eq!(result.method(), BatchMethod::Disjoint);
+        let (favorable, total) = result.exact_odds().unwrap();
+        assert_eq!(favorable.to_u128(), Some(2));
+        assert_eq!(total.to_u128(), Some(50_063_860));
+        assert_eq!(
+            result.exact_probability(),
+            Some(Probability::new(1u32, 25_031_930u32).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_single_ticket_matches_cumulative_odds() {
+        let (range, pick) = small_game();
+        let tickets = vec![ticket(&[1, 2, 3, 4])];
+        let result = batch_win_probability_by_enumeration(&tickets, &range, &pick, 2).unwrap();
+
+        let table = crate::probability::match_distribution(12, 4).unwrap();
+        assert_eq!(
+            result.exact_odds(),
+            Some((table[2].at_least_favorable(), table[2].total()))
+        );
+    }
+
+    #[test]
+    fn test_inclusion_exclusion_agrees_with_enumeration() {
+        let (range, pick) = small_game();
+        let tickets = vec![
+            ticket(&[1, 2, 3, 4]),
+            ticket(&[3, 4, 5, 6]),
+            ticket(&[1, 6, 9, 12]),
+            ticket(&[7, 8, 9, 10]),
+        ];
+
+        for min_matches in 1..=4 {
+            let enumerated =
+                batch_win_probability_by_enumeration(&tickets, &range, &pick, min_matches).unwrap();
+            let included =
+                batch_win_probability_by_inclusion_exclusion(&tickets, &range, &pick, min_matches)
+                    .unwrap();
+            assert_eq!(enumerated.exact_odds(), included.exact_odds());
+        }
+    }
+
+    #[test]
+    fn test_overlapping_tickets_are_not_double_counted() {
+        let (range, pick) = small_game();
+        let one = batch_win_probability_by_enumeration(&[ticket(&[1, 2, 3, 4])], &range, &pick, 2)
+            .unwrap()
+            .exact_odds()
+            .unwrap()
+            .0
+            .to_u128()
+            .unwrap();
+        let two = batch_win_probability_by_enumeration(
+            &[ticket(&[1, 2, 3, 4]), ticket(&[1, 2, 3, 5])],
+            &range,
+            &pick,
+            2,
+        )
+        .unwrap()
+        .exact_odds()
+        .unwrap()
+        .0
+        .to_u128()
+        .unwrap();
+
+        assert!(two > one);
+        assert!(two < 2 * one);
+    }
+
+    #[test]
+    fn test_sampling_bounds_contain_exact_value() {
+        let (range, pick) = small_game();
+        let tickets = vec![ticket(&[1, 2, 3, 4]), ticket(&[5, 6, 7, 8])];
+        let exact = batch_win_probability_by_enumeration(&tickets, &range, &pick, 2).unwrap();
+
+        let mut rng = rand::rng();
+        let sampled =
+            batch_win_probability_by_sampling(&mut rng, &tickets, &range, &pick, 2, 20_000)
+                .unwrap();
+        let (lower, upper) = sampled.bounds();
+        // Allow a small slack so the test is not flaky at the 95% level
+        assert!(lower - 0.02 <= exact.probability() && exact.probability() <= upper + 0.02);
+        assert_eq!(
+            sampled.method(),
+            BatchMethod::MonteCarlo { samples: 20_000 }
+        );
+    }
+
+    #[test]
+    fn test_auto_uses_inclusion_exclusion_for_large_games() {
+        let range = BallRange::mega_sena();
+        let pick = PickCount::new(6, &range).unwrap();
+        let tickets = vec![ticket(&[1, 2, 3, 4, 5, 6]), ticket(&[4, 5, 6, 7, 8, 9])];
+
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &tickets, &range, &pick, 4).unwrap();
+        assert_eq!(result.method(), BatchMethod::InclusionExclusion);
+        // Two disjoint-ish tickets: strictly between one and two times the single odds
+        let single = 21_790u128;
+        let favorable = result.exact_odds().unwrap().0.to_u128().unwrap();
+        assert!(favorable > single && favorable < 2 * single);
+    }
+
+    #[test]
+    fn test_auto_falls_back_to_sampling_when_budget_exceeded() {
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(25)).unwrap();
+        let pick = PickCount::new(15, &range).unwrap();
+        let tickets: Vec<Ticket> = (0..8u8)
+            .map(|shift| {
+                ticket(
+                    &(1..=15)
+                        .map(|b| (b + shift - 1) % 25 + 1)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        assert!(!inclusion_exclusion_fits(8, 11));
+        let result = batch_win_probability_by_inclusion_exclusion(&tickets, &range, &pick, 11);
+        assert!(matches!(
+            result,
+            Err(LottoError::CalculationOverflow { .. })
+        ));
+
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &tickets, &range, &pick, 11).unwrap();
+        assert!(matches!(result.method(), BatchMethod::MonteCarlo { .. }));
+    }
+
+    #[test]
+    fn test_jackpot_beyond_u128() {
+        let range = BallRange::new(BallNumber::new(0), BallNumber::new(255)).unwrap();
+        let pick = PickCount::new(127, &range).unwrap();
+        let tickets = vec![ticket(&(0..127).collect::<Vec<_>>())];
+
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &tickets, &range, &pick, 127).unwrap();
+        let (favorable, total) = result.exact_odds().unwrap();
+        assert_eq!(favorable, &BigUint::one());
+        assert_eq!(total, &combination_big(256, 127));
+    }
+
+    #[test]
+    fn test_empty_batch_never_wins() {
+        let (range, pick) = small_game();
+        let mut rng = rand::rng();
+
+        for min_matches in [0, 2, 4] {
+            let result = batch_win_probability(&mut rng, &[], &range, &pick, min_matches).unwrap();
+            assert_eq!(result.probability(), 0.0);
+            assert_eq!(result.exact_odds().unwrap().0, &BigUint::zero());
+        }
+    }
+
+    #[test]
+    fn test_invalid_match_count() {
+        let (range, pick) = small_game();
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &[ticket(&[1, 2, 3, 4])], &range, &pick, 5);
+        assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
+    }
+
+    #[test]
+    fn test_invalid_ticket_rejected() {
+        let (range, pick) = small_game();
+        let mut rng = rand::rng();
+        let result = batch_win_probability(&mut rng, &[ticket(&[1, 2, 3, 40])], &range, &pick, 2);
+        assert!(matches!(result, Err(LottoError::BallOutOfRange { .. })));
+    }
+}
@@ -0,0 +1,418 @@
+//! Betslip grid layouts and geometric pattern filters.
+//!
+//! Official slips lay the numbers out in a grid, filled row by row. Tickets
+//! that form a line, a diagonal or crowd a single column are both popular
+//! with players and unwanted by anyone trying to avoid shared jackpots.
+//! This module describes the grid, filters tickets by their shape on it
+//! and renders a ticket on its slip.
+
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, Ticket};
+use std::collections::HashMap;
+
+/// Grid layout of a betslip: the range filled row by row, `columns` wide.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::betslip::BetslipLayout;
+/// use lotto_quick_pick::newtypes::BallNumber;
+///
+/// let slip = BetslipLayout::mega_sena();
+/// assert_eq!((slip.rows(), slip.columns()), (6, 10));
+/// assert_eq!(slip.position(BallNumber::new(23)), Some((2, 2)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BetslipLayout {
+    range: BallRange,
+    columns: usize,
+}
+
+impl BetslipLayout {
+    /// Create a new BetslipLayout.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of ball numbers on the slip
+    /// * `columns` - Number of balls per row
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidBetslipLayout` if `columns` is zero or wider than the range.
+    pub fn new(range: &BallRange, columns: usize) -> Result<Self> {
+        if columns == 0 || columns > range.size() {
+            return Err(LottoError::InvalidBetslipLayout {
+                columns,
+                size: range.size(),
+            });
+        }
+        Ok(Self {
+            range: *range,
+            columns,
+        })
+    }
+
+    /// Mega-Sena slip: 01-60 in 6 rows of 10.
+    pub fn mega_sena() -> Self {
+        Self {
+            range: BallRange::mega_sena(),
+            columns: 10,
+        }
+    }
+
+    /// Lotofácil slip: 01-25 in 5 rows of 5.
+    pub fn lotofacil() -> Self {
+        Self {
+            range: BallRange::new(BallNumber::new(1), BallNumber::new(25))
+                .expect("1-25 is a valid range"),
+            columns: 5,
+        }
+    }
+
+    /// Lotomania slip: 00-99 in 10 rows of 10.
+    pub fn lotomania() -> Self {
+        Self {
+            range: BallRange::lotomania(),
+            columns: 10,
+        }
+    }
+
+    /// Get the layout for a range: a known slip when one matches, otherwise
+    /// rows of 10 (or a single row for ranges of 10 balls or fewer).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::betslip::BetslipLayout;
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange};
+    ///
+    /// let range = BallRange::new(BallNumber::new(1), BallNumber::new(25)).unwrap();
+    /// assert_eq!(BetslipLayout::for_range(&range), BetslipLayout::lotofacil());
+    /// ```
+    pub fn for_range(range: &BallRange) -> Self {
+        [Self::mega_sena(), Self::lotofacil(), Self::lotomania()]
+            .into_iter()
+            .find(|layout| layout.range == *range)
+            .unwrap_or(Self {
+                range: *range,
+                columns: range.size().min(10),
+            })
+    }
+
+    /// Get the ball range.
+    pub fn range(&self) -> &BallRange {
+        &self.range
+    }
+
+    /// Get the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Get the number of rows (the last one may be partially filled).
+    pub fn rows(&self) -> usize {
+        self.range.size().div_ceil(self.columns)
+    }
+
+    /// Get the (row, column) of a ball, or `None` if it is not on the slip.
+    pub fn position(&self, ball: BallNumber) -> Option<(usize, usize)> {
+        if !self.range.contains(ball) {
+            return None;
+        }
+        let offset = (ball.value() - self.range.start().value()) as usize;
+        Some((offset / self.columns, offset % self.columns))
+    }
+
+    /// Render a ticket on the slip, marking its balls as `[NN]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::betslip::BetslipLayout;
+    /// use lotto_quick_pick::newtypes::{BallNumber, Ticket};
+    ///
+    /// let ticket = Ticket::new([1, 12, 23, 34, 45, 56].map(BallNumber::new).to_vec());
+    /// let slip = BetslipLayout::mega_sena().render(&ticket);
+    /// assert!(slip.starts_with("[01] 02 "));
+    /// assert_eq!(slip.lines().count(), 6);
+    /// ```
+    pub fn render(&self, ticket: &Ticket) -> String {
+        let mut out = String::new();
+        for row in 0..self.rows() {
+            let cells: Vec<String> = (0..self.columns)
+                .filter_map(|column| self.ball_at(row, column))
+                .map(|ball| {
+                    if ticket.contains(&ball) {
+                        format!("[{}]", ball)
+                    } else {
+                        format!(" {} ", ball)
+                    }
+                })
+                .collect();
+            out.push_str(cells.join("").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    fn ball_at(&self, row: usize, column: usize) -> Option<BallNumber> {
+        let offset = row * self.columns + column;
+        (offset < self.range.size())
+            .then(|| BallNumber::new(self.range.start().value() + offset as u8))
+    }
+}
+
+/// Geometric constraints on how a ticket may look on its slip.
+///
+/// Every limit is optional; an empty filter allows every ticket.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::betslip::{BetslipLayout, PatternFilter};
+/// use lotto_quick_pick::newtypes::{BallNumber, Ticket};
+///
+/// let slip = BetslipLayout::mega_sena();
+/// let filter = PatternFilter::new().with_max_per_row(2).with_no_straight_line(3);
+///
+/// let column = Ticket::new([3, 13, 23, 34, 45, 56].map(BallNumber::new).to_vec());
+/// let spread = Ticket::new([3, 15, 27, 32, 48, 59].map(BallNumber::new).to_vec());
+/// assert!(!filter.allows(&slip, &column)); // 03-13-23 is a vertical line
+/// assert!(filter.allows(&slip, &spread));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct PatternFilter {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_per_row: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_per_column: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_per_diagonal: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    max_line_length: Option<usize>,
+}
+
+impl PatternFilter {
+    /// Create a filter that allows every ticket.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow at most `max` balls in any row.
+    pub fn with_max_per_row(mut self, max: usize) -> Self {
+        self.max_per_row = Some(max);
+        self
+    }
+
+    /// Allow at most `max` balls in any column.
+    pub fn with_max_per_column(mut self, max: usize) -> Self {
+        self.max_per_column = Some(max);
+        self
+    }
+
+    /// Allow at most `max` balls on any diagonal, in either direction.
+    pub fn with_max_per_diagonal(mut self, max: usize) -> Self {
+        self.max_per_diagonal = Some(max);
+        self
+    }
+
+    /// Reject `length` or more adjacent balls in a straight line:
+    /// horizontal, vertical or diagonal.
+    ///
+    /// A single ball is not a line, so lengths below 2 leave the filter as it is.
+    pub fn with_no_straight_line(mut self, length: usize) -> Self {
+        if length >= 2 {
+            self.max_line_length = Some(length - 1);
+        }
+        self
+    }
+
+    /// Check if the filter has no constraints.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check if a ticket satisfies every constraint on the given slip.
+    ///
+    /// Balls outside the slip are ignored.
+    pub fn allows(&self, layout: &BetslipLayout, ticket: &Ticket) -> bool {
+        let cells: Vec<(usize, usize)> = ticket
+            .balls()
+            .iter()
+            .filter_map(|&ball| layout.position(ball))
+            .collect();
+
+        let within = |limit: Option<usize>, key: fn(&(usize, usize)) -> isize| {
+            limit.is_none_or(|max| max_group(&cells, key) <= max)
+        };
+
+        within(self.max_per_row, |&(r, _)| r as isize)
+            && within(self.max_per_column, |&(_, c)| c as isize)
+            && within(self.max_per_diagonal, |&(r, c)| r as isize - c as isize)
+            && within(self.max_per_diagonal, |&(r, c)| (r + c) as isize)
+            && self
+                .max_line_length
+                .is_none_or(|max| longest_line(&cells) <= max)
+    }
+}
+
+/// Size of the largest group of cells sharing the same key.
+fn max_group(cells: &[(usize, usize)], key: fn(&(usize, usize)) -> isize) -> usize {
+    let mut counts = HashMap::new();
+    for cell in cells {
+        *counts.entry(key(cell)).or_insert(0) += 1;
+    }
+    counts.into_values().max().unwrap_or(0)
+}
+
+/// Length of the longest run of adjacent cells along a row, column or diagonal.
+fn longest_line(cells: &[(usize, usize)]) -> usize {
+    let marked = |r: isize, c: isize| r >= 0 && c >= 0 && cells.contains(&(r as usize, c as usize));
+
+    let mut longest = 0;
+    for &(r, c) in cells {
+        let (r, c) = (r as isize, c as isize);
+        for (dr, dc) in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+            // Only count runs from their first cell
+            if marked(r - dr, c - dc) {
+                continue;
+            }
+            let mut length = 1;
+            while marked(r + dr * length as isize, c + dc * length as isize) {
+                length += 1;
+            }
+            longest = longest.max(length);
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(values: &[u8]) -> Ticket {
+        Ticket::new(values.iter().map(|&v| BallNumber::new(v)).collect())
+    }
+
+    #[test]
+    fn test_layout_positions() {
+        let slip = BetslipLayout::mega_sena();
+        assert_eq!(slip.position(BallNumber::new(1)), Some((0, 0)));
+        assert_eq!(slip.position(BallNumber::new(10)), Some((0, 9)));
+        assert_eq!(slip.position(BallNumber::new(60)), Some((5, 9)));
+        assert_eq!(slip.position(BallNumber::new(61)), None);
+
+        let lotomania = BetslipLayout::lotomania();
+        assert_eq!(lotomania.position(BallNumber::new(0)), Some((0, 0)));
+        assert_eq!(lotomania.rows(), 10);
+    }
+
+    #[test]
+    fn test_partial_last_row() {
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(23)).unwrap();
+        let slip = BetslipLayout::new(&range, 10).unwrap();
+        assert_eq!(slip.rows(), 3);
+        let rendered = slip.render(&ticket(&[23]));
+        assert_eq!(rendered.lines().last(), Some(" 21  22 [23]"));
+    }
+
+    #[test]
+    fn test_invalid_layout() {
+        let range = BallRange::mega_sena();
+        assert!(matches!(
+            BetslipLayout::new(&range, 0),
+            Err(LottoError::InvalidBetslipLayout { .. })
+        ));
+        assert!(matches!(
+            BetslipLayout::new(&range, 61),
+            Err(LottoError::InvalidBetslipLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_row_column_and_diagonal_limits() {
+        let slip = BetslipLayout::mega_sena();
+        let row = ticket(&[1, 3, 5, 22, 44, 58]);
+        let column = ticket(&[7, 17, 37, 42, 51, 60]);
+        let diagonal = ticket(&[1, 12, 34, 40, 49, 57]); // 01, 12, 34 on r - c = 0
+        let anti = ticket(&[10, 19, 37, 41, 52, 53]); // 10, 19, 37 on r + c = 9
+
+        assert!(!PatternFilter::new().with_max_per_row(2).allows(&slip, &row));
+        assert!(PatternFilter::new().with_max_per_row(3).allows(&slip, &row));
+        assert!(
+            !PatternFilter::new()
+                .with_max_per_column(2)
+                .allows(&slip, &column)
+        );
+        assert!(
+            !PatternFilter::new()
+                .with_max_per_diagonal(2)
+                .allows(&slip, &diagonal)
+        );
+        assert!(
+            !PatternFilter::new()
+                .with_max_per_diagonal(2)
+                .allows(&slip, &anti)
+        );
+        assert!(
+            PatternFilter::new()
+                .with_max_per_diagonal(3)
+                .allows(&slip, &anti)
+        );
+    }
+
+    #[test]
+    fn test_straight_lines() {
+        let slip = BetslipLayout::mega_sena();
+        let filter = PatternFilter::new().with_no_straight_line(3);
+        assert!(!filter.allows(&slip, &ticket(&[4, 5, 6, 30, 41, 59]))); // horizontal
+        assert!(!filter.allows(&slip, &ticket(&[8, 19, 30, 33, 51, 55]))); // diagonal
+        assert!(!filter.allows(&slip, &ticket(&[9, 18, 27, 41, 43, 60]))); // anti-diagonal
+        // Same row but not adjacent, and no wrap-around between rows
+        assert!(filter.allows(&slip, &ticket(&[4, 6, 8, 30, 41, 59])));
+        assert!(filter.allows(&slip, &ticket(&[9, 10, 11, 33, 45, 57])));
+    }
+
+    #[test]
+    fn test_line_shorter_than_two_is_ignored() {
+        assert!(PatternFilter::new().with_no_straight_line(0).is_empty());
+        assert!(PatternFilter::new().with_no_straight_line(1).is_empty());
+        assert_eq!(
+            PatternFilter::new()
+                .with_no_straight_line(3)
+                .with_no_straight_line(1),
+            PatternFilter::new().with_no_straight_line(3)
+        );
+    }
+
+    #[test]
+    fn test_empty_filter_allows_everything() {
+        let filter = PatternFilter::new();
+        assert!(filter.is_empty());
+        assert!(filter.allows(&BetslipLayout::mega_sena(), &ticket(&[1, 2, 3, 4, 5, 6])));
+    }
+
+    #[test]
+    fn test_filtered_generation() {
+        let slip = BetslipLayout::mega_sena();
+        let filter = PatternFilter::new()
+            .with_max_per_row(1)
+            .with_max_per_column(1);
+        let pick = crate::newtypes::PickCount::new(6, slip.range()).unwrap();
+        let count = crate::newtypes::GameCount::new(20).unwrap();
+        let mut rng = rand::rng();
+
+        let tickets = crate::ticket::generate_unique_tickets_filtered(
+            &mut rng,
+            slip.range(),
+            &pick,
+            &count,
+            |t| filter.allows(&slip, t),
+        )
+        .unwrap();
+        assert!(tickets.iter().all(|t| filter.allows(&slip, t)));
+    }
+}
@@ -0,0 +1,560 @@
+//! Minimal arbitrary-precision unsigned integers.
+//!
+//! `BallRange` allows up to 256 balls, and counts such as C(255, 127) do not
+//! fit in u128. This module provides just enough big-integer arithmetic for
+//! exact combinatorics without pulling in an external dependency.
+//!
+//! # Representation
+//!
+//! Numbers are stored as little-endian `u32` limbs with no trailing zero
+//! limbs, so zero is the empty vector and equality is structural.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::str::FromStr;
+
+/// Arbitrary-precision unsigned integer.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::bigint::BigUint;
+///
+/// let a = BigUint::from(u128::MAX);
+/// let b = &a * &a;
+/// assert_eq!(&b / &a, a);
+/// assert_eq!(BigUint::from(50_063_860u32).to_string(), "50063860");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    /// Create a BigUint equal to zero.
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    /// Create a BigUint equal to one.
+    pub fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+
+    /// Check if the value is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Get the number of significant bits.
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(&top) => self.limbs.len() * 32 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Convert to u128, if the value fits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(42u32).to_u128(), Some(42));
+    /// assert_eq!((BigUint::from(u128::MAX) + BigUint::one()).to_u128(), None);
+    /// ```
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| (acc << 32) | limb as u128),
+        )
+    }
+
+    /// Convert to the nearest f64 (infinity if too large).
+    pub fn to_f64(&self) -> f64 {
+        self.limbs
+            .iter()
+            .rev()
+            .fold(0.0, |acc, &limb| acc * 4_294_967_296.0 + limb as f64)
+    }
+
+    /// Get the little-endian bytes of the value, without trailing zero bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::bigint::BigUint;
+    ///
+    /// let value = BigUint::from(0x01_02_03u32);
+    /// assert_eq!(value.to_le_bytes(), vec![3, 2, 1]);
+    /// assert_eq!(BigUint::from_le_bytes(&[3, 2, 1, 0]), value);
+    /// ```
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self
+            .limbs
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect();
+        while bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        bytes
+    }
+
+    /// Create a BigUint from little-endian bytes.
+    pub fn from_le_bytes(bytes: &[u8]) -> Self {
+        let mut limbs: Vec<u32> = bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut limb = [0u8; 4];
+                limb[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(limb)
+            })
+            .collect();
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    /// Raise to the given power.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::bigint::BigUint;
+    ///
+    /// assert_eq!(BigUint::from(10u32).pow(20).to_string(), "100000000000000000000");
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Subtract, returning None if the result would be negative.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        let mut borrow = 0i64;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let rhs = other.limbs.get(i).copied().unwrap_or(0) as i64;
+            let mut diff = *limb as i64 - rhs - borrow;
+            borrow = if diff < 0 {
+                diff += 1 << 32;
+                1
+            } else {
+                0
+            };
+            *limb = diff as u32;
+        }
+        Some(Self::from_limbs(limbs))
+    }
+
+    /// Divide, returning (quotient, remainder).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the divisor is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "division by zero");
+
+        if *self < *divisor {
+            return (Self::zero(), self.clone());
+        }
+        if let [small] = divisor.limbs[..] {
+            let (quotient, remainder) = self.div_rem_small(small);
+            return (quotient, Self::from(remainder));
+        }
+
+        // Binary long division: the operands are a few hundred bits at most
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = Self::zero();
+        for bit in (0..self.bits()).rev() {
+            remainder = remainder.shl1(self.bit(bit));
+            if remainder >= *divisor {
+                remainder = remainder
+                    .checked_sub(divisor)
+                    .expect("remainder is at least the divisor");
+                quotient[bit / 32] |= 1 << (bit % 32);
+            }
+        }
+        (Self::from_limbs(quotient), remainder)
+    }
+
+    /// Greatest common divisor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::bigint::BigUint;
+    ///
+    /// let gcd = BigUint::from(21_465u32).gcd(&BigUint::from(50_063_860u32));
+    /// assert_eq!(gcd, BigUint::from(5u32));
+    /// ```
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let (_, remainder) = a.div_rem(&b);
+            a = b;
+            b = remainder;
+        }
+        a
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Self { limbs }
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.limbs
+            .get(index / 32)
+            .is_some_and(|limb| limb & (1 << (index % 32)) != 0)
+    }
+
+    /// Shift left by one bit, shifting `low_bit` in.
+    fn shl1(mut self, low_bit: bool) -> Self {
+        let mut carry = low_bit as u32;
+        for limb in &mut self.limbs {
+            let next_carry = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next_carry;
+        }
+        if carry != 0 {
+            self.limbs.push(carry);
+        }
+        self
+    }
+
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut quotient = vec![0u32; self.limbs.len()];
+        let mut remainder = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = (remainder << 32) | limb as u64;
+            quotient[i] = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        (Self::from_limbs(quotient), remainder as u32)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> Self {
+        let mut limbs = Vec::with_capacity(4);
+        while value != 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+        Self { limbs }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(value: u32) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl From<usize> for BigUint {
+    fn from(value: usize) -> Self {
+        Self::from(value as u128)
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = crate::error::LottoError;
+
+    /// Parse a decimal number, such as a rank too large for u128.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(crate::error::LottoError::ParseError(format!(
+                "'{}' is not a non-negative integer",
+                s
+            )));
+        }
+
+        // Fold in base-10^9 chunks from the most significant end
+        let chunk_base = BigUint::from(1_000_000_000u32);
+        let head = s.len() % 9;
+        let mut value = BigUint::zero();
+        let mut chunks =
+            std::iter::once(&s[..head])
+                .filter(|chunk| !chunk.is_empty())
+                .chain(s.as_bytes()[head..].chunks(9).map(|chunk| {
+                    std::str::from_utf8(chunk).expect("ASCII digits are valid UTF-8")
+                }));
+        if let Some(first) = chunks.next() {
+            value = BigUint::from(first.parse::<u32>().expect("validated digits"));
+        }
+        for chunk in chunks {
+            let chunk = BigUint::from(chunk.parse::<u32>().expect("validated digits"));
+            value = &(&value * &chunk_base) + &chunk;
+        }
+        Ok(value)
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+
+        // Peel off base-10^9 chunks from the least significant end
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_small(1_000_000_000);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+
+        let mut digits = chunks.pop().map(|c| c.to_string()).unwrap_or_default();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let sum = self.limbs.get(i).copied().unwrap_or(0) as u64
+                + other.limbs.get(i).copied().unwrap_or(0) as u64
+                + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        if carry != 0 {
+            limbs.push(carry as u32);
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    /// # Panics
+    ///
+    /// Panics if the result would be negative.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let current = limbs[i + j] as u64 + a as u64 * b as u64 + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Div for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+/// Forward operators on owned values to the reference implementations.
+macro_rules! forward_owned_op {
+    ($($trait:ident $method:ident),*) => {
+        $(
+            impl $trait for BigUint {
+                type Output = BigUint;
+
+                fn $method(self, other: BigUint) -> BigUint {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_op!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_u128() {
+        for value in [
+            0u128,
+            1,
+            42,
+            u32::MAX as u128 + 1,
+            u64::MAX as u128,
+            u128::MAX,
+        ] {
+            assert_eq!(BigUint::from(value).to_u128(), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_zero_is_empty() {
+        assert!(BigUint::from(0u32).is_zero());
+        assert_eq!(BigUint::from(0u32), BigUint::zero());
+        assert_eq!(BigUint::zero().to_string(), "0");
+    }
+
+    #[test]
+    fn test_add_with_carry() {
+        let a = BigUint::from(u128::MAX);
+        let sum = &a + &BigUint::one();
+        assert_eq!(sum.bits(), 129);
+        assert_eq!(sum.to_u128(), None);
+        assert_eq!(sum.to_string(), "340282366920938463463374607431768211456");
+    }
+
+    #[test]
+    fn test_sub_and_checked_sub() {
+        let a = BigUint::from(1u128 << 100);
+        let b = BigUint::from(1u32);
+        assert_eq!((&a - &b).to_u128(), Some((1u128 << 100) - 1));
+        assert_eq!(b.checked_sub(&a), None);
+    }
+
+    #[test]
+    fn test_mul_matches_u128() {
+        let a = BigUint::from(123_456_789_012_345u64);
+        let b = BigUint::from(987_654_321u32);
+        assert_eq!(
+            (&a * &b).to_u128(),
+            Some(123_456_789_012_345u128 * 987_654_321)
+        );
+    }
+
+    #[test]
+    fn test_div_rem_large_divisor() {
+        let a = BigUint::from(u128::MAX).pow(2);
+        let b = BigUint::from(u128::MAX - 12_345);
+        let (quotient, remainder) = a.div_rem(&b);
+        assert!(remainder < b);
+        assert_eq!(&(&quotient * &b) + &remainder, a);
+    }
+
+    #[test]
+    fn test_div_rem_small_divisor() {
+        let (quotient, remainder) = BigUint::from(1_000_003u32).div_rem(&BigUint::from(10u32));
+        assert_eq!(quotient, BigUint::from(100_000u32));
+        assert_eq!(remainder, BigUint::from(3u32));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let small = BigUint::from(u64::MAX);
+        let large = BigUint::from(u128::MAX);
+        assert!(small < large);
+        assert!(&large + &BigUint::one() > large);
+        assert_eq!(large.cmp(&large.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_display_pads_inner_chunks() {
+        let value = BigUint::from(10u32).pow(18) + BigUint::from(7u32);
+        assert_eq!(value.to_string(), "1000000000000000007");
+        assert_eq!(format!("{:>5}", BigUint::from(42u32)), "   42");
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(BigUint::from(50_063_860u32).to_f64(), 50_063_860.0);
+        let big = BigUint::from(2u32).pow(200);
+        assert_eq!(big.to_f64(), 2f64.powi(200));
+    }
+
+    #[test]
+    fn test_from_str_round_trips_display() {
+        for text in ["0", "7", "1000000000", "1000000000000000007"] {
+            assert_eq!(text.parse::<BigUint>().unwrap().to_string(), text);
+        }
+        let big = BigUint::from(2u32).pow(200);
+        assert_eq!(big.to_string().parse::<BigUint>().unwrap(), big);
+        assert_eq!("007".parse::<BigUint>().unwrap(), BigUint::from(7u32));
+        assert!("".parse::<BigUint>().is_err());
+        assert!("-1".parse::<BigUint>().is_err());
+        assert!("1e9".parse::<BigUint>().is_err());
+    }
+
+    #[test]
+    fn test_gcd() {
+        let a = BigUint::from(2u32).pow(150) * BigUint::from(3u32);
+        let b = BigUint::from(2u32).pow(140) * BigUint::from(5u32);
+        assert_eq!(a.gcd(&b), BigUint::from(2u32).pow(140));
+    }
+}
@@ -0,0 +1,275 @@
+//! Checking tickets against a draw result.
+//!
+//! Each ticket is compared with the drawn balls to find its hits, and the
+//! batch is summarized by how many tickets reached each number of hits.
+//! Tickets marking more balls than are drawn (multi-number bets) are placed
+//! in the tier of their best simple bet, which is capped at the draw size,
+//! and are paid for every simple bet they contain.
+
+use crate::bigint::BigUint;
+use crate::expected_value::PrizeTable;
+use crate::multi_bet::simple_bet_breakdown;
+use crate::newtypes::{BallNumber, BallRange, Ticket};
+use crate::ticket_key::TicketKey;
+
+/// A ticket compared with a draw result.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::check::CheckedTicket;
+/// use lotto_quick_pick::{BallNumber, Ticket};
+///
+/// let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+/// let result = Ticket::new([1, 8, 15, 30, 42, 60].map(BallNumber::new).to_vec());
+/// let checked = CheckedTicket::new(ticket, &result);
+///
+/// assert_eq!(checked.matches(), 3);
+/// assert!(checked.is_hit(BallNumber::new(8)));
+/// assert!(!checked.is_hit(BallNumber::new(4)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedTicket {
+    ticket: Ticket,
+    hits: Vec<BallNumber>,
+}
+
+impl CheckedTicket {
+    /// Compare a ticket with the drawn balls.
+    pub fn new(ticket: Ticket, result: &Ticket) -> Self {
+        let range = full_range();
+        Self::with_drawn(
+            ticket,
+            &TicketKey::from_balls(result.balls(), &range),
+            &range,
+        )
+    }
+
+    /// Compare a ticket with the key of the drawn balls, built over `range`.
+    fn with_drawn(ticket: Ticket, drawn: &TicketKey, range: &BallRange) -> Self {
+        let hits = ticket
+            .balls()
+            .iter()
+            .copied()
+            .filter(|&ball| drawn.contains(ball, range))
+            .collect();
+        Self { ticket, hits }
+    }
+
+    /// Get the checked ticket.
+    pub fn ticket(&self) -> &Ticket {
+        &self.ticket
+    }
+
+    /// Get the ticket's balls that were drawn, in ascending order.
+    pub fn hits(&self) -> &[BallNumber] {
+        &self.hits
+    }
+
+    /// Get the number of drawn balls on the ticket.
+    pub fn matches(&self) -> usize {
+        self.hits.len()
+    }
+
+    /// Check if a ball of the ticket was drawn.
+    pub fn is_hit(&self, ball: BallNumber) -> bool {
+        self.hits.binary_search(&ball).is_ok()
+    }
+
+    /// Get the prize tier reached: the hits, capped at the draw size.
+    pub fn tier(&self, draw_size: usize) -> usize {
+        self.matches().min(draw_size)
+    }
+
+    /// Count the simple bets of the ticket matching each number of balls.
+    ///
+    /// See [`simple_bet_breakdown`] for the layout of the result; a ticket
+    /// of `draw_size` balls is one simple bet.
+    pub fn simple_bets(&self, draw_size: usize) -> Vec<BigUint> {
+        simple_bet_breakdown(self.ticket.len(), draw_size, self.matches())
+    }
+
+    /// Get the prize won by the ticket, paying each of its simple bets.
+    pub fn winnings(&self, table: &PrizeTable, draw_size: usize) -> f64 {
+        table.bet_payout(self.ticket.len(), self.matches(), draw_size)
+    }
+}
+
+/// Check every ticket against a draw result.
+///
+/// The drawn balls are turned into a [`TicketKey`] once, so each ball of
+/// each ticket is looked up with a single bit test.
+pub fn check_tickets(tickets: &[Ticket], result: &Ticket) -> Vec<CheckedTicket> {
+    let range = full_range();
+    let drawn = TicketKey::from_balls(result.balls(), &range);
+    tickets
+        .iter()
+        .map(|ticket| CheckedTicket::with_drawn(ticket.clone(), &drawn, &range))
+        .collect()
+}
+
+/// Range of every ball, so one draw key serves tickets of any game.
+fn full_range() -> BallRange {
+    BallRange::new(BallNumber::new(0), BallNumber::new(255)).expect("0 is less than 255")
+}
+
+/// How many checked tickets reached each prize tier.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::check::{CheckSummary, check_tickets};
+/// use lotto_quick_pick::expected_value::PrizeTable;
+/// use lotto_quick_pick::{BallNumber, Ticket};
+///
+/// let result = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+/// let tickets = vec![
+///     Ticket::new([4, 8, 15, 16, 23, 60].map(BallNumber::new).to_vec()),
+///     Ticket::new([1, 2, 3, 5, 6, 7].map(BallNumber::new).to_vec()),
+/// ];
+/// let summary = CheckSummary::new(&check_tickets(&tickets, &result), 6);
+///
+/// assert_eq!(summary.count(5), 1);
+/// assert_eq!(summary.count(0), 1);
+/// assert_eq!(summary.best(), Some(5));
+/// let table = PrizeTable::new(5.0, 50_000_000.0).with_tier(5, 50_000.0);
+/// assert_eq!(summary.winnings(&table), 50_000.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckSummary {
+    draw_size: usize,
+    counts: Vec<usize>,
+    simple_bets: Vec<BigUint>,
+}
+
+impl CheckSummary {
+    /// Summarize checked tickets for a draw of `draw_size` balls.
+    pub fn new(checked: &[CheckedTicket], draw_size: usize) -> Self {
+        let mut counts = vec![0; draw_size + 1];
+        let mut simple_bets = vec![BigUint::zero(); draw_size + 1];
+        for ticket in checked {
+            counts[ticket.tier(draw_size)] += 1;
+            for (total, bets) in simple_bets.iter_mut().zip(ticket.simple_bets(draw_size)) {
+                *total = &*total + &bets;
+            }
+        }
+        Self {
+            draw_size,
+            counts,
+            simple_bets,
+        }
+    }
+
+    /// Get the number of tickets checked.
+    pub fn tickets(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Get the number of tickets in the tier of `matches` hits.
+    pub fn count(&self, matches: usize) -> usize {
+        self.counts.get(matches).copied().unwrap_or(0)
+    }
+
+    /// Get the number of simple bets matching `matches` balls, counting
+    /// every simple bet of a multi-number bet.
+    pub fn simple_bets(&self, matches: usize) -> BigUint {
+        self.simple_bets
+            .get(matches)
+            .cloned()
+            .unwrap_or_else(BigUint::zero)
+    }
+
+    /// Get the highest tier reached, if any ticket was checked.
+    pub fn best(&self) -> Option<usize> {
+        self.counts.iter().rposition(|&count| count > 0)
+    }
+
+    /// Get the total prize won by the checked tickets, paying every simple
+    /// bet of a multi-number bet.
+    pub fn winnings(&self, table: &PrizeTable) -> f64 {
+        self.simple_bets
+            .iter()
+            .enumerate()
+            .map(|(matches, bets)| bets.to_f64() * table.payout(matches, self.draw_size))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(balls: &[u8]) -> Ticket {
+        Ticket::new(balls.iter().copied().map(BallNumber::new).collect())
+    }
+
+    #[test]
+    fn test_hits_are_the_shared_balls() {
+        let checked =
+            CheckedTicket::new(ticket(&[1, 2, 3, 4, 5, 6]), &ticket(&[2, 4, 6, 8, 10, 12]));
+
+        assert_eq!(checked.hits(), &[2, 4, 6].map(BallNumber::new));
+        assert_eq!(
+            checked.matches(),
+            checked.ticket().matches(&ticket(&[2, 4, 6, 8, 10, 12]))
+        );
+    }
+
+    #[test]
+    fn test_multi_bet_tier_is_capped_at_draw_size() {
+        let result = ticket(&[1, 2, 3, 4, 5, 6]);
+        let bet = CheckedTicket::new(ticket(&[1, 2, 3, 4, 5, 6, 7, 8]), &result);
+        let summary = CheckSummary::new(std::slice::from_ref(&bet), 6);
+
+        assert_eq!(bet.matches(), 6);
+        assert_eq!(bet.tier(6), 6);
+        assert_eq!(summary.count(6), 1);
+    }
+
+    #[test]
+    fn test_multi_bet_is_paid_for_every_simple_bet() {
+        let result = ticket(&[1, 2, 3, 4, 5, 6]);
+        let tickets = vec![
+            ticket(&[1, 2, 3, 4, 5, 6, 7, 8]),
+            ticket(&[1, 2, 3, 4, 5, 60]),
+        ];
+        let checked = check_tickets(&tickets, &result);
+        let table = PrizeTable::new(5.0, 1_000.0)
+            .with_tier(5, 100.0)
+            .with_tier(4, 10.0);
+
+        // 1 sena, 12 quinas and 15 quadras
+        assert_eq!(checked[0].winnings(&table, 6), 2_350.0);
+        assert_eq!(checked[1].winnings(&table, 6), 100.0);
+
+        let summary = CheckSummary::new(&checked, 6);
+        assert_eq!(summary.simple_bets(5).to_u128(), Some(13));
+        assert_eq!(summary.simple_bets(4).to_u128(), Some(15));
+        assert_eq!(summary.winnings(&table), 2_450.0);
+    }
+
+    #[test]
+    fn test_summary_counts_and_best() {
+        let result = ticket(&[1, 2, 3, 4, 5, 6]);
+        let tickets = vec![
+            ticket(&[1, 2, 3, 10, 11, 12]),
+            ticket(&[1, 2, 3, 13, 14, 15]),
+            ticket(&[20, 21, 22, 23, 24, 25]),
+        ];
+        let summary = CheckSummary::new(&check_tickets(&tickets, &result), 6);
+
+        assert_eq!(summary.tickets(), 3);
+        assert_eq!(summary.count(3), 2);
+        assert_eq!(summary.count(0), 1);
+        assert_eq!(summary.count(7), 0);
+        assert_eq!(summary.best(), Some(3));
+        assert_eq!(summary.winnings(&PrizeTable::new(5.0, 1e6)), 0.0);
+    }
+
+    #[test]
+    fn test_empty_summary() {
+        let summary = CheckSummary::new(&[], 6);
+        assert_eq!(summary.tickets(), 0);
+        assert_eq!(summary.best(), None);
+    }
+}
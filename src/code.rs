@@ -0,0 +1,229 @@
+//! Short ticket codes for sharing tickets by voice or on paper.
+//!
+//! A code is the ticket's combinatorial [rank](crate::rank) within its game,
+//! written in Crockford base32 and followed by a check character (the rank
+//! modulo 37). Every code of a game has the same length, so a dropped or
+//! doubled character is caught along with any single mistyped character
+//! and any swap of two adjacent characters.
+//!
+//! Decoding is forgiving the way Crockford intended: it ignores case and
+//! hyphens, and reads `I`/`L` as `1` and `O` as `0`.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallRange, PickCount, Ticket};
+use crate::probability::combination_big;
+use crate::rank::{rank, unrank};
+
+/// Crockford base32 digits, followed by the five extra check symbols.
+const SYMBOLS: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+/// Get the number of base32 digits (without the check character) of every
+/// code of a game.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::code::code_digits;
+/// use lotto_quick_pick::{BallRange, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// // C(60, 6) = 50,063,860 ranks fit in 26 bits
+/// assert_eq!(code_digits(&range, &pick), 6);
+/// ```
+pub fn code_digits(range: &BallRange, pick: &PickCount) -> usize {
+    let last = combination_big(range.size(), pick.value())
+        .checked_sub(&BigUint::one())
+        .unwrap_or_default();
+    last.bits().div_ceil(5).max(1)
+}
+
+/// Encode a ticket as its code.
+///
+/// # Arguments
+///
+/// * `ticket` - The ticket to encode
+/// * `range` - The range of the ticket's game
+///
+/// # Errors
+///
+/// Returns `BallOutOfRange` if a ball falls outside the range, or
+/// `PickExceedsRange` if the ticket has no balls or more balls than the
+/// range.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::code::{decode, encode};
+/// use lotto_quick_pick::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+///
+/// let code = encode(&ticket, &range).unwrap();
+/// assert_eq!(code, "04A2DV4");
+/// assert_eq!(decode("04a2-dv4", &range, &pick).unwrap(), ticket);
+/// assert!(decode("04A2DV5", &range, &pick).is_err());
+/// ```
+pub fn encode(ticket: &Ticket, range: &BallRange) -> Result<String> {
+    let pick = PickCount::new(ticket.len(), range)?;
+    let value = rank(ticket, range)?;
+
+    let base = BigUint::from(32u32);
+    let mut digits = Vec::with_capacity(code_digits(range, &pick) + 1);
+    let mut rest = value.clone();
+    for _ in 0..code_digits(range, &pick) {
+        let (quotient, digit) = rest.div_rem(&base);
+        digits.push(SYMBOLS[digit.to_u128().unwrap_or(0) as usize]);
+        rest = quotient;
+    }
+    digits.reverse();
+    digits.push(SYMBOLS[check_value(&value)]);
+
+    Ok(String::from_utf8(digits).expect("symbols are ASCII"))
+}
+
+/// Decode a code back into its ticket.
+///
+/// # Arguments
+///
+/// * `code` - The code, in any case and with optional hyphens
+/// * `range` - The range of the ticket's game
+/// * `pick` - Number of balls of the ticket's game
+///
+/// # Errors
+///
+/// Returns `InvalidTicketCode` if the code has the wrong length, a
+/// character outside the alphabet, a wrong check character, or names no
+/// ticket of the game.
+pub fn decode(code: &str, range: &BallRange, pick: &PickCount) -> Result<Ticket> {
+    let invalid = |reason: String| LottoError::InvalidTicketCode {
+        code: code.to_string(),
+        reason,
+    };
+
+    let symbols: Vec<char> = code.chars().filter(|&c| c != '-').collect();
+    let digits = code_digits(range, pick);
+    if symbols.len() != digits + 1 {
+        return Err(invalid(format!(
+            "expected {} characters, but got {}",
+            digits + 1,
+            symbols.len()
+        )));
+    }
+
+    let base = BigUint::from(32u32);
+    let mut value = BigUint::zero();
+    for &symbol in &symbols[..digits] {
+        let digit = symbol_value(symbol)
+            .filter(|&digit| digit < 32)
+            .ok_or_else(|| invalid(format!("'{}' is not a base32 digit", symbol)))?;
+        value = &(&value * &base) + &BigUint::from(digit as u32);
+    }
+
+    let check = symbols[digits];
+    match symbol_value(check) {
+        Some(expected) if expected == check_value(&value) => {}
+        Some(_) => return Err(invalid("check character does not match".to_string())),
+        None => return Err(invalid(format!("'{}' is not a check character", check))),
+    }
+
+    unrank(&value, range, pick).map_err(|e| invalid(e.to_string()))
+}
+
+/// Get the check value of a rank: the rank modulo 37.
+fn check_value(value: &BigUint) -> usize {
+    (value % &BigUint::from(37u32)).to_u128().unwrap_or(0) as usize
+}
+
+/// Get the value of a code symbol, applying Crockford's aliases.
+fn symbol_value(symbol: char) -> Option<usize> {
+    let symbol = match symbol.to_ascii_uppercase() {
+        'O' => '0',
+        'I' | 'L' => '1',
+        other => other,
+    };
+    SYMBOLS.iter().position(|&s| s as char == symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newtypes::{BallNumber, GameCount};
+    use crate::ticket::generate_unique_tickets;
+
+    fn game(start: u8, end: u8, pick: usize) -> (BallRange, PickCount) {
+        let range = BallRange::new(BallNumber::new(start), BallNumber::new(end)).unwrap();
+        let pick = PickCount::new(pick, &range).unwrap();
+        (range, pick)
+    }
+
+    #[test]
+    fn test_round_trip_across_games() {
+        for (start, end, pick) in [(1, 25, 15), (1, 60, 6), (0, 99, 50), (0, 255, 100)] {
+            let (range, pick) = game(start, end, pick);
+            let tickets = generate_unique_tickets(
+                &mut rand::rng(),
+                &range,
+                &pick,
+                &GameCount::new(20).unwrap(),
+            )
+            .unwrap();
+            for ticket in tickets {
+                let code = encode(&ticket, &range).unwrap();
+                assert_eq!(code.len(), code_digits(&range, &pick) + 1);
+                assert_eq!(decode(&code, &range, &pick).unwrap(), ticket);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lowest_ticket_is_all_zeros() {
+        let (range, pick) = game(1, 60, 6);
+        let first = Ticket::new((1..=6).map(BallNumber::new).collect());
+
+        assert_eq!(encode(&first, &range).unwrap(), "0000000");
+        assert_eq!(decode("OOO-OOO0", &range, &pick).unwrap(), first);
+    }
+
+    #[test]
+    fn test_single_typos_and_swaps_are_detected() {
+        let (range, pick) = game(1, 60, 6);
+        let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+        let code: Vec<u8> = encode(&ticket, &range).unwrap().into_bytes();
+
+        for position in 0..code.len() {
+            for &symbol in SYMBOLS.iter() {
+                let mut typo = code.clone();
+                typo[position] = symbol;
+                if typo != code {
+                    let typo = String::from_utf8(typo).unwrap();
+                    assert!(decode(&typo, &range, &pick).is_err(), "{}", typo);
+                }
+            }
+        }
+        for position in 0..code.len() - 1 {
+            let mut swapped = code.clone();
+            swapped.swap(position, position + 1);
+            if swapped != code {
+                let swapped = String::from_utf8(swapped).unwrap();
+                assert!(decode(&swapped, &range, &pick).is_err(), "{}", swapped);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrong_length_and_characters_are_rejected() {
+        let (range, pick) = game(1, 60, 6);
+
+        assert!(matches!(
+            decode("04A2DV", &range, &pick),
+            Err(LottoError::InvalidTicketCode { .. })
+        ));
+        assert!(decode("04A2UV4", &range, &pick).is_err());
+        // Largest 6-digit value is past C(60, 6)
+        assert!(decode("ZZZZZZ3", &range, &pick).is_err());
+    }
+}
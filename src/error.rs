@@ -2,11 +2,17 @@
 //!
 //! This module defines all error variants that can occur during
 //! lottery ticket generation and probability calculations.
+//! Every variant belongs to an [`ErrorCategory`], which gives callers a
+//! stable way to react to a class of failure, and can be rendered as JSON
+//! for machine consumers.
 
+use crate::json::Json;
+use std::fmt;
 use thiserror::Error;
 
 /// Represents all possible errors in the lotto quick pick library.
 #[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LottoError {
     /// The starting number is greater than or equal to the ending number.
     #[error("Start value ({start}) must be less than end value ({end})")]
@@ -53,6 +59,59 @@ pub enum LottoError {
     #[error("Ticket contains duplicate ball: {value}")]
     DuplicateBall { value: u8 },
 
+    /// A monetary amount is negative, not finite, or otherwise unusable.
+    #[error("Invalid {field}: {value}")]
+    InvalidAmount { field: String, value: f64 },
+
+    /// The same match count was given more than one prize.
+    #[error("Prize tier for {matches} matched balls is defined more than once")]
+    DuplicatePrizeTier { matches: usize },
+
+    /// A multi-number bet marks fewer balls than are drawn.
+    #[error("A bet must mark at least {draw_size} balls, but got {bet_size}")]
+    BetTooSmall { bet_size: usize, draw_size: usize },
+
+    /// A betslip grid has no columns or more columns than balls.
+    #[error("Cannot lay out {size} balls in {columns} columns")]
+    InvalidBetslipLayout { columns: usize, size: usize },
+
+    /// A probability has a zero total or more favorable than total outcomes.
+    #[error("Invalid probability: {favorable} favorable out of {total} outcomes")]
+    InvalidProbability { favorable: String, total: String },
+
+    /// A combinatorial rank is not below the number of combinations.
+    #[error("Rank {rank} is out of range for {total} combinations")]
+    RankOutOfRange { rank: String, total: String },
+
+    /// A ticket code is mistyped or names no ticket of the game.
+    #[error("Invalid ticket code '{code}': {reason}")]
+    InvalidTicketCode { code: String, reason: String },
+
+    /// A binary ticket store is malformed, corrupt or cannot be written.
+    #[error("Invalid ticket store: {reason}")]
+    InvalidStore { reason: String },
+
+    /// A line of a ticket file is not a valid ticket.
+    #[error("Line {line}: {source}")]
+    InvalidTicketLine {
+        line: usize,
+        source: Box<LottoError>,
+    },
+
+    /// A ticket file has no tickets, only blank lines, comments or a header.
+    #[error("No tickets found in {path}")]
+    EmptyTicketFile { path: String },
+
+    /// The number of balls drawn is zero or exceeds the available range.
+    #[error("Cannot draw {draw_size} balls from a range of {available} values")]
+    InvalidDrawSize { draw_size: usize, available: usize },
+
+    /// Too few tickets satisfy the slip constraints.
+    #[error(
+        "Only {generated} of {requested} tickets satisfying the slip constraints were found after maximum attempts"
+    )]
+    ConstraintsUnsatisfiable { requested: usize, generated: usize },
+
     /// Input/output error during user interaction.
     #[error("I/O error: {0}")]
     IoError(String),
@@ -64,3 +123,337 @@ pub enum LottoError {
 
 /// Type alias for Results using LottoError.
 pub type Result<T> = std::result::Result<T, LottoError>;
+
+/// Broad class of a [`LottoError`].
+///
+/// Each category has a stable process exit code, so scripts wrapping the
+/// command-line tool can tell a bad invocation from an impossible request
+/// or a failing disk without parsing messages.
+///
+/// | Category        | Exit code |
+/// |-----------------|-----------|
+/// | `InvalidConfig` | 2         |
+/// | `InvalidInput`  | 3         |
+/// | `Infeasible`    | 4         |
+/// | `Overflow`      | 5         |
+/// | `Io`            | 6         |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorCategory {
+    /// The game, amounts or other parameters are not valid.
+    InvalidConfig,
+    /// A ticket, ticket file, code or store is malformed.
+    InvalidInput,
+    /// The parameters are valid but the request cannot be satisfied.
+    Infeasible,
+    /// A calculation does not fit the numeric types used.
+    Overflow,
+    /// Reading or writing failed.
+    Io,
+}
+
+impl ErrorCategory {
+    /// Process exit code for errors of this category.
+    ///
+    /// The codes start at 2, the code `clap` uses for usage errors, so
+    /// `1` stays free for failures that are not errors of the library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::{ErrorCategory, LottoError};
+    ///
+    /// assert_eq!(ErrorCategory::InvalidConfig.exit_code(), 2);
+    /// assert_eq!(LottoError::ZeroGames.category().exit_code(), 2);
+    /// ```
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCategory::InvalidConfig => 2,
+            ErrorCategory::InvalidInput => 3,
+            ErrorCategory::Infeasible => 4,
+            ErrorCategory::Overflow => 5,
+            ErrorCategory::Io => 6,
+        }
+    }
+
+    /// Snake-case name of the category, as used in JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorCategory::InvalidConfig => "invalid_config",
+            ErrorCategory::InvalidInput => "invalid_input",
+            ErrorCategory::Infeasible => "infeasible",
+            ErrorCategory::Overflow => "overflow",
+            ErrorCategory::Io => "io",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl LottoError {
+    /// The class this error belongs to.
+    ///
+    /// A ticket file line error is always invalid input, even when the
+    /// error it wraps is a [`LottoError::ParseError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::{ErrorCategory, LottoError};
+    ///
+    /// let error = LottoError::TooManyUniqueGames { requested: 10, maximum: 6 };
+    /// assert_eq!(error.category(), ErrorCategory::Infeasible);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            LottoError::InvalidRange { .. }
+            | LottoError::PickExceedsRange { .. }
+            | LottoError::ZeroGames
+            | LottoError::InvalidMatchCount { .. }
+            | LottoError::InvalidAmount { .. }
+            | LottoError::DuplicatePrizeTier { .. }
+            | LottoError::InvalidBetslipLayout { .. }
+            | LottoError::InvalidProbability { .. }
+            | LottoError::InvalidDrawSize { .. }
+            | LottoError::ParseError(_) => ErrorCategory::InvalidConfig,
+            LottoError::InvalidTicketSize { .. }
+            | LottoError::BallOutOfRange { .. }
+            | LottoError::DuplicateBall { .. }
+            | LottoError::BetTooSmall { .. }
+            | LottoError::RankOutOfRange { .. }
+            | LottoError::InvalidTicketCode { .. }
+            | LottoError::InvalidStore { .. }
+            | LottoError::InvalidTicketLine { .. }
+            | LottoError::EmptyTicketFile { .. } => ErrorCategory::InvalidInput,
+            LottoError::TooManyUniqueGames { .. }
+            | LottoError::UniqueGenerationFailed { .. }
+            | LottoError::ConstraintsUnsatisfiable { .. } => ErrorCategory::Infeasible,
+            LottoError::CalculationOverflow { .. } => ErrorCategory::Overflow,
+            LottoError::IoError(_) => ErrorCategory::Io,
+        }
+    }
+
+    /// Name of the variant, e.g. `"InvalidRange"`.
+    pub fn variant(&self) -> &'static str {
+        match self {
+            LottoError::InvalidRange { .. } => "InvalidRange",
+            LottoError::PickExceedsRange { .. } => "PickExceedsRange",
+            LottoError::ZeroGames => "ZeroGames",
+            LottoError::CalculationOverflow { .. } => "CalculationOverflow",
+            LottoError::InvalidMatchCount { .. } => "InvalidMatchCount",
+            LottoError::TooManyUniqueGames { .. } => "TooManyUniqueGames",
+            LottoError::UniqueGenerationFailed { .. } => "UniqueGenerationFailed",
+            LottoError::InvalidTicketSize { .. } => "InvalidTicketSize",
+            LottoError::BallOutOfRange { .. } => "BallOutOfRange",
+            LottoError::DuplicateBall { .. } => "DuplicateBall",
+            LottoError::InvalidAmount { .. } => "InvalidAmount",
+            LottoError::DuplicatePrizeTier { .. } => "DuplicatePrizeTier",
+            LottoError::BetTooSmall { .. } => "BetTooSmall",
+            LottoError::InvalidBetslipLayout { .. } => "InvalidBetslipLayout",
+            LottoError::InvalidProbability { .. } => "InvalidProbability",
+            LottoError::RankOutOfRange { .. } => "RankOutOfRange",
+            LottoError::InvalidTicketCode { .. } => "InvalidTicketCode",
+            LottoError::InvalidStore { .. } => "InvalidStore",
+            LottoError::InvalidTicketLine { .. } => "InvalidTicketLine",
+            LottoError::EmptyTicketFile { .. } => "EmptyTicketFile",
+            LottoError::InvalidDrawSize { .. } => "InvalidDrawSize",
+            LottoError::ConstraintsUnsatisfiable { .. } => "ConstraintsUnsatisfiable",
+            LottoError::IoError(_) => "IoError",
+            LottoError::ParseError(_) => "ParseError",
+        }
+    }
+
+    /// The fields of the variant as a JSON object.
+    ///
+    /// Tuple variants name their single field `detail`; a wrapped error is
+    /// rendered in full by [`LottoError::to_json`].
+    fn fields(&self) -> Json {
+        let fields: Vec<(&str, Json)> = match self {
+            LottoError::InvalidRange { start, end } => {
+                vec![("start", Json::from(*start)), ("end", Json::from(*end))]
+            }
+            LottoError::PickExceedsRange { pick, available } => vec![
+                ("pick", Json::from(*pick)),
+                ("available", Json::from(*available)),
+            ],
+            LottoError::ZeroGames => vec![],
+            LottoError::CalculationOverflow { operation } => {
+                vec![("operation", Json::from(operation.as_str()))]
+            }
+            LottoError::InvalidMatchCount {
+                match_count,
+                pick_count,
+            } => vec![
+                ("match_count", Json::from(*match_count)),
+                ("pick_count", Json::from(*pick_count)),
+            ],
+            LottoError::TooManyUniqueGames { requested, maximum } => vec![
+                ("requested", Json::from(*requested)),
+                ("maximum", Json::from(*maximum)),
+            ],
+            LottoError::UniqueGenerationFailed {
+                requested,
+                generated,
+            } => vec![
+                ("requested", Json::from(*requested)),
+                ("generated", Json::from(*generated)),
+            ],
+            LottoError::InvalidTicketSize { expected, got } => vec![
+                ("expected", Json::from(*expected)),
+                ("got", Json::from(*got)),
+            ],
+            LottoError::BallOutOfRange { value, start, end } => vec![
+                ("value", Json::from(*value)),
+                ("start", Json::from(*start)),
+                ("end", Json::from(*end)),
+            ],
+            LottoError::DuplicateBall { value } => vec![("value", Json::from(*value))],
+            LottoError::InvalidAmount { field, value } => vec![
+                ("field", Json::from(field.as_str())),
+                ("value", Json::from(*value)),
+            ],
+            LottoError::DuplicatePrizeTier { matches } => vec![("matches", Json::from(*matches))],
+            LottoError::BetTooSmall {
+                bet_size,
+                draw_size,
+            } => vec![
+                ("bet_size", Json::from(*bet_size)),
+                ("draw_size", Json::from(*draw_size)),
+            ],
+            LottoError::InvalidBetslipLayout { columns, size } => vec![
+                ("columns", Json::from(*columns)),
+                ("size", Json::from(*size)),
+            ],
+            LottoError::InvalidProbability { favorable, total } => vec![
+                ("favorable", Json::from(favorable.as_str())),
+                ("total", Json::from(total.as_str())),
+            ],
+            LottoError::RankOutOfRange { rank, total } => vec![
+                ("rank", Json::from(rank.as_str())),
+                ("total", Json::from(total.as_str())),
+            ],
+            LottoError::InvalidTicketCode { code, reason } => vec![
+                ("code", Json::from(code.as_str())),
+                ("reason", Json::from(reason.as_str())),
+            ],
+            LottoError::InvalidStore { reason } => vec![("reason", Json::from(reason.as_str()))],
+            LottoError::InvalidTicketLine { line, source } => {
+                vec![("line", Json::from(*line)), ("source", source.to_json())]
+            }
+            LottoError::EmptyTicketFile { path } => vec![("path", Json::from(path.as_str()))],
+            LottoError::InvalidDrawSize {
+                draw_size,
+                available,
+            } => vec![
+                ("draw_size", Json::from(*draw_size)),
+                ("available", Json::from(*available)),
+            ],
+            LottoError::ConstraintsUnsatisfiable {
+                requested,
+                generated,
+            } => vec![
+                ("requested", Json::from(*requested)),
+                ("generated", Json::from(*generated)),
+            ],
+            LottoError::IoError(detail) | LottoError::ParseError(detail) => {
+                vec![("detail", Json::from(detail.as_str()))]
+            }
+        };
+        Json::object(fields)
+    }
+
+    /// Render the error as a JSON object with its category, variant,
+    /// message and fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::LottoError;
+    ///
+    /// let error = LottoError::InvalidRange { start: 60, end: 1 };
+    /// assert_eq!(
+    ///     error.to_json().to_string(),
+    ///     concat!(
+    ///         r#"{"category":"invalid_config","variant":"InvalidRange","#,
+    ///         r#""message":"Start value (60) must be less than end value (1)","#,
+    ///         r#""fields":{"start":60,"end":1}}"#
+    ///     )
+    /// );
+    /// ```
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("category", Json::from(self.category().name())),
+            ("variant", Json::from(self.variant())),
+            ("message", Json::from(self.to_string())),
+            ("fields", self.fields()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_have_distinct_exit_codes() {
+        let categories = [
+            ErrorCategory::InvalidConfig,
+            ErrorCategory::InvalidInput,
+            ErrorCategory::Infeasible,
+            ErrorCategory::Overflow,
+            ErrorCategory::Io,
+        ];
+        let mut codes: Vec<i32> = categories.iter().map(|c| c.exit_code()).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), categories.len());
+        assert!(codes.iter().all(|&code| code > 1));
+    }
+
+    #[test]
+    fn test_ticket_line_is_invalid_input() {
+        let error = LottoError::InvalidTicketLine {
+            line: 3,
+            source: Box::new(LottoError::DuplicateBall { value: 7 }),
+        };
+        assert_eq!(error.category(), ErrorCategory::InvalidInput);
+        assert_eq!(
+            error.to_json().to_string(),
+            concat!(
+                r#"{"category":"invalid_input","variant":"InvalidTicketLine","#,
+                r#""message":"Line 3: Ticket contains duplicate ball: 7","#,
+                r#""fields":{"line":3,"source":{"category":"invalid_input","#,
+                r#""variant":"DuplicateBall","message":"Ticket contains duplicate ball: 7","#,
+                r#""fields":{"value":7}}}}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_ticket_line_wrapping_parse_error_is_invalid_input() {
+        let error = LottoError::InvalidTicketLine {
+            line: 1,
+            source: Box::new(LottoError::ParseError("invalid ball 'x'".to_string())),
+        };
+        assert_eq!(error.category(), ErrorCategory::InvalidInput);
+        assert_eq!(error.category().exit_code(), 3);
+        assert!(
+            error
+                .to_json()
+                .to_string()
+                .starts_with(r#"{"category":"invalid_input","variant":"InvalidTicketLine""#)
+        );
+    }
+
+    #[test]
+    fn test_tuple_variants_use_detail_field() {
+        let error = LottoError::IoError("disk full".to_string());
+        assert_eq!(error.category(), ErrorCategory::Io);
+        assert_eq!(error.category().exit_code(), 6);
+        assert_eq!(error.fields().to_string(), r#"{"detail":"disk full"}"#);
+    }
+}
@@ -0,0 +1,451 @@
+//! Expected value and return-to-player analysis.
+//!
+//! This module combines the exact match probabilities from
+//! [`crate::probability`] with a prize table to compute how much of the
+//! ticket price is returned to the player on average.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::multi_bet::simple_bet_breakdown;
+use crate::probability::{Probability, calculate_probability_big};
+
+/// A fixed payout for matching exactly `matches` balls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrizeTier {
+    matches: usize,
+    payout: f64,
+}
+
+impl PrizeTier {
+    /// Create a new PrizeTier.
+    ///
+    /// # Arguments
+    ///
+    /// * `matches` - Number of matched balls that wins this tier
+    /// * `payout` - Amount paid for a winning ticket in this tier
+    pub fn new(matches: usize, payout: f64) -> Self {
+        Self { matches, payout }
+    }
+
+    /// Get the number of matched balls for this tier.
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+
+    /// Get the payout for this tier.
+    pub fn payout(&self) -> f64 {
+        self.payout
+    }
+}
+
+/// Prize structure of a game: ticket price, jackpot and lower tiers.
+///
+/// The jackpot is always paid for matching every picked ball, so it must
+/// not be repeated in the lower tiers.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::expected_value::PrizeTable;
+///
+/// let table = PrizeTable::new(5.0, 50_000_000.0)
+///     .with_tier(5, 50_000.0)
+///     .with_tier(4, 1_000.0);
+/// assert_eq!(table.tiers().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrizeTable {
+    ticket_price: f64,
+    jackpot: f64,
+    tiers: Vec<PrizeTier>,
+}
+
+impl PrizeTable {
+    /// Create a new PrizeTable with only a jackpot.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticket_price` - Price of a single ticket
+    /// * `jackpot` - Amount paid for matching every picked ball
+    pub fn new(ticket_price: f64, jackpot: f64) -> Self {
+        Self {
+            ticket_price,
+            jackpot,
+            tiers: Vec::new(),
+        }
+    }
+
+    /// Add a lower prize tier.
+    pub fn with_tier(mut self, matches: usize, payout: f64) -> Self {
+        self.tiers.push(PrizeTier::new(matches, payout));
+        self
+    }
+
+    /// Replace the jackpot amount.
+    pub fn with_jackpot(mut self, jackpot: f64) -> Self {
+        self.jackpot = jackpot;
+        self
+    }
+
+    /// Get the ticket price.
+    pub fn ticket_price(&self) -> f64 {
+        self.ticket_price
+    }
+
+    /// Get the jackpot amount.
+    pub fn jackpot(&self) -> f64 {
+        self.jackpot
+    }
+
+    /// Get the lower prize tiers.
+    pub fn tiers(&self) -> &[PrizeTier] {
+        &self.tiers
+    }
+
+    /// Get the amount paid for matching `matches` of `pick` balls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::expected_value::PrizeTable;
+    ///
+    /// let table = PrizeTable::new(5.0, 50_000_000.0).with_tier(5, 50_000.0);
+    /// assert_eq!(table.payout(6, 6), 50_000_000.0);
+    /// assert_eq!(table.payout(5, 6), 50_000.0);
+    /// assert_eq!(table.payout(4, 6), 0.0);
+    /// ```
+    pub fn payout(&self, matches: usize, pick: usize) -> f64 {
+        if matches == pick {
+            return self.jackpot;
+        }
+        self.tiers
+            .iter()
+            .find(|tier| tier.matches() == matches)
+            .map_or(0.0, PrizeTier::payout)
+    }
+
+    /// Get the amount paid to a bet of `bet_size` balls covering `hits` of
+    /// `draw_size` drawn balls.
+    ///
+    /// A multi-number bet is paid for every simple bet it contains, so a
+    /// bet of exactly `draw_size` balls gets `payout(hits, draw_size)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::expected_value::PrizeTable;
+    ///
+    /// let table = PrizeTable::new(5.0, 1_000.0).with_tier(5, 100.0).with_tier(4, 10.0);
+    /// assert_eq!(table.bet_payout(6, 6, 6), 1_000.0);
+    /// // 1 sena, 12 quinas and 15 quadras
+    /// assert_eq!(table.bet_payout(8, 6, 6), 2_350.0);
+    /// ```
+    pub fn bet_payout(&self, bet_size: usize, hits: usize, draw_size: usize) -> f64 {
+        simple_bet_breakdown(bet_size, draw_size, hits)
+            .iter()
+            .enumerate()
+            .map(|(matches, bets)| bets.to_f64() * self.payout(matches, draw_size))
+            .sum()
+    }
+}
+
+/// Contribution of a single prize tier to the expected return.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierReturn {
+    tier: PrizeTier,
+    favorable: BigUint,
+    total: BigUint,
+}
+
+impl TierReturn {
+    /// Get the prize tier.
+    pub fn tier(&self) -> PrizeTier {
+        self.tier
+    }
+
+    /// Get the probability of winning this tier as (favorable, total).
+    pub fn odds(&self) -> (&BigUint, &BigUint) {
+        (&self.favorable, &self.total)
+    }
+
+    /// Get the exact probability of winning this tier.
+    pub fn exact_probability(&self) -> Probability {
+        Probability::reduced(self.favorable.clone(), self.total.clone())
+    }
+
+    /// Get the probability of winning this tier.
+    pub fn probability(&self) -> f64 {
+        self.favorable.to_f64() / self.total.to_f64()
+    }
+
+    /// Get the expected amount this tier returns per ticket.
+    pub fn contribution(&self) -> f64 {
+        self.probability() * self.tier.payout
+    }
+}
+
+/// Expected value report for a single ticket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvReport {
+    ticket_price: f64,
+    jackpot: TierReturn,
+    tiers: Vec<TierReturn>,
+}
+
+impl EvReport {
+    /// Get the ticket price.
+    pub fn ticket_price(&self) -> f64 {
+        self.ticket_price
+    }
+
+    /// Get the jackpot tier return.
+    pub fn jackpot(&self) -> &TierReturn {
+        &self.jackpot
+    }
+
+    /// Get the lower tier returns, sorted by descending match count.
+    pub fn tiers(&self) -> &[TierReturn] {
+        &self.tiers
+    }
+
+    /// Expected amount returned per ticket (jackpot included).
+    pub fn expected_return(&self) -> f64 {
+        self.jackpot.contribution() + self.lower_tiers_return()
+    }
+
+    /// Expected profit per ticket: expected return minus ticket price.
+    pub fn net_expected_value(&self) -> f64 {
+        self.expected_return() - self.ticket_price
+    }
+
+    /// Return-to-player as a percentage of the ticket price.
+    pub fn return_to_player(&self) -> f64 {
+        self.expected_return() / self.ticket_price * 100.0
+    }
+
+    /// Jackpot size at which the expected return equals the ticket price.
+    ///
+    /// Returns 0.0 when the lower tiers alone already pay back the ticket.
+    pub fn break_even_jackpot(&self) -> f64 {
+        let missing = self.ticket_price - self.lower_tiers_return();
+        if missing <= 0.0 {
+            0.0
+        } else {
+            missing / self.jackpot.probability()
+        }
+    }
+
+    fn lower_tiers_return(&self) -> f64 {
+        self.tiers.iter().map(TierReturn::contribution).sum()
+    }
+}
+
+/// Calculate the expected value of a single ticket.
+///
+/// # Arguments
+///
+/// * `total_balls` - Total number of balls in the lottery
+/// * `pick_count` - Number of balls picked per game
+/// * `table` - Ticket price, jackpot and lower prize tiers
+///
+/// # Returns
+///
+/// An EvReport, or an error if the prize table is invalid for the game.
+///
+/// # Errors
+///
+/// Returns `LottoError::InvalidAmount` if the price is not positive or a
+/// payout is negative or not finite, `LottoError::InvalidMatchCount` if a
+/// tier requires more matches than picked balls, and
+/// `LottoError::DuplicatePrizeTier` if a match count is paid twice.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::expected_value::{PrizeTable, expected_value};
+///
+/// // Mega-Sena with a 50M jackpot and no lower tiers
+/// let table = PrizeTable::new(5.0, 50_063_860.0);
+/// let report = expected_value(60, 6, &table).unwrap();
+/// assert!((report.expected_return() - 1.0).abs() < 1e-9);
+/// assert!((report.return_to_player() - 20.0).abs() < 1e-9);
+/// ```
+pub fn expected_value(
+    total_balls: usize,
+    pick_count: usize,
+    table: &PrizeTable,
+) -> Result<EvReport> {
+    if !(table.ticket_price.is_finite() && table.ticket_price > 0.0) {
+        return Err(LottoError::InvalidAmount {
+            field: "ticket price".to_string(),
+            value: table.ticket_price,
+        });
+    }
+    validate_amount("jackpot", table.jackpot)?;
+
+    let jackpot = tier_return(
+        total_balls,
+        pick_count,
+        PrizeTier::new(pick_count, table.jackpot),
+    )?;
+
+    let mut tiers = Vec::with_capacity(table.tiers.len());
+    for &tier in &table.tiers {
+        validate_amount("payout", tier.payout)?;
+        if tier.matches == pick_count
+            || tiers
+                .iter()
+                .any(|t: &TierReturn| t.tier.matches == tier.matches)
+        {
+            return Err(LottoError::DuplicatePrizeTier {
+                matches: tier.matches,
+            });
+        }
+        tiers.push(tier_return(total_balls, pick_count, tier)?);
+    }
+    tiers.sort_by_key(|t| std::cmp::Reverse(t.tier.matches));
+
+    Ok(EvReport {
+        ticket_price: table.ticket_price,
+        jackpot,
+        tiers,
+    })
+}
+
+/// Calculate the expected value of a single ticket using Config object.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::Config;
+/// use lotto_quick_pick::expected_value::{PrizeTable, expected_value_for_config};
+///
+/// let config = Config::new(1, 1, 60, 6).unwrap();
+/// let table = PrizeTable::new(5.0, 10_000_000.0).with_tier(5, 50_000.0);
+/// let report = expected_value_for_config(&config, &table).unwrap();
+/// assert!(report.return_to_player() < 100.0);
+/// ```
+pub fn expected_value_for_config(config: &crate::Config, table: &PrizeTable) -> Result<EvReport> {
+    expected_value(config.range().size(), config.pick().value(), table)
+}
+
+fn tier_return(total_balls: usize, pick_count: usize, tier: PrizeTier) -> Result<TierReturn> {
+    let (favorable, total) = calculate_probability_big(total_balls, pick_count, tier.matches)?;
+    Ok(TierReturn {
+        tier,
+        favorable,
+        total,
+    })
+}
+
+fn validate_amount(field: &str, value: f64) -> Result<()> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(LottoError::InvalidAmount {
+            field: field.to_string(),
+            value,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected_value_jackpot_only() {
+        let table = PrizeTable::new(2.0, 50_063_860.0);
+        let report = expected_value(60, 6, &table).unwrap();
+        assert!((report.expected_return() - 1.0).abs() < 1e-9);
+        assert!((report.net_expected_value() + 1.0).abs() < 1e-9);
+        assert!((report.return_to_player() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_expected_value_includes_lower_tiers() {
+        // 10 balls, pick 3: P(3) = 1/120, P(2) = 21/120
+        let table = PrizeTable::new(1.0, 120.0).with_tier(2, 120.0 / 21.0);
+        let report = expected_value(10, 3, &table).unwrap();
+        assert!((report.expected_return() - 2.0).abs() < 1e-9);
+        assert!((report.return_to_player() - 200.0).abs() < 1e-9);
+        assert_eq!(
+            report.tiers()[0].exact_probability(),
+            Probability::new(7u32, 40u32).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_break_even_jackpot() {
+        let table = PrizeTable::new(5.0, 1.0);
+        let report = expected_value(60, 6, &table).unwrap();
+        assert!((report.break_even_jackpot() - 5.0 * 50_063_860.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_break_even_jackpot_zero_when_lower_tiers_pay() {
+        let table = PrizeTable::new(1.0, 0.0).with_tier(2, 1_000.0);
+        let report = expected_value(10, 3, &table).unwrap();
+        assert_eq!(report.break_even_jackpot(), 0.0);
+    }
+
+    #[test]
+    fn test_tiers_sorted_descending() {
+        let table = PrizeTable::new(5.0, 1.0)
+            .with_tier(4, 1_000.0)
+            .with_tier(5, 50_000.0);
+        let report = expected_value(60, 6, &table).unwrap();
+        assert_eq!(report.tiers()[0].tier().matches(), 5);
+        assert_eq!(report.tiers()[1].tier().matches(), 4);
+    }
+
+    #[test]
+    fn test_expected_value_beyond_u128() {
+        let table = PrizeTable::new(1.0, 1.0).with_tier(100, 1.0);
+        let report = expected_value(256, 128, &table).unwrap();
+        assert!(report.expected_return() > 0.0);
+        assert!(report.break_even_jackpot().is_finite());
+    }
+
+    #[test]
+    fn test_expected_value_invalid_price() {
+        let table = PrizeTable::new(0.0, 1.0);
+        let result = expected_value(60, 6, &table);
+        assert!(matches!(result, Err(LottoError::InvalidAmount { .. })));
+    }
+
+    #[test]
+    fn test_expected_value_negative_payout() {
+        let table = PrizeTable::new(5.0, 1.0).with_tier(5, -1.0);
+        let result = expected_value(60, 6, &table);
+        assert!(matches!(result, Err(LottoError::InvalidAmount { .. })));
+    }
+
+    #[test]
+    fn test_expected_value_duplicate_tier() {
+        let table = PrizeTable::new(5.0, 1.0)
+            .with_tier(5, 10.0)
+            .with_tier(5, 20.0);
+        let result = expected_value(60, 6, &table);
+        assert!(matches!(
+            result,
+            Err(LottoError::DuplicatePrizeTier { matches: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_expected_value_jackpot_tier_repeated() {
+        let table = PrizeTable::new(5.0, 1.0).with_tier(6, 10.0);
+        let result = expected_value(60, 6, &table);
+        assert!(matches!(
+            result,
+            Err(LottoError::DuplicatePrizeTier { matches: 6 })
+        ));
+    }
+
+    #[test]
+    fn test_expected_value_tier_exceeds_pick() {
+        let table = PrizeTable::new(5.0, 1.0).with_tier(7, 10.0);
+        let result = expected_value(60, 6, &table);
+        assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
+    }
+}
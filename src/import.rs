@@ -0,0 +1,387 @@
+//! Reading hand-picked tickets from files.
+//!
+//! Tickets are read one per line, with balls separated by commas,
+//! semicolons or whitespace, or as a single [ticket code](crate::code).
+//! Blank lines and `#` comments are skipped. A first line naming at least
+//! one known column is a CSV header: columns named `ball...` (or
+//! `bola...`) hold the balls, a `code` column is used when there are no
+//! ball columns, and every other column, such as the `index` and `rank`
+//! columns written by [`crate::output`], is ignored. Any other first line
+//! is read as a ticket.
+//!
+//! ```text
+//! # my numbers
+//! 4 8 15 16 23 42
+//! 05,10,20,30,40,50
+//! 04A2-DV4
+//! ```
+//!
+//! Every line is validated with [`Ticket::try_new`]; errors carry the line
+//! number and the underlying error, such as `DuplicateBall`,
+//! `BallOutOfRange` or `InvalidTicketSize`.
+
+use crate::code::decode;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+use crate::ticket_key::TicketKey;
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Iterator over the tickets of a ticket file, one `Result` per ticket line.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::import::TicketReader;
+/// use lotto_quick_pick::{BallRange, LottoError, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let text = "# hand-picked\n4 8 15 16 23 42\n1,2,3,4,5,5\n";
+/// let results: Vec<_> = TicketReader::new(text.as_bytes(), &range, &pick).collect();
+///
+/// assert!(results[0].is_ok());
+/// assert_eq!(
+///     results[1].as_ref().unwrap_err().to_string(),
+///     "Line 3: Ticket contains duplicate ball: 5"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct TicketReader<R> {
+    reader: R,
+    range: BallRange,
+    pick: PickCount,
+    line: usize,
+    columns: Option<Vec<Column>>,
+    header_checked: bool,
+}
+
+impl<R: BufRead> TicketReader<R> {
+    /// Create a new TicketReader.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the ticket lines
+    /// * `range` - The range every ball must fall in
+    /// * `pick` - Number of balls every ticket must have
+    pub fn new(reader: R, range: &BallRange, pick: &PickCount) -> Self {
+        Self {
+            reader,
+            range: *range,
+            pick: *pick,
+            line: 0,
+            columns: None,
+            header_checked: false,
+        }
+    }
+
+    /// Parse one ticket line, which is known not to be blank or a comment.
+    fn parse_line(&mut self, line: &str) -> Result<Option<Ticket>> {
+        let fields = split_fields(line);
+
+        if !self.header_checked {
+            self.header_checked = true;
+            // A header names at least one known column; anything else is a ticket
+            let columns: Vec<Column> = fields.iter().map(|name| Column::named(name)).collect();
+            if columns.iter().any(|&c| c != Column::Ignored) {
+                self.columns = Some(columns);
+                return Ok(None);
+            }
+        }
+
+        let column = |i: usize| match &self.columns {
+            Some(columns) => columns.get(i).copied().unwrap_or(Column::Ignored),
+            None => Column::Ball,
+        };
+
+        // A lone field that cannot be the whole ticket is a code
+        let code = match &self.columns {
+            Some(columns) if !columns.contains(&Column::Ball) => (0..fields.len())
+                .find(|&i| column(i) == Column::Code)
+                .map(|i| fields[i]),
+            Some(_) => None,
+            None => match fields.as_slice() {
+                [field] if self.pick.value() > 1 || !field.bytes().all(|b| b.is_ascii_digit()) => {
+                    Some(*field)
+                }
+                _ => None,
+            },
+        };
+        if let Some(code) = code {
+            return decode(code, &self.range, &self.pick).map(Some);
+        }
+
+        let balls = fields
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| column(*i) == Column::Ball)
+            .map(|(_, field)| {
+                field
+                    .parse::<u8>()
+                    .map(BallNumber::new)
+                    .map_err(|e| LottoError::ParseError(format!("invalid ball '{}': {}", field, e)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ticket::try_new(balls, &self.range, &self.pick).map(Some)
+    }
+}
+
+/// Role of a CSV column, from its header name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Ball,
+    Code,
+    Ignored,
+}
+
+impl Column {
+    fn named(name: &str) -> Self {
+        let name = name.to_ascii_lowercase();
+        if name.starts_with("ball") || name.starts_with("bola") {
+            Column::Ball
+        } else if name == "code" || name == "codigo" {
+            Column::Code
+        } else {
+            Column::Ignored
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TicketReader<R> {
+    type Item = Result<Ticket>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buffer = String::new();
+        loop {
+            buffer.clear();
+            match self.reader.read_line(&mut buffer) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(LottoError::IoError(e.to_string()))),
+            }
+            self.line += 1;
+
+            let line = buffer.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            match self.parse_line(line) {
+                Ok(Some(ticket)) => return Some(Ok(ticket)),
+                Ok(None) => continue,
+                Err(e) => {
+                    return Some(Err(LottoError::InvalidTicketLine {
+                        line: self.line,
+                        source: Box::new(e),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// Split a line on commas or semicolons when present, otherwise on whitespace.
+fn split_fields(line: &str) -> Vec<&str> {
+    if line.contains([',', ';']) {
+        line.split([',', ';']).map(str::trim).collect()
+    } else {
+        line.split_whitespace().collect()
+    }
+}
+
+/// Read every ticket from a reader, stopping at the first invalid line.
+///
+/// # Arguments
+///
+/// * `reader` - Source of the ticket lines
+/// * `range` - The range every ball must fall in
+/// * `pick` - Number of balls every ticket must have
+///
+/// # Errors
+///
+/// Returns `InvalidTicketLine` with the line number and the reason the line
+/// was rejected, or `IoError` if reading fails.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::import::read_tickets;
+/// use lotto_quick_pick::{BallRange, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let csv = "index,ball_1,ball_2,ball_3,ball_4,ball_5,ball_6\n1,4,8,15,16,23,42\n";
+///
+/// let tickets = read_tickets(csv.as_bytes(), &range, &pick).unwrap();
+/// assert_eq!(tickets[0].to_string(), "04 08 15 16 23 42");
+/// ```
+pub fn read_tickets(
+    reader: impl BufRead,
+    range: &BallRange,
+    pick: &PickCount,
+) -> Result<Vec<Ticket>> {
+    TicketReader::new(reader, range, pick).collect()
+}
+
+/// Read every ticket from a file, stopping at the first invalid line.
+///
+/// # Errors
+///
+/// Returns `IoError` if the file cannot be opened, otherwise the errors of
+/// [`read_tickets`].
+pub fn load_tickets(
+    path: impl AsRef<Path>,
+    range: &BallRange,
+    pick: &PickCount,
+) -> Result<Vec<Ticket>> {
+    let file = std::fs::File::open(path.as_ref())
+        .map_err(|e| LottoError::IoError(format!("{}: {}", path.as_ref().display(), e)))?;
+    read_tickets(BufReader::new(file), range, pick)
+}
+
+/// Remove repeated tickets, keeping the first occurrence of each.
+///
+/// # Arguments
+///
+/// * `tickets` - The tickets to deduplicate in place
+/// * `range` - The range the tickets' balls are drawn from
+///
+/// # Returns
+///
+/// The number of tickets removed.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::import::dedup_tickets;
+/// use lotto_quick_pick::{BallNumber, BallRange, Ticket};
+///
+/// let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+/// let mut tickets = vec![ticket.clone(), ticket];
+///
+/// assert_eq!(dedup_tickets(&mut tickets, &BallRange::mega_sena()), 1);
+/// assert_eq!(tickets.len(), 1);
+/// ```
+pub fn dedup_tickets(tickets: &mut Vec<Ticket>, range: &BallRange) -> usize {
+    let before = tickets.len();
+    let mut seen = HashSet::with_capacity(before);
+    tickets.retain(|ticket| seen.insert(TicketKey::from_balls(ticket.balls(), range)));
+    before - tickets.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mega_sena() -> (BallRange, PickCount) {
+        let range = BallRange::mega_sena();
+        let pick = PickCount::new(6, &range).unwrap();
+        (range, pick)
+    }
+
+    fn line_error(text: &str) -> (usize, LottoError) {
+        let (range, pick) = mega_sena();
+        match read_tickets(text.as_bytes(), &range, &pick) {
+            Err(LottoError::InvalidTicketLine { line, source }) => (line, *source),
+            other => panic!("expected a line error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reads_mixed_separators_and_comments() {
+        let (range, pick) = mega_sena();
+        let text =
+            "\n# header comment\n1 2 3 4 5 6\n7,8,9,10,11,12  # trailing\n13;14;15;16;17;18\n";
+        let tickets = read_tickets(text.as_bytes(), &range, &pick).unwrap();
+
+        assert_eq!(tickets.len(), 3);
+        assert_eq!(tickets[2].balls()[0], BallNumber::new(13));
+    }
+
+    #[test]
+    fn test_reports_line_and_specific_error() {
+        assert_eq!(
+            line_error("1 2 3 4 5 6\n\n1 2 3 4 5 61\n"),
+            (
+                3,
+                LottoError::BallOutOfRange {
+                    value: 61,
+                    start: 1,
+                    end: 60
+                }
+            )
+        );
+        assert_eq!(
+            line_error("1 2 3 4 5\n"),
+            (
+                1,
+                LottoError::InvalidTicketSize {
+                    expected: 6,
+                    got: 5
+                }
+            )
+        );
+        assert_eq!(
+            line_error("# x\n1 2 3 4 6 6\n"),
+            (2, LottoError::DuplicateBall { value: 6 })
+        );
+        assert!(matches!(
+            line_error("1 2 3 x 5 6\n"),
+            (1, LottoError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_csv_header_selects_ball_columns() {
+        let (range, pick) = mega_sena();
+        let csv =
+            "index,ball_1,ball_2,ball_3,ball_4,ball_5,ball_6,rank\n1,55,56,57,58,59,60,50063859\n";
+        let tickets = read_tickets(csv.as_bytes(), &range, &pick).unwrap();
+
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets[0].balls()[0], BallNumber::new(55));
+    }
+
+    #[test]
+    fn test_codes_with_and_without_header() {
+        let (range, pick) = mega_sena();
+        let plain = "04A2-DV4\n0000000\n";
+        let csv = "index,code\n1,04a2dv4\n";
+
+        let tickets = read_tickets(plain.as_bytes(), &range, &pick).unwrap();
+        assert_eq!(tickets[0].to_string(), "04 08 15 16 23 42");
+        assert_eq!(tickets[1].to_string(), "01 02 03 04 05 06");
+        assert_eq!(
+            read_tickets(csv.as_bytes(), &range, &pick).unwrap(),
+            tickets[..1]
+        );
+        assert!(matches!(
+            line_error("1 2 3 4 5 6\n04A2DV5\n"),
+            (2, LottoError::InvalidTicketCode { .. })
+        ));
+    }
+
+    #[test]
+    fn test_first_line_without_known_columns_is_a_ticket() {
+        assert!(matches!(
+            line_error("name,score\n"),
+            (1, LottoError::ParseError(_))
+        ));
+        // A typo is reported as a bad ball, not as a bad header
+        let (line, error) = line_error("1 2 3 x 5 6\n7 8 9 10 11 12\n");
+        assert_eq!(line, 1);
+        assert!(!error.to_string().contains("header"), "{}", error);
+    }
+
+    #[test]
+    fn test_dedup_keeps_first_occurrence_order() {
+        let (range, pick) = mega_sena();
+        let text = "1 2 3 4 5 6\n7 8 9 10 11 12\n6 5 4 3 2 1\n";
+        let mut tickets = read_tickets(text.as_bytes(), &range, &pick).unwrap();
+
+        assert_eq!(dedup_tickets(&mut tickets, &range), 1);
+        assert_eq!(tickets.len(), 2);
+        assert_eq!(tickets[1].balls()[0], BallNumber::new(7));
+    }
+}
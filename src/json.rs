@@ -0,0 +1,545 @@
+//! Minimal JSON values for machine-readable output.
+//!
+//! Instead of pulling in a serialization framework this module offers a
+//! small value tree that renders itself as compact JSON, and a strict
+//! parser for the request bodies of the [`server`](crate::server). Numbers
+//! are kept as their decimal text, which lets exact big integers through
+//! without losing precision.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use std::fmt;
+
+/// A JSON value.
+///
+/// Object members keep their insertion order.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::json::Json;
+///
+/// let value = Json::object([
+///     ("name", Json::from("Mega-Sena")),
+///     ("balls", Json::array([4u8, 8, 15].map(Json::from))),
+///     ("seed", Json::Null),
+/// ]);
+///
+/// assert_eq!(
+///     value.to_string(),
+///     r#"{"name":"Mega-Sena","balls":[4,8,15],"seed":null}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    /// The `null` literal.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number, stored as its JSON text.
+    Number(String),
+    /// A string, escaped when rendered.
+    String(String),
+    /// An ordered list of values.
+    Array(Vec<Json>),
+    /// Members in insertion order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Create an array from values.
+    pub fn array(values: impl IntoIterator<Item = Json>) -> Self {
+        Json::Array(values.into_iter().collect())
+    }
+
+    /// Create an object from key and value pairs.
+    pub fn object<K: Into<String>>(members: impl IntoIterator<Item = (K, Json)>) -> Self {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        )
+    }
+
+    /// Parse a JSON document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` naming the byte offset of the first syntax
+    /// error, or if arrays and objects nest more than 64 deep.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::json::Json;
+    ///
+    /// let value = Json::parse(r#"{"pick": 6, "balls": [4, 8], "name": "caf\u00e9"}"#).unwrap();
+    /// assert_eq!(value.get("pick").and_then(Json::as_u64), Some(6));
+    /// assert_eq!(value.get("balls").and_then(Json::as_array).map(|a| a.len()), Some(2));
+    /// assert_eq!(value.get("name").and_then(Json::as_str), Some("café"));
+    /// assert!(Json::parse("{\"pick\": }").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.bytes.len() {
+            return Err(parser.error("unexpected data after the value"));
+        }
+        Ok(value)
+    }
+
+    /// Get the member of an object, or `None` for other values.
+    ///
+    /// With duplicate keys the last one wins, as in most parsers.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .rev()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a non-negative integer, if it is one that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Json::Number(text) => text.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Json::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the text of a string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Get the elements of an array.
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects [`Json::parse`] accepts.
+const MAX_DEPTH: usize = 64;
+
+/// Recursive-descent parser over the bytes of a document.
+struct Parser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> LottoError {
+        LottoError::ParseError(format!("invalid JSON at byte {}: {}", self.pos, message))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.bytes.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume `literal` if the input continues with it.
+    fn eat(&mut self, literal: &str) -> bool {
+        let found = self.bytes[self.pos..].starts_with(literal.as_bytes());
+        if found {
+            self.pos += literal.len();
+        }
+        found
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) if self.eat("null") => Ok(Json::Null),
+            Some(_) if self.eat("true") => Ok(Json::Bool(true)),
+            Some(_) if self.eat("false") => Ok(Json::Bool(false)),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Parse an array or object one level deeper.
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json>) -> Result<Json> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    /// Parse the elements between `[` and `]`.
+    fn array(&mut self) -> Result<Json> {
+        self.pos += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Json::Array(values));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    /// Parse the members between `{` and `}`.
+    fn object(&mut self) -> Result<Json> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.bytes.get(self.pos) != Some(&b'"') {
+                return Err(self.error("expected a member name"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("expected ':'"));
+            }
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Json::Object(members));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    /// Parse a quoted string, resolving escapes.
+    fn string(&mut self) -> Result<String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            // Copy the run of plain characters up to the next quote or escape
+            let start = self.pos;
+            while let Some(&b) = self.bytes.get(self.pos) {
+                if b == b'"' || b == b'\\' || b < 0x20 {
+                    break;
+                }
+                self.pos += 1;
+            }
+            out.push_str(&self.text[start..self.pos]);
+
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    out.push(self.escape()?);
+                }
+                Some(_) => return Err(self.error("control character in string")),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    /// Parse the character after a backslash.
+    fn escape(&mut self) -> Result<char> {
+        let Some(&b) = self.bytes.get(self.pos) else {
+            return Err(self.error("unterminated string"));
+        };
+        self.pos += 1;
+        Ok(match b {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\u{8}',
+            b'f' => '\u{c}',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let high = self.hex4()?;
+                let code = if (0xD800..0xDC00).contains(&high) {
+                    // A high surrogate must be followed by an escaped low one
+                    if !self.eat("\\u") {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+                    let low = self.hex4()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("unpaired surrogate"));
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+                char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))?
+            }
+            _ => return Err(self.error("unknown escape")),
+        })
+    }
+
+    /// Parse the four hex digits of a `\u` escape.
+    fn hex4(&mut self) -> Result<u32> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected four hex digits"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("checked hex digits"))
+    }
+
+    /// Parse a number, keeping its text.
+    fn number(&mut self) -> Result<Json> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while parser.bytes.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos - from
+        };
+
+        let integer_start = self.pos;
+        let integer = digits(self);
+        if integer == 0 || (integer > 1 && self.bytes[integer_start] == b'0') {
+            return Err(self.error("invalid number"));
+        }
+        if self.eat(".") && digits(self) == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if self.eat("e") || self.eat("E") {
+            let _ = self.eat("+") || self.eat("-");
+            if digits(self) == 0 {
+                return Err(self.error("invalid number"));
+            }
+        }
+        Ok(Json::Number(self.text[start..self.pos].to_string()))
+    }
+}
+
+macro_rules! json_integer {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Json {
+            fn from(value: $ty) -> Self {
+                Json::Number(value.to_string())
+            }
+        })*
+    };
+}
+
+json_integer!(u8, u32, u64, u128, usize, i64);
+
+impl From<f64> for Json {
+    /// Non-finite numbers have no JSON representation and become `null`.
+    fn from(value: f64) -> Self {
+        if value.is_finite() {
+            Json::Number(value.to_string())
+        } else {
+            Json::Null
+        }
+    }
+}
+
+impl From<&BigUint> for Json {
+    fn from(value: &BigUint) -> Self {
+        Json::Number(value.to_string())
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<&str> for Json {
+    fn from(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+}
+
+impl From<String> for Json {
+    fn from(value: String) -> Self {
+        Json::String(value)
+    }
+}
+
+impl<T: Into<Json>> From<Option<T>> for Json {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(text) => f.write_str(text),
+            Json::String(text) => write_string(f, text),
+            Json::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Json::Object(members) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Write a quoted JSON string, escaping quotes, backslashes and controls.
+fn write_string(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings_are_escaped() {
+        let value = Json::from("say \"hi\"\\\n\u{1}");
+        assert_eq!(value.to_string(), r#""say \"hi\"\\\n\u0001""#);
+    }
+
+    #[test]
+    fn test_non_finite_numbers_become_null() {
+        assert_eq!(Json::from(f64::NAN), Json::Null);
+        assert_eq!(Json::from(f64::INFINITY), Json::Null);
+        assert_eq!(Json::from(0.25).to_string(), "0.25");
+    }
+
+    #[test]
+    fn test_big_integers_keep_every_digit() {
+        let big = BigUint::from(u128::MAX).pow(2);
+        assert_eq!(Json::from(&big).to_string(), big.to_string());
+    }
+
+    #[test]
+    fn test_parse_round_trips() {
+        let text = r#"{"a":[1,-2.5e3,0.25,true,false,null],"b":{"c":"d\"e\\f\n"},"big":123456789012345678901234567890}"#;
+        let value = Json::parse(text).unwrap();
+        assert_eq!(value.to_string(), text);
+        assert_eq!(
+            value.get("big").unwrap(),
+            &Json::Number("123456789012345678901234567890".to_string())
+        );
+        assert_eq!(value.get("big").and_then(Json::as_u64), None);
+        assert_eq!(
+            Json::parse(" [ 1 , { } , [ ] ] ").unwrap().to_string(),
+            "[1,{},[]]"
+        );
+    }
+
+    #[test]
+    fn test_parse_escapes() {
+        let value = Json::parse(r#""\u00e9\ud83c\udfb2\/\t""#).unwrap();
+        assert_eq!(value.as_str(), Some("é🎲/\t"));
+        assert!(Json::parse(r#""\ud83c""#).is_err());
+        assert!(Json::parse(r#""\x""#).is_err());
+        assert!(Json::parse("\"tab\there\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_documents() {
+        for text in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "{a:1}",
+            "01",
+            "1.",
+            "-",
+            "1e",
+            "tru",
+            "[1] 2",
+            "\"open",
+        ] {
+            assert!(
+                matches!(Json::parse(text), Err(LottoError::ParseError(_))),
+                "{:?}",
+                text
+            );
+        }
+        let deep = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+        assert!(Json::parse(&deep).is_err());
+        let ok = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        assert!(Json::parse(&ok).is_ok());
+    }
+
+    #[test]
+    fn test_nested_values() {
+        let value = Json::object([
+            ("empty", Json::array([])),
+            ("none", Json::from(None::<u8>)),
+            ("flag", Json::from(true)),
+        ]);
+        assert_eq!(value.to_string(), r#"{"empty":[],"none":null,"flag":true}"#);
+    }
+}
@@ -8,7 +8,7 @@
 //! - Type-safe configuration using newtypes
 //! - Pluggable random number generators
 //! - Efficient ticket generation (uses optimal strategy based on pick size)
-//! - Probability calculation without factorial (no overflow for practical lotteries)
+//! - Probability calculation without factorial (exact big integers beyond u128)
 //! - Comprehensive error handling
 //!
 //! # Examples
@@ -24,34 +24,82 @@
 //! assert_eq!(tickets.len(), 10);
 //! ```
 
+pub mod batch;
+pub mod betslip;
+pub mod bigint;
+pub mod check;
+pub mod code;
 pub mod error;
+pub mod expected_value;
+pub mod import;
+pub mod json;
+pub mod locale;
+pub mod multi_bet;
 pub mod newtypes;
+pub mod output;
+pub mod popularity;
+pub mod preset;
 pub mod probability;
+pub mod rank;
 pub mod rng;
+#[cfg(feature = "serde")]
+mod serde_support;
+pub mod server;
+pub mod settings;
+pub mod store;
 pub mod ticket;
 pub mod ticket_bitwise;
 pub mod ticket_key;
+pub mod time_to_win;
 
-pub use error::{LottoError, Result};
+pub use batch::{BatchWinProbability, batch_win_probability};
+pub use betslip::{BetslipLayout, PatternFilter};
+pub use bigint::BigUint;
+pub use error::{ErrorCategory, LottoError, Result};
+pub use expected_value::{EvReport, PrizeTable, expected_value};
+pub use multi_bet::{MultiBet, generate_multi_bet};
 pub use newtypes::{BallNumber, BallRange, GameCount, PickCount, Ticket};
-pub use probability::{calculate_probability, combination};
+pub use output::{OutputFormat, Report};
+pub use popularity::{PopularityModel, generate_unpopular_tickets};
+pub use preset::GamePreset;
+pub use probability::{
+    MatchOdds, Probability, ProbabilityFormat, calculate_probability, combination,
+    match_distribution,
+};
 pub use rng::RandomNumberGenerator;
-pub use ticket::{generate_ticket, generate_unique_tickets};
+pub use ticket::{
+    OutputOrder, generate_ticket, generate_unique_tickets, generate_unique_tickets_filtered,
+    generate_unique_tickets_ordered, sort_tickets,
+};
+pub use time_to_win::{DrawSchedule, TimeToWin};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 
 /// Configuration for lottery ticket generation.
 ///
 /// This struct holds all necessary parameters for generating lottery tickets
-/// in a type-safe manner.
+/// in a type-safe manner. [`Config::new`] covers the common case; a
+/// [`ConfigBuilder`] also sets the draw size, seed, uniqueness, output
+/// order and slip constraints.
 #[derive(Debug, Clone)]
 pub struct Config {
     game_count: GameCount,
     range: BallRange,
     pick: PickCount,
+    draw: PickCount,
+    seed: Option<u64>,
+    unique: bool,
+    order: OutputOrder,
+    filter: PatternFilter,
 }
 
 impl Config {
     /// Create a new Config from raw values.
     ///
+    /// A reversed range is swapped; use [`ConfigBuilder::strict`] to reject it
+    /// instead. The draw size equals the pick and tickets are unique.
+    ///
     /// # Arguments
     ///
     /// * `games` - Number of unique tickets to generate
@@ -88,6 +136,11 @@ impl Config {
             game_count,
             range,
             pick: pick_count,
+            draw: pick_count,
+            seed: None,
+            unique: true,
+            order: OutputOrder::Generation,
+            filter: PatternFilter::new(),
         })
     }
 
@@ -105,12 +158,275 @@ impl Config {
     pub fn pick(&self) -> &PickCount {
         &self.pick
     }
+
+    /// Get the number of balls drawn, which is at most the pick count.
+    pub fn draw_size(&self) -> &PickCount {
+        &self.draw
+    }
+
+    /// Get the seed tickets are generated from, if one was set.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Check if the generated tickets must all differ.
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Get the order generated tickets are returned in.
+    pub fn order(&self) -> OutputOrder {
+        self.order
+    }
+
+    /// Get the slip constraints every generated ticket satisfies.
+    pub fn filter(&self) -> &PatternFilter {
+        &self.filter
+    }
+
+    /// Create a random number generator for this configuration.
+    ///
+    /// The generator is seeded from [`seed`](Self::seed) when one is set, so
+    /// the same configuration generates the same tickets, and from a random
+    /// seed otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::{ConfigBuilder, GamePreset, generate_tickets};
+    ///
+    /// let config = ConfigBuilder::from_preset(GamePreset::Quina)
+    ///     .with_games(3)
+    ///     .with_seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// let first = generate_tickets(&mut config.rng(), &config).unwrap();
+    /// let second = generate_tickets(&mut config.rng(), &config).unwrap();
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.unwrap_or_else(rand::random))
+    }
+}
+
+/// Builder for a [`Config`], starting from a game preset or a ball range.
+///
+/// Every value is checked when the configuration is built, and each problem
+/// is reported with the error naming the offending value.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::{ConfigBuilder, GamePreset, OutputOrder};
+///
+/// // Ten 8-number Mega-Sena bets, six balls drawn, sorted by balls
+/// let config = ConfigBuilder::from_preset(GamePreset::MegaSena)
+///     .with_games(10)
+///     .with_pick(8)
+///     .with_order(OutputOrder::Lexicographic)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.pick().value(), 8);
+/// assert_eq!(config.draw_size().value(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    games: usize,
+    start: u8,
+    end: u8,
+    pick: usize,
+    draw: Option<usize>,
+    seed: Option<u64>,
+    unique: bool,
+    order: OutputOrder,
+    filter: PatternFilter,
+    strict: bool,
+}
+
+impl ConfigBuilder {
+    /// Start a configuration of one game picking `pick` balls from `range`.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of ball numbers, such as [`BallRange::mega_sena`]
+    /// * `pick` - Number of balls to pick per ticket
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::{BallRange, ConfigBuilder};
+    ///
+    /// let config = ConfigBuilder::new(BallRange::lotomania(), 50).build().unwrap();
+    /// assert_eq!(config.range().start().value(), 0);
+    /// ```
+    pub fn new(range: BallRange, pick: usize) -> Self {
+        Self {
+            games: 1,
+            start: range.start().value(),
+            end: range.end().value(),
+            pick,
+            draw: None,
+            seed: None,
+            unique: true,
+            order: OutputOrder::Generation,
+            filter: PatternFilter::new(),
+            strict: false,
+        }
+    }
+
+    /// Start a configuration of one smallest bet on a catalog game.
+    pub fn from_preset(game: GamePreset) -> Self {
+        Self {
+            draw: Some(game.draw_size().value()),
+            ..Self::new(game.range(), game.pick().value())
+        }
+    }
+
+    /// Set the number of tickets to generate.
+    pub fn with_games(mut self, games: usize) -> Self {
+        self.games = games;
+        self
+    }
+
+    /// Set the ball range from raw start and end numbers.
+    ///
+    /// A reversed range is swapped unless the builder is [`strict`](Self::strict).
+    pub fn with_range(mut self, start: u8, end: u8) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Set the number of balls to pick per ticket.
+    pub fn with_pick(mut self, pick: usize) -> Self {
+        self.pick = pick;
+        self
+    }
+
+    /// Set the number of balls drawn, for multi-number bets.
+    ///
+    /// Defaults to the preset's draw size, or to the pick.
+    pub fn with_draw_size(mut self, draw: usize) -> Self {
+        self.draw = Some(draw);
+        self
+    }
+
+    /// Set the seed the tickets are generated from.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set whether the tickets must all differ (the default).
+    pub fn with_unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    /// Set the order the tickets are returned in.
+    pub fn with_order(mut self, order: OutputOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Set slip constraints every ticket must satisfy.
+    pub fn with_filter(mut self, filter: PatternFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Reject a reversed range instead of swapping its ends.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Validate the options and build the configuration.
+    ///
+    /// # Errors
+    ///
+    /// - `ZeroGames` if no tickets are requested
+    /// - `InvalidRange` if the range is empty, or reversed in strict mode
+    /// - `PickExceedsRange` if the pick is zero or exceeds the range
+    /// - `InvalidDrawSize` if the draw size is zero or exceeds the range
+    /// - `BetTooSmall` if more balls are drawn than picked
+    /// - `TooManyUniqueGames` if more unique tickets are requested than exist
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::{BallRange, ConfigBuilder, LottoError};
+    ///
+    /// let reversed = ConfigBuilder::new(BallRange::mega_sena(), 6).with_range(60, 1);
+    /// assert!(reversed.clone().build().is_ok());
+    /// assert_eq!(
+    ///     reversed.strict().build().unwrap_err(),
+    ///     LottoError::InvalidRange { start: 60, end: 1 }
+    /// );
+    /// ```
+    pub fn build(self) -> Result<Config> {
+        let game_count = GameCount::new(self.games)?;
+
+        let (start, end) = if self.start <= self.end {
+            (self.start, self.end)
+        } else if self.strict {
+            return Err(LottoError::InvalidRange {
+                start: self.start,
+                end: self.end,
+            });
+        } else {
+            (self.end, self.start)
+        };
+        let range = BallRange::new(BallNumber::new(start), BallNumber::new(end))?;
+        let pick = PickCount::new(self.pick, &range)?;
+
+        let draw = self.draw.unwrap_or(self.pick);
+        let draw = PickCount::new(draw, &range).map_err(|_| LottoError::InvalidDrawSize {
+            draw_size: draw,
+            available: range.size(),
+        })?;
+        if draw.value() > pick.value() {
+            return Err(LottoError::BetTooSmall {
+                bet_size: pick.value(),
+                draw_size: draw.value(),
+            });
+        }
+
+        // Beyond u128 every request is feasible (game_count is a usize)
+        if self.unique
+            && let Ok(maximum) = probability::combination(range.size(), pick.value())
+            && game_count.value() as u128 > maximum
+        {
+            return Err(LottoError::TooManyUniqueGames {
+                requested: game_count.value(),
+                maximum,
+            });
+        }
+
+        Ok(Config {
+            game_count,
+            range,
+            pick,
+            draw,
+            seed: self.seed,
+            unique: self.unique,
+            order: self.order,
+            filter: self.filter,
+        })
+    }
+}
+
+impl From<GamePreset> for ConfigBuilder {
+    fn from(game: GamePreset) -> Self {
+        Self::from_preset(game)
+    }
 }
 
 /// Generate lottery tickets using the provided configuration.
 ///
-/// This is a convenience function that uses the configuration to generate
-/// unique lottery tickets.
+/// This is a convenience function that generates the configured number of
+/// tickets, unique unless the configuration allows repeats, satisfying its
+/// slip constraints and returned in its output order.
 ///
 /// # Arguments
 ///
@@ -119,7 +435,7 @@ impl Config {
 ///
 /// # Returns
 ///
-/// A Result containing a vector of unique tickets, or an error.
+/// A Result containing a vector of tickets, or an error.
 ///
 /// # Examples
 ///
@@ -137,7 +453,37 @@ pub fn generate_tickets<R: RandomNumberGenerator>(
     rng: &mut R,
     config: &Config,
 ) -> Result<Vec<Ticket>> {
-    generate_unique_tickets(rng, config.range(), config.pick(), config.game_count())
+    let (range, pick, game_count) = (config.range(), config.pick(), config.game_count());
+    if config.is_unique() && config.filter().is_empty() {
+        return generate_unique_tickets_ordered(rng, range, pick, game_count, config.order());
+    }
+
+    let layout = BetslipLayout::for_range(range);
+    let allowed = |ticket: &Ticket| config.filter().allows(&layout, ticket);
+    let mut tickets = if config.is_unique() {
+        generate_unique_tickets_filtered(rng, range, pick, game_count, allowed)?
+    } else {
+        // Repeats are allowed, so only the constraints can reject a ticket
+        let max_attempts = game_count.value() * 10_000;
+        let mut tickets = Vec::with_capacity(game_count.value());
+        let mut attempts = 0;
+        while tickets.len() < game_count.value() {
+            if attempts >= max_attempts {
+                return Err(LottoError::ConstraintsUnsatisfiable {
+                    requested: game_count.value(),
+                    generated: tickets.len(),
+                });
+            }
+            attempts += 1;
+            let ticket = generate_ticket(rng, range, pick);
+            if allowed(&ticket) {
+                tickets.push(ticket);
+            }
+        }
+        tickets
+    };
+    sort_tickets(&mut tickets, config.order(), range);
+    Ok(tickets)
 }
 
 #[cfg(test)]
@@ -178,6 +524,132 @@ mod tests {
         assert!(matches!(result, Err(LottoError::InvalidRange { .. })));
     }
 
+    #[test]
+    fn test_builder_from_preset_separates_draw_and_pick() {
+        let config = ConfigBuilder::from_preset(GamePreset::Lotofacil)
+            .with_games(3)
+            .with_pick(17)
+            .build()
+            .unwrap();
+        assert_eq!(config.range().size(), 25);
+        assert_eq!(config.pick().value(), 17);
+        assert_eq!(config.draw_size().value(), 15);
+        assert!(config.is_unique());
+
+        // A bare range draws as many balls as are picked
+        let config = ConfigBuilder::new(BallRange::powerball(), 5)
+            .build()
+            .unwrap();
+        assert_eq!(config.draw_size().value(), 5);
+    }
+
+    #[test]
+    fn test_builder_strict_rejects_reversed_range() {
+        let builder = ConfigBuilder::from_preset(GamePreset::Quina).with_range(80, 1);
+        let config = builder.clone().build().unwrap();
+        assert_eq!(config.range().start().value(), 1);
+        assert_eq!(
+            builder.strict().build().unwrap_err(),
+            LottoError::InvalidRange { start: 80, end: 1 }
+        );
+    }
+
+    #[test]
+    fn test_builder_validation_errors() {
+        let mega = || ConfigBuilder::from_preset(GamePreset::MegaSena);
+        assert_eq!(
+            mega().with_games(0).build().unwrap_err(),
+            LottoError::ZeroGames
+        );
+        assert_eq!(
+            mega().with_draw_size(7).build().unwrap_err(),
+            LottoError::BetTooSmall {
+                bet_size: 6,
+                draw_size: 7
+            }
+        );
+        assert_eq!(
+            mega().with_draw_size(0).build().unwrap_err(),
+            LottoError::InvalidDrawSize {
+                draw_size: 0,
+                available: 60
+            }
+        );
+        assert_eq!(
+            mega().with_pick(61).build().unwrap_err(),
+            LottoError::PickExceedsRange {
+                pick: 61,
+                available: 60
+            }
+        );
+        assert_eq!(
+            ConfigBuilder::new(BallRange::mega_sena(), 3)
+                .with_range(1, 5)
+                .with_games(11)
+                .build()
+                .unwrap_err(),
+            LottoError::TooManyUniqueGames {
+                requested: 11,
+                maximum: 10
+            }
+        );
+        // Repeats make any number of tickets feasible
+        assert!(
+            ConfigBuilder::new(BallRange::mega_sena(), 3)
+                .with_range(1, 5)
+                .with_games(11)
+                .with_unique(false)
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_generate_tickets_follows_builder_options() {
+        let config = ConfigBuilder::from_preset(GamePreset::MegaSena)
+            .with_games(20)
+            .with_seed(7)
+            .with_order(OutputOrder::Lexicographic)
+            .with_filter(PatternFilter::new().with_max_per_row(2))
+            .build()
+            .unwrap();
+        let tickets = generate_tickets(&mut config.rng(), &config).unwrap();
+        assert_eq!(tickets.len(), 20);
+        assert!(tickets.windows(2).all(|pair| pair[0] < pair[1]));
+        let slip = BetslipLayout::mega_sena();
+        assert!(tickets.iter().all(|t| config.filter().allows(&slip, t)));
+        assert_eq!(
+            tickets,
+            generate_tickets(&mut config.rng(), &config).unwrap()
+        );
+
+        // Without uniqueness, a tiny game must repeat tickets
+        let config = ConfigBuilder::new(BallRange::mega_sena(), 2)
+            .with_range(1, 3)
+            .with_games(10)
+            .with_unique(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            generate_tickets(&mut config.rng(), &config).unwrap().len(),
+            10
+        );
+
+        // With repeats allowed, only the slip constraints can fail
+        let config = ConfigBuilder::from_preset(GamePreset::MegaSena)
+            .with_unique(false)
+            .with_filter(PatternFilter::new().with_max_per_row(0))
+            .build()
+            .unwrap();
+        assert_eq!(
+            generate_tickets(&mut config.rng(), &config).unwrap_err(),
+            LottoError::ConstraintsUnsatisfiable {
+                requested: 1,
+                generated: 0
+            }
+        );
+    }
+
     #[test]
     fn test_generate_tickets_returns_correct_count() {
         let mut rng = rand::rng();
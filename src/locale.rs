@@ -0,0 +1,382 @@
+//! Languages and number formatting for people-facing text.
+//!
+//! The library's own messages, such as the [`LottoError`] `Display`, are
+//! always English. A [`Locale`] translates them and formats numbers the
+//! way its readers expect: `50,063,860` and `0.25` in English,
+//! `50.063.860` and `0,25` in Brazilian Portuguese.
+
+use crate::error::{LottoError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// A language for messages and numbers.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::locale::Locale;
+///
+/// let locale: Locale = "pt_BR.UTF-8".parse().unwrap();
+/// assert_eq!(locale, Locale::Portuguese);
+/// assert_eq!(locale.integer(50063860), "50.063.860");
+/// assert_eq!(Locale::English.integer(50063860), "50,063,860");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Locale {
+    /// English, the language of the library's own messages.
+    #[default]
+    English,
+    /// Brazilian Portuguese.
+    Portuguese,
+}
+
+impl Locale {
+    /// Pick the locale from the environment, like `gettext` does.
+    ///
+    /// The first non-empty of `LC_ALL`, `LC_MESSAGES` and `LANG` decides;
+    /// an unset, unknown or `C` locale means English.
+    pub fn from_env() -> Self {
+        Self::first_set(
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .into_iter()
+                .map(|name| std::env::var(name).ok()),
+        )
+    }
+
+    /// Parse the first non-empty value, falling back to English.
+    fn first_set(values: impl IntoIterator<Item = Option<String>>) -> Self {
+        values
+            .into_iter()
+            .flatten()
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Separator between groups of three integer digits.
+    pub fn thousands_separator(self) -> char {
+        match self {
+            Locale::English => ',',
+            Locale::Portuguese => '.',
+        }
+    }
+
+    /// Separator between the integer and fractional digits.
+    pub fn decimal_separator(self) -> char {
+        match self {
+            Locale::English => '.',
+            Locale::Portuguese => ',',
+        }
+    }
+
+    /// Localize a number written the way Rust formats it.
+    ///
+    /// Plain decimal numbers get grouped integer digits and the locale's
+    /// decimal separator; anything else, such as scientific notation or
+    /// `inf`, only has its decimal point replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A number such as `1234567.89`, `-42` or `6.47e-6`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::locale::Locale;
+    ///
+    /// assert_eq!(Locale::Portuguese.format_number("1234567.89"), "1.234.567,89");
+    /// assert_eq!(Locale::English.format_number("-1234"), "-1,234");
+    /// assert_eq!(Locale::Portuguese.format_number("6.47e-6"), "6,47e-6");
+    /// ```
+    pub fn format_number(self, text: &str) -> String {
+        let (sign, digits) = match text.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", text),
+        };
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits, None),
+        };
+        let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if integer.is_empty() || !all_digits(integer) || !fraction.is_none_or(all_digits) {
+            return text.replace('.', &self.decimal_separator().to_string());
+        }
+
+        let mut out = String::with_capacity(text.len() + integer.len() / 3);
+        out.push_str(sign);
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push(self.thousands_separator());
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator());
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// Format an integer, e.g. a count or a [`BigUint`](crate::BigUint),
+    /// with grouped digits.
+    pub fn integer(self, value: impl fmt::Display) -> String {
+        self.format_number(&value.to_string())
+    }
+
+    /// Format a decimal number with a fixed number of decimals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::locale::Locale;
+    ///
+    /// assert_eq!(Locale::Portuguese.decimal(2500.5, 2), "2.500,50");
+    /// assert_eq!(Locale::English.decimal(2500.5, 2), "2,500.50");
+    /// ```
+    pub fn decimal(self, value: f64, decimals: usize) -> String {
+        self.format_number(&format!("{:.*}", decimals, value))
+    }
+
+    /// Translate an error message.
+    ///
+    /// English is the error's own `Display`, unchanged. Other locales
+    /// translate the message and format its numbers; free-text details
+    /// carried by the error stay as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::LottoError;
+    /// use lotto_quick_pick::locale::Locale;
+    ///
+    /// let error = LottoError::TooManyUniqueGames { requested: 60000000, maximum: 50063860 };
+    /// assert_eq!(
+    ///     Locale::Portuguese.error_message(&error),
+    ///     "Não é possível gerar 60.000.000 bilhetes únicos (máximo possível: 50.063.860)"
+    /// );
+    /// assert_eq!(Locale::English.error_message(&error), error.to_string());
+    /// ```
+    pub fn error_message(self, error: &LottoError) -> String {
+        if self == Locale::English {
+            return error.to_string();
+        }
+        let n = |value: &dyn fmt::Display| self.integer(value);
+        match error {
+            LottoError::InvalidRange { start, end } => {
+                format!("O início ({}) deve ser menor que o fim ({})", start, end)
+            }
+            LottoError::PickExceedsRange { pick, available } => format!(
+                "Não é possível escolher {} bolas de um intervalo de {} números",
+                n(pick),
+                n(available)
+            ),
+            LottoError::ZeroGames => "O número de jogos deve ser pelo menos 1".to_string(),
+            LottoError::CalculationOverflow { operation } => {
+                format!("Overflow aritmético no cálculo: {}", operation)
+            }
+            LottoError::InvalidMatchCount {
+                match_count,
+                pick_count,
+            } => format!(
+                "Não é possível acertar {} bolas escolhendo apenas {}",
+                match_count, pick_count
+            ),
+            LottoError::TooManyUniqueGames { requested, maximum } => format!(
+                "Não é possível gerar {} bilhetes únicos (máximo possível: {})",
+                n(requested),
+                n(maximum)
+            ),
+            LottoError::UniqueGenerationFailed {
+                requested,
+                generated,
+            } => format!(
+                "Falha ao gerar {} bilhetes únicos (apenas {} gerados após o máximo de tentativas)",
+                n(requested),
+                n(generated)
+            ),
+            LottoError::InvalidTicketSize { expected, got } => format!(
+                "O bilhete deve ter exatamente {} bolas, mas tem {}",
+                expected, got
+            ),
+            LottoError::BallOutOfRange { value, start, end } => format!(
+                "A bola {} está fora do intervalo {}..={}",
+                value, start, end
+            ),
+            LottoError::DuplicateBall { value } => {
+                format!("O bilhete contém a bola repetida: {}", value)
+            }
+            LottoError::InvalidAmount { field, value } => format!(
+                "Valor inválido para {}: {}",
+                field,
+                self.format_number(&value.to_string())
+            ),
+            LottoError::DuplicatePrizeTier { matches } => format!(
+                "A faixa de prêmio de {} acertos foi definida mais de uma vez",
+                matches
+            ),
+            LottoError::BetTooSmall {
+                bet_size,
+                draw_size,
+            } => format!(
+                "Uma aposta deve marcar pelo menos {} bolas, mas marca {}",
+                draw_size, bet_size
+            ),
+            LottoError::InvalidBetslipLayout { columns, size } => format!(
+                "Não é possível dispor {} bolas em {} colunas",
+                size, columns
+            ),
+            LottoError::InvalidProbability { favorable, total } => format!(
+                "Probabilidade inválida: {} casos favoráveis de {}",
+                self.format_number(favorable),
+                self.format_number(total)
+            ),
+            LottoError::RankOutOfRange { rank, total } => format!(
+                "A posição {} está fora do intervalo de {} combinações",
+                self.format_number(rank),
+                self.format_number(total)
+            ),
+            LottoError::InvalidTicketCode { code, reason } => {
+                format!("Código de bilhete inválido '{}': {}", code, reason)
+            }
+            LottoError::InvalidStore { reason } => {
+                format!("Arquivo binário de bilhetes inválido: {}", reason)
+            }
+            LottoError::InvalidTicketLine { line, source } => {
+                format!("Linha {}: {}", line, self.error_message(source))
+            }
+            LottoError::EmptyTicketFile { path } => {
+                format!("Nenhum bilhete encontrado em {}", path)
+            }
+            LottoError::InvalidDrawSize {
+                draw_size,
+                available,
+            } => format!(
+                "Não é possível sortear {} bolas de um intervalo de {} números",
+                n(draw_size),
+                n(available)
+            ),
+            LottoError::ConstraintsUnsatisfiable {
+                requested,
+                generated,
+            } => format!(
+                "Apenas {} de {} bilhetes respeitam as restrições da cartela após o máximo de tentativas",
+                n(generated),
+                n(requested)
+            ),
+            LottoError::IoError(detail) => format!("Erro de E/S: {}", detail),
+            LottoError::ParseError(detail) => format!("Falha ao interpretar a entrada: {}", detail),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LottoError;
+
+    /// Parse a language tag such as `en`, `pt-BR` or a POSIX locale such
+    /// as `pt_BR.UTF-8`; only the language part is significant.
+    fn from_str(s: &str) -> Result<Self> {
+        let language = s
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Ok(Locale::English),
+            "pt" => Ok(Locale::Portuguese),
+            _ => Err(LottoError::ParseError(format!(
+                "unsupported language '{}' (expected en or pt-BR)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Locale::English => "en",
+            Locale::Portuguese => "pt-BR",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_tags_and_posix_locales() {
+        for tag in ["en", "en_US.UTF-8", "C", "POSIX", "C.UTF-8"] {
+            assert_eq!(tag.parse::<Locale>().unwrap(), Locale::English, "{}", tag);
+        }
+        for tag in ["pt", "pt-BR", "pt_BR.UTF-8", "PT_br@latin"] {
+            assert_eq!(
+                tag.parse::<Locale>().unwrap(),
+                Locale::Portuguese,
+                "{}",
+                tag
+            );
+        }
+        assert!(matches!(
+            "de_DE".parse::<Locale>(),
+            Err(LottoError::ParseError(_))
+        ));
+        assert_eq!(
+            Locale::Portuguese.to_string().parse(),
+            Ok(Locale::Portuguese)
+        );
+    }
+
+    #[test]
+    fn test_environment_precedence() {
+        let set = |value: &str| Some(value.to_string());
+        assert_eq!(
+            Locale::first_set([None, set("pt_BR.UTF-8"), set("en_US")]),
+            Locale::Portuguese
+        );
+        assert_eq!(
+            Locale::first_set([set(""), None, set("pt_BR")]),
+            Locale::Portuguese
+        );
+        assert_eq!(
+            Locale::first_set([set("de_DE"), set("pt_BR")]),
+            Locale::English
+        );
+        assert_eq!(Locale::first_set([None, None, None]), Locale::English);
+    }
+
+    #[test]
+    fn test_number_grouping() {
+        let pt = Locale::Portuguese;
+        assert_eq!(pt.integer(0), "0");
+        assert_eq!(pt.integer(999), "999");
+        assert_eq!(pt.integer(1000), "1.000");
+        assert_eq!(pt.integer(123456789u64), "123.456.789");
+        assert_eq!(pt.format_number("-1234.5"), "-1.234,5");
+        assert_eq!(pt.format_number("inf"), "inf");
+        assert_eq!(pt.format_number("never"), "never");
+        assert_eq!(Locale::English.decimal(0.126, 2), "0.13");
+        assert_eq!(Locale::English.decimal(1e6, 0), "1,000,000");
+    }
+
+    #[test]
+    fn test_translates_nested_errors() {
+        let error = LottoError::InvalidTicketLine {
+            line: 1200,
+            source: Box::new(LottoError::BallOutOfRange {
+                value: 61,
+                start: 1,
+                end: 60,
+            }),
+        };
+        assert_eq!(
+            Locale::Portuguese.error_message(&error),
+            "Linha 1200: A bola 61 está fora do intervalo 1..=60"
+        );
+        assert_eq!(
+            Locale::English.error_message(&error),
+            "Line 1200: Ball number 61 is outside range 1..=60"
+        );
+    }
+}
@@ -1,9 +1,49 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use lotto_quick_pick::{
-    self as lqp, Config, generate_tickets, probability::calculate_probability_for_config,
+    self as lqp, Config, ConfigBuilder, GamePreset, OutputFormat, OutputOrder, Probability,
+    ProbabilityFormat, Report,
+    batch::batch_win_probability,
+    betslip::{BetslipLayout, PatternFilter},
+    check::{CheckSummary, CheckedTicket, check_tickets},
+    code::encode,
+    expected_value::{PrizeTable, expected_value_for_config},
+    generate_ticket, generate_unique_tickets_filtered, generate_unique_tickets_ordered,
+    import::{TicketReader, dedup_tickets, load_tickets, read_tickets},
+    locale::Locale,
+    multi_bet::MultiBet,
+    newtypes::PickCount,
+    popularity::{PopularityModel, generate_unpopular_tickets_filtered},
+    probability::{
+        calculate_probability_big_for_config, calculate_probability_for_bet, combination_big,
+        match_distribution_for_bet,
+    },
+    rank::{combinations, rank},
+    server::serve,
+    settings::{Settings, SettingsFile},
+    sort_tickets,
+    store::{StoreEncoding, StoreHeader, StoreReader, StoreWriter, is_store},
+    ticket_key::TicketKey,
+    time_to_win::{DrawSchedule, time_to_win_for_bet},
 };
-use rand::rng;
+use rand::{SeedableRng, rngs::StdRng};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Format the English or the Portuguese text, whichever `--lang` selects.
+///
+/// Both texts take the same arguments, in the same order.
+macro_rules! tr {
+    ($en:literal, $pt:literal $(, $arg:expr)* $(,)?) => {
+        match locale() {
+            Locale::English => format!($en $(, $arg)*),
+            Locale::Portuguese => format!($pt $(, $arg)*),
+        }
+    };
+}
 
 /// Command-line lottery ticket generator.
 ///
@@ -11,26 +51,666 @@ use rand::rng;
 /// and displays winning probabilities.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
-    /// Shows the probability of winning a prize with the matched balls
-    #[arg(short = 'm', long, value_name = "MATCHED-BALLS")]
-    matched: Option<usize>,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Colors the output: auto (when writing to a terminal and NO_COLOR is unset), always or never [default: auto]
+    #[arg(long, value_name = "WHEN", value_enum, global = true)]
+    color: Option<ColorChoice>,
+
+    /// Language of the output: en or pt-BR [default: the settings file, else LC_ALL, LC_MESSAGES or LANG]
+    #[arg(long, value_name = "LANG", global = true)]
+    lang: Option<Locale>,
+
+    /// Writes errors as text or as one JSON object per line
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        value_enum,
+        global = true,
+        default_value_t
+    )]
+    error_format: ErrorFormat,
+
+    /// Reads settings from this file instead of the XDG config directory
+    #[arg(long, value_name = "FILE", global = true)]
+    config: Option<PathBuf>,
+
+    /// Applies a named profile from the settings file
+    #[arg(long, value_name = "NAME", global = true)]
+    profile: Option<String>,
+
+    /// Without a subcommand, the flags generate tickets as `generate` does
+    #[command(flatten)]
+    generate: GenerateArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generates unique tickets (the default when no subcommand is given)
+    Generate(GenerateArgs),
+    /// Shows the full match-distribution odds table for a game
+    Odds(OddsArgs),
+    /// Checks tickets against a draw result, highlighting the matched balls
+    Check(CheckArgs),
+    /// Plays tickets against many random draws and compares the hits with the odds
+    Simulate(SimulateArgs),
+    /// Lists the tickets of a game in rank order
+    Enumerate(EnumerateArgs),
+    /// Validates ticket files and binary stores, reporting every problem found
+    Verify(VerifyArgs),
+    /// Shows the expected value and return-to-player of a single ticket
+    Ev(EvArgs),
+    /// Converts a text ticket file into a compact binary store
+    Pack(PackArgs),
+    /// Converts a binary ticket store back into text
+    Unpack(UnpackArgs),
+    /// Prompts for the game and generates tickets or shows odds from a menu
+    Interactive,
+    /// Serves generate, odds and check as a JSON API over HTTP on localhost
+    Serve(ServeArgs),
+}
 
-    /// Sets the number of tickets that will be generated
-    #[arg(short, long, value_name = "TICKETS", default_value_t = 1)]
-    tickets: usize,
+/// Game parameters shared by the subcommands.
+#[derive(Args, Debug)]
+struct GameArgs {
+    /// Uses the ball range, pick and draw size of a known game: mega-sena, lotofacil,
+    /// quina, dupla-sena, powerball or lotomania (each can still be overridden)
+    #[arg(short, long, value_name = "GAME")]
+    game: Option<GamePreset>,
 
     /// Sets the starting number of the lottery game
     #[arg(short, long, value_name = "START-NUMBER")]
-    start_number: u8,
+    start_number: Option<u8>,
 
     /// Sets the ending number of the lottery game
     #[arg(short, long, value_name = "END-NUMBER")]
-    end_number: u8,
+    end_number: Option<u8>,
 
     /// Sets the quantity of numbers that will be picked for each ticket
     #[arg(short, long, value_name = "PICK")]
-    pick: usize,
+    pick: Option<usize>,
+}
+
+impl GameArgs {
+    /// Build a single-game Config from the game parameters.
+    fn config(&self) -> lqp::Result<Config> {
+        self.config_with_games(1)
+    }
+
+    /// Build a Config for a number of games, letting explicit flags override the preset.
+    ///
+    /// The preset's draw size is kept unless the pick is overridden below it.
+    /// A start number above the end number is an error, not swapped.
+    fn config_with_games(&self, games: usize) -> lqp::Result<Config> {
+        let range = self.game.map(GamePreset::range);
+        let (Some(start), Some(end), Some(pick)) = (
+            self.start_number.or(range.map(|r| r.start().value())),
+            self.end_number.or(range.map(|r| r.end().value())),
+            self.pick.or(self.game.map(|game| game.pick().value())),
+        ) else {
+            return Err(lqp::LottoError::ParseError(
+                "no game given: use --game, or --start-number, --end-number and --pick, \
+                 or set a game in the settings file"
+                    .to_string(),
+            ));
+        };
+        let draw = self
+            .game
+            .map(|game| game.draw_size().value())
+            .filter(|&draw| draw <= pick)
+            .unwrap_or(pick);
+        let range = lqp::BallRange::new(lqp::BallNumber::new(start), lqp::BallNumber::new(end))?;
+        ConfigBuilder::new(range, pick)
+            .with_games(games)
+            .with_draw_size(draw)
+            .build()
+    }
+
+    /// Take the game from the settings where the command line leaves it open.
+    ///
+    /// A `--game` flag replaces the settings' game entirely; single flags
+    /// such as `--pick` only override that part of it.
+    fn apply(&mut self, settings: &Settings) {
+        if self.game.is_some() {
+            return;
+        }
+        self.game = settings
+            .game()
+            .and_then(|name| name.parse::<GamePreset>().ok());
+        self.start_number = self.start_number.or(settings.start());
+        self.end_number = self.end_number.or(settings.end());
+        self.pick = self.pick.or(settings.pick());
+    }
+}
+
+/// Output options shared by the commands that list tickets.
+#[derive(Args, Debug)]
+struct OutputArgs {
+    /// Writes the tickets as text, json, csv or ndjson [default: text]
+    #[arg(long, value_name = "FORMAT")]
+    format: Option<OutputFormat>,
+
+    /// Shows the combinatorial rank of each ticket
+    #[arg(long)]
+    rank: bool,
+
+    /// Shows the short shareable code of each ticket (accepted back by --input)
+    #[arg(long)]
+    code: bool,
+}
+
+impl OutputArgs {
+    /// Get the output format, text unless one was given.
+    fn format(&self) -> OutputFormat {
+        self.format.unwrap_or_default()
+    }
+
+    /// Take the format from the settings unless one was given.
+    fn apply(&mut self, settings: &Settings) {
+        self.format = self.format.or(settings.format());
+    }
+}
+
+#[derive(Args, Debug)]
+struct GenerateArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Shows the probability of winning a prize with the matched balls
+    #[arg(short = 'm', long, value_name = "MATCHED-BALLS")]
+    matched: Option<usize>,
+
+    /// Sets the number of tickets that will be generated [default: 1]
+    #[arg(short, long, value_name = "TICKETS")]
+    tickets: Option<usize>,
+
+    /// Number of balls drawn, when each ticket is a multi-number bet marking more
+    #[arg(short, long, value_name = "DRAW-SIZE")]
+    draw_size: Option<usize>,
+
+    /// Lists the simple bets covered by each multi-number bet
+    #[arg(long, requires = "draw_size")]
+    expand: bool,
+
+    /// Prefers combinations other players rarely pick, to avoid sharing the jackpot
+    #[arg(long)]
+    avoid_popular: bool,
+
+    /// Reads the tickets from a file (or `-` for standard input) instead of generating them
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["avoid_popular", "max_per_row", "max_per_column", "max_per_diagonal", "no_lines"]
+    )]
+    input: Option<PathBuf>,
+
+    /// Removes repeated tickets, keeping the first of each
+    #[arg(long)]
+    dedup: bool,
+
+    /// Seeds the random generator, to reproduce a previous run
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Lists the tickets in generation, lexicographic or rank order
+    #[arg(long, value_name = "ORDER", default_value_t = OutputOrder::Generation)]
+    order: OutputOrder,
+
+    #[command(flatten)]
+    output: OutputArgs,
+
+    #[command(flatten)]
+    popularity: PopularityArgs,
+
+    #[command(flatten)]
+    slip: SlipArgs,
+}
+
+#[derive(Args, Debug)]
+struct EvArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Price of a single ticket
+    #[arg(long, value_name = "PRICE")]
+    price: f64,
+
+    /// Prize paid for matching every picked ball
+    #[arg(long, value_name = "AMOUNT")]
+    jackpot: f64,
+
+    /// Lower prize tier as MATCHES=PAYOUT (repeatable, e.g. --tier 5=50000)
+    #[arg(long, value_name = "MATCHES=PAYOUT", value_parser = parse_tier)]
+    tier: Vec<(usize, f64)>,
+
+    /// Ticket to evaluate, shrinking the jackpot by its expected sharing (e.g. "4 8 15 16 23 42")
+    #[arg(long, value_name = "BALLS", value_parser = parse_ticket)]
+    ticket: Option<lqp::Ticket>,
+
+    #[command(flatten)]
+    popularity: PopularityArgs,
+}
+
+#[derive(Args, Debug)]
+struct OddsArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Number of balls drawn, to show the odds of a multi-number bet of PICK balls
+    #[arg(short, long, value_name = "DRAW-SIZE")]
+    draw_size: Option<usize>,
+
+    /// Number of draws played per week, to show waiting times in years
+    #[arg(long, value_name = "DRAWS")]
+    draws_per_week: Option<f64>,
+
+    /// Number of distinct tickets played in each draw
+    #[arg(long, value_name = "TICKETS", default_value_t = 1)]
+    tickets_per_draw: usize,
+}
+
+#[derive(Args, Debug)]
+struct CheckArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Drawn balls to check against (e.g. "4 8 15 16 23 42")
+    #[arg(short, long, value_name = "BALLS", value_parser = parse_ticket)]
+    result: lqp::Ticket,
+
+    /// Ticket file to check (`-` for standard input)
+    #[arg(short, long, value_name = "FILE", required_unless_present = "ticket")]
+    input: Option<PathBuf>,
+
+    /// Ticket to check (repeatable)
+    #[arg(long, value_name = "BALLS", value_parser = parse_ticket)]
+    ticket: Vec<lqp::Ticket>,
+
+    /// Number of balls drawn, when the tickets are multi-number bets marking more
+    #[arg(short, long, value_name = "DRAW-SIZE")]
+    draw_size: Option<usize>,
+
+    /// Prize for matching every drawn ball, to total the winnings
+    #[arg(long, value_name = "AMOUNT")]
+    jackpot: Option<f64>,
+
+    /// Lower prize tier as MATCHES=PAYOUT (repeatable, e.g. --tier 5=50000)
+    #[arg(long, value_name = "MATCHES=PAYOUT", value_parser = parse_tier)]
+    tier: Vec<(usize, f64)>,
+}
+
+impl GenerateArgs {
+    /// Take the game, ticket count, format and seed from the settings where not given.
+    fn apply(&mut self, settings: &Settings) {
+        self.game.apply(settings);
+        self.output.apply(settings);
+        self.tickets = self.tickets.or(settings.tickets());
+        self.seed = self.seed.or(settings.seed());
+    }
+}
+
+#[derive(Args, Debug)]
+struct SimulateArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Number of random draws to play
+    #[arg(long, value_name = "DRAWS", default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    draws: u64,
+
+    /// Number of random tickets to play, unless --input or --ticket is given [default: 1]
+    #[arg(short, long, value_name = "TICKETS")]
+    tickets: Option<usize>,
+
+    /// Ticket file to play (`-` for standard input)
+    #[arg(short, long, value_name = "FILE")]
+    input: Option<PathBuf>,
+
+    /// Ticket to play (repeatable)
+    #[arg(long, value_name = "BALLS", value_parser = parse_ticket)]
+    ticket: Vec<lqp::Ticket>,
+
+    /// Number of balls drawn, when the tickets are multi-number bets marking more
+    #[arg(short, long, value_name = "DRAW-SIZE")]
+    draw_size: Option<usize>,
+
+    /// Seeds the random generator, to reproduce a previous run
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+
+    /// Price of each ticket, to total the cost of playing
+    #[arg(long, value_name = "PRICE")]
+    price: Option<f64>,
+
+    /// Prize for matching every drawn ball, to total the winnings
+    #[arg(long, value_name = "AMOUNT")]
+    jackpot: Option<f64>,
+
+    /// Lower prize tier as MATCHES=PAYOUT (repeatable, e.g. --tier 5=50000)
+    #[arg(long, value_name = "MATCHES=PAYOUT", value_parser = parse_tier)]
+    tier: Vec<(usize, f64)>,
+}
+
+impl SimulateArgs {
+    /// Take the game, ticket count and seed from the settings where not given.
+    fn apply(&mut self, settings: &Settings) {
+        self.game.apply(settings);
+        self.tickets = self.tickets.or(settings.tickets());
+        self.seed = self.seed.or(settings.seed());
+    }
+}
+
+#[derive(Args, Debug)]
+struct EnumerateArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Rank of the first ticket listed
+    #[arg(long, value_name = "RANK", default_value_t = lqp::BigUint::zero())]
+    from: lqp::BigUint,
+
+    /// Lists at most N tickets
+    #[arg(short = 'n', long, value_name = "N")]
+    limit: Option<usize>,
+
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+struct VerifyArgs {
+    /// Text or CSV ticket files and binary stores to verify (`-` for standard input)
+    #[arg(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Treats repeated tickets as errors
+    #[arg(long)]
+    strict: bool,
+}
+
+#[derive(Args, Debug)]
+struct PackArgs {
+    #[command(flatten)]
+    game: GameArgs,
+
+    /// Text or CSV ticket file to read (`-` for standard input)
+    #[arg(short, long, value_name = "FILE")]
+    input: PathBuf,
+
+    /// Binary store to write
+    #[arg(short, long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// Stores each ticket as its bitmap or its combinatorial rank
+    #[arg(long, value_name = "ENCODING", default_value_t = StoreEncoding::Bitmap)]
+    encoding: StoreEncoding,
+
+    /// Name of the game recorded in the store
+    #[arg(long, value_name = "NAME")]
+    name: Option<String>,
+
+    /// Description of the generator recorded in the store
+    #[arg(long, value_name = "GENERATOR")]
+    generator: Option<String>,
+
+    /// Seed the tickets were generated from, recorded in the store
+    #[arg(long, value_name = "SEED")]
+    seed: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+struct UnpackArgs {
+    /// Binary store to read (`-` for standard input)
+    #[arg(value_name = "FILE")]
+    input: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Port to listen on at 127.0.0.1 (0 picks a free port)
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+}
+
+/// When to color the output.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Turn colors on or off for the rest of the program.
+    fn apply(self) {
+        let enabled = match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+        };
+        colored::control::set_override(enabled);
+    }
+}
+
+/// How errors are written to standard error.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ErrorFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// The `--error-format` in effect, set once by `main`.
+static ERROR_FORMAT: OnceLock<ErrorFormat> = OnceLock::new();
+
+/// The `--lang` in effect, set once by `main`.
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// The language of the output.
+fn locale() -> Locale {
+    LOCALE.get().copied().unwrap_or_default()
+}
+
+/// Format a count or other integer for the `--lang`.
+fn num(value: impl std::fmt::Display) -> String {
+    locale().integer(value)
+}
+
+/// Format a decimal number for the `--lang`.
+fn dec(value: f64, decimals: usize) -> String {
+    locale().decimal(value, decimals)
+}
+
+/// Portuguese for the contexts given to `report`, keyed by the English.
+const ERROR_CONTEXTS: &[(&str, &str)] = &[
+    (
+        "Batch probability calculation error",
+        "Erro no cálculo da probabilidade do lote",
+    ),
+    ("Configuration error", "Erro de configuração"),
+    ("Enumeration error", "Erro na enumeração"),
+    ("Expected value error", "Erro no valor esperado"),
+    ("Generation error", "Erro na geração"),
+    ("Input error", "Erro de entrada"),
+    ("Interactive mode error", "Erro no modo interativo"),
+    ("Output error", "Erro de saída"),
+    ("Pack error", "Erro ao empacotar"),
+    ("Popularity model error", "Erro no modelo de popularidade"),
+    (
+        "Probability calculation error",
+        "Erro no cálculo de probabilidade",
+    ),
+    ("Result error", "Erro no resultado"),
+    ("Server error", "Erro no servidor"),
+    ("Settings error", "Erro nas configurações"),
+    ("Ticket error", "Erro no bilhete"),
+    ("Unpack error", "Erro ao desempacotar"),
+];
+
+/// Write an error to standard error in the `--error-format`.
+///
+/// `context` says what failed. Text is written in the `--lang`; the JSON
+/// form stays English for scripts and carries the context next to the
+/// error's category, variant and fields.
+fn report(context: &str, error: &lqp::LottoError) {
+    match ERROR_FORMAT.get().copied().unwrap_or_default() {
+        ErrorFormat::Text => {
+            let context = match locale() {
+                Locale::English => context,
+                Locale::Portuguese => ERROR_CONTEXTS
+                    .iter()
+                    .find(|(en, _)| *en == context)
+                    .map_or(context, |(_, pt)| pt),
+            };
+            let message = locale().error_message(error);
+            eprintln!("{}", format!("{}: {}", context, message).red().bold());
+        }
+        ErrorFormat::Json => eprintln!(
+            "{}",
+            lqp::json::Json::object([
+                ("context", lqp::json::Json::from(context)),
+                ("error", error.to_json())
+            ])
+        ),
+    }
+}
+
+/// Report an error and exit with the code of its category.
+fn fail(context: &str, error: &lqp::LottoError) -> ! {
+    report(context, error);
+    std::process::exit(error.category().exit_code());
+}
+
+/// Options for the betslip layout and its pattern filters.
+#[derive(Args, Debug)]
+struct SlipArgs {
+    /// Number of columns on the betslip [default: the game's slip, or 10]
+    #[arg(long, value_name = "COLUMNS")]
+    slip_columns: Option<usize>,
+
+    /// Shows each ticket marked on its betslip
+    #[arg(long)]
+    show_slip: bool,
+
+    /// Allows at most N balls in any row of the slip
+    #[arg(long, value_name = "N")]
+    max_per_row: Option<usize>,
+
+    /// Allows at most N balls in any column of the slip
+    #[arg(long, value_name = "N")]
+    max_per_column: Option<usize>,
+
+    /// Allows at most N balls on any diagonal of the slip
+    #[arg(long, value_name = "N")]
+    max_per_diagonal: Option<usize>,
+
+    /// Rejects three or more adjacent balls in a straight line on the slip
+    #[arg(long)]
+    no_lines: bool,
+}
+
+impl SlipArgs {
+    /// Build the betslip layout for a game.
+    fn layout(&self, config: &Config) -> lqp::Result<BetslipLayout> {
+        match self.slip_columns {
+            Some(columns) => BetslipLayout::new(config.range(), columns),
+            None => Ok(BetslipLayout::for_range(config.range())),
+        }
+    }
+
+    /// Build the pattern filter from the limits given.
+    fn filter(&self) -> PatternFilter {
+        let mut filter = PatternFilter::new();
+        if let Some(max) = self.max_per_row {
+            filter = filter.with_max_per_row(max);
+        }
+        if let Some(max) = self.max_per_column {
+            filter = filter.with_max_per_column(max);
+        }
+        if let Some(max) = self.max_per_diagonal {
+            filter = filter.with_max_per_diagonal(max);
+        }
+        if self.no_lines {
+            filter = filter.with_no_straight_line(3);
+        }
+        filter
+    }
+}
+
+/// Options for the number-popularity model.
+#[derive(Args, Debug)]
+struct PopularityArgs {
+    /// Loads the popularity model from a file instead of the built-in birthday bias
+    #[arg(long, value_name = "FILE")]
+    popularity: Option<std::path::PathBuf>,
+
+    /// Number of tickets sold per draw [default: 10000000, or the value in the file]
+    #[arg(long, value_name = "PLAYERS")]
+    players: Option<f64>,
+}
+
+impl PopularityArgs {
+    /// Check if a popularity option was given.
+    fn is_set(&self) -> bool {
+        self.popularity.is_some() || self.players.is_some()
+    }
+
+    /// Build the popularity model for a game.
+    fn model(&self, config: &Config) -> lqp::Result<PopularityModel> {
+        let model = match &self.popularity {
+            Some(path) => PopularityModel::load(path, config.range(), config.pick())?,
+            None => PopularityModel::birthday_bias(config.range(), config.pick(), DEFAULT_PLAYERS)?,
+        };
+        match self.players {
+            Some(players) => model.with_players(players),
+            None => Ok(model),
+        }
+    }
+}
+
+/// Tickets sold per draw assumed by the built-in popularity model.
+const DEFAULT_PLAYERS: f64 = 10_000_000.0;
+
+/// Parse a prize tier given as `MATCHES=PAYOUT`.
+fn parse_tier(value: &str) -> Result<(usize, f64), String> {
+    let (matches, payout) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected MATCHES=PAYOUT, got '{}'", value))?;
+    let matches = matches
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid match count '{}': {}", matches, e))?;
+    let payout = payout
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid payout '{}': {}", payout, e))?;
+    Ok((matches, payout))
+}
+
+/// Parse a ticket given as ball numbers separated by spaces or commas.
+///
+/// Only the numbers are parsed here; the game rules are checked later.
+fn parse_ticket(value: &str) -> Result<lqp::Ticket, String> {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map(lqp::BallNumber::new)
+                .map_err(|e| format!("invalid ball '{}': {}", s, e))
+        })
+        .collect::<Result<_, _>>()
+        .map(lqp::Ticket::new)
 }
 
 /// Display generated tickets with colored formatting.
@@ -44,109 +724,1575 @@ fn display_tickets(tickets: &[lqp::Ticket]) {
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    let _ = ERROR_FORMAT.set(cli.error_format);
+    cli.color.unwrap_or(ColorChoice::Auto).apply();
 
+    let settings = match load_settings(&cli) {
+        Ok(settings) => settings,
+        Err(e) => {
+            let _ = LOCALE.set(cli.lang.unwrap_or_else(Locale::from_env));
+            fail("Settings error", &e)
+        }
+    };
+    cli.apply(&settings);
+    let _ = LOCALE.set(cli.lang.unwrap_or_else(Locale::from_env));
+
+    match cli.command {
+        Some(Command::Generate(args)) => run_generate(&args),
+        Some(Command::Odds(args)) => run_odds(&args),
+        Some(Command::Check(args)) => run_check(&args),
+        Some(Command::Simulate(args)) => run_simulate(&args),
+        Some(Command::Enumerate(args)) => run_enumerate(&args),
+        Some(Command::Verify(args)) => run_verify(&args),
+        Some(Command::Ev(args)) => run_ev(&args),
+        Some(Command::Pack(args)) => run_pack(&args),
+        Some(Command::Unpack(args)) => run_unpack(&args),
+        Some(Command::Interactive) => run_interactive(),
+        Some(Command::Serve(args)) => run_serve(&args),
+        None => run_generate(&cli.generate),
+    }
+}
+
+/// Load the settings file, `--config` or the one in the XDG config
+/// directory if it exists, and select the `--profile`.
+fn load_settings(cli: &Cli) -> lqp::Result<Settings> {
+    let file = match &cli.config {
+        Some(path) => SettingsFile::load(path)?,
+        None => match SettingsFile::default_path().filter(|path| path.exists()) {
+            Some(path) => SettingsFile::load(path)?,
+            None => SettingsFile::default(),
+        },
+    };
+    let settings = file.profile(cli.profile.as_deref())?;
+
+    // The library checks the values; the game name must be in its catalog
+    if let Some(game) = settings.game() {
+        game.parse::<GamePreset>()?;
+    }
+    Ok(settings)
+}
+
+impl Cli {
+    /// Fill the options not given on the command line from the settings.
+    fn apply(&mut self, settings: &Settings) {
+        if self.color.is_none()
+            && let Some(color) = settings.color()
+        {
+            self.color = ColorChoice::from_str(color, true).ok();
+            self.color.unwrap_or(ColorChoice::Auto).apply();
+        }
+        self.lang = self.lang.or(settings.lang());
+
+        match &mut self.command {
+            None => self.generate.apply(settings),
+            Some(Command::Generate(args)) => args.apply(settings),
+            Some(Command::Odds(args)) => args.game.apply(settings),
+            Some(Command::Check(args)) => args.game.apply(settings),
+            Some(Command::Simulate(args)) => args.apply(settings),
+            Some(Command::Enumerate(args)) => {
+                args.game.apply(settings);
+                args.output.apply(settings);
+            }
+            Some(Command::Verify(args)) => args.game.apply(settings),
+            Some(Command::Ev(args)) => args.game.apply(settings),
+            Some(Command::Pack(args)) => args.game.apply(settings),
+            Some(Command::Unpack(_)) | Some(Command::Interactive) | Some(Command::Serve(_)) => {}
+        }
+    }
+}
+
+/// Generate tickets and optionally show odds.
+fn run_generate(cli: &GenerateArgs) {
     // Create configuration with error handling
-    let config = match Config::new(cli.tickets, cli.start_number, cli.end_number, cli.pick) {
+    let config = match cli.game.config_with_games(cli.tickets.unwrap_or(1)) {
         Ok(cfg) => cfg,
-        Err(e) => {
-            eprintln!("{}", format!("Configuration error: {}", e).red().bold());
-            std::process::exit(1);
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    // Build the popularity model when it is used
+    let popularity = if cli.avoid_popular || cli.popularity.is_set() {
+        match cli.popularity.model(&config) {
+            Ok(model) => Some(model),
+            Err(e) => fail("Popularity model error", &e),
         }
+    } else {
+        None
     };
 
-    // Generate tickets
-    let mut rng = rng();
-    let tickets = match generate_tickets(&mut rng, &config) {
+    let layout = match cli.slip.layout(&config) {
+        Ok(layout) => layout,
+        Err(e) => fail("Configuration error", &e),
+    };
+    let filter = cli.slip.filter();
+
+    // Read tickets from a file, or generate them from a fresh seed unless one is given
+    let seed = cli.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let generated = match (&cli.input, &popularity) {
+        (Some(path), _) => read_input(path, &config),
+        (None, Some(model)) if cli.avoid_popular => {
+            generate_unpopular_tickets_filtered(&mut rng, model, config.game_count(), |t| {
+                filter.allows(&layout, t)
+            })
+        }
+        _ if !filter.is_empty() => generate_unique_tickets_filtered(
+            &mut rng,
+            config.range(),
+            config.pick(),
+            config.game_count(),
+            |t| filter.allows(&layout, t),
+        ),
+        _ => generate_unique_tickets_ordered(
+            &mut rng,
+            config.range(),
+            config.pick(),
+            config.game_count(),
+            cli.order,
+        ),
+    };
+    let mut tickets = match generated {
         Ok(t) => t,
-        Err(e) => {
-            eprintln!("{}", format!("Generation error: {}", e).red().bold());
-            std::process::exit(1);
+        Err(e) if cli.input.is_some() => fail("Input error", &e),
+        Err(e) => fail("Generation error", &e),
+    };
+    if cli.dedup {
+        let removed = dedup_tickets(&mut tickets, config.range());
+        if removed > 0 {
+            eprintln!(
+                "{}",
+                tr!(
+                    "Removed {} duplicate tickets",
+                    "{} bilhetes repetidos removidos",
+                    num(removed)
+                )
+                .yellow()
+            );
         }
+    }
+    sort_tickets(&mut tickets, cli.order, config.range());
+
+    // Tickets marking more balls than are drawn are multi-number bets
+    let draw = match draw_pick(&config, cli.draw_size) {
+        Ok(draw) => draw,
+        Err(e) => fail("Configuration error", &e),
     };
 
+    // Write machine-readable formats instead of the text report
+    if cli.output.format() != OutputFormat::Text {
+        // A seed only reproduces generated tickets
+        let seed = cli.input.is_none().then_some(seed);
+        write_report(&cli.output, &config, &tickets, draw, seed, cli.matched);
+        return;
+    }
+
     // Display tickets
-    display_tickets(&tickets);
+    if cli.output.rank || cli.output.code {
+        display_annotated(&tickets, &config, cli.output.rank, cli.output.code);
+    } else if cli.slip.show_slip {
+        for ticket in &tickets {
+            display_slip(ticket, &layout);
+        }
+    } else {
+        display_tickets(&tickets);
+    }
+    if draw != *config.pick() {
+        display_multi_bets(&tickets, &config, &draw, cli.expand);
+    }
+    if let Some(model) = &popularity {
+        display_popularity(&tickets, model);
+    }
 
     // Display probability if requested
     if let Some(matched_balls) = cli.matched {
-        match calculate_probability_for_config(&config, matched_balls) {
-            Ok((favorable, total)) => {
-                // Simplify the fraction if possible
-                let gcd = gcd_u128(favorable, total);
-                let simplified_favorable = favorable / gcd;
-                let simplified_total = total / gcd;
-
-                if simplified_favorable == 1 {
-                    println!(
-                        "\nYour probability of matching {} balls is {} in {}",
-                        matched_balls,
-                        simplified_favorable.to_string().bright_yellow(),
-                        simplified_total.to_string().bright_yellow()
-                    );
-                } else {
-                    println!(
-                        "\nYour probability of matching {} balls is approximately 1 in {}",
-                        matched_balls,
-                        (simplified_total / simplified_favorable)
-                            .to_string()
-                            .bright_yellow()
-                    );
+        match calculate_probability_for_bet(
+            config.range().size(),
+            config.pick().value(),
+            draw.value(),
+            matched_balls,
+        ) {
+            Ok((favorable, total)) => display_match_probability(matched_balls, favorable, total),
+            Err(e) => fail("Probability calculation error", &e),
+        }
+
+        if tickets.len() > 1 && draw == *config.pick() {
+            display_batch_probability(&mut rng, &tickets, &config, matched_balls);
+        }
+    }
+}
+
+/// Print the "1 in N" odds of matching a number of balls.
+fn display_match_probability(matched_balls: usize, favorable: lqp::BigUint, total: lqp::BigUint) {
+    let probability =
+        Probability::new(favorable, total).expect("favorable draws never exceed the total");
+    let odds = probability
+        .display(ProbabilityFormat::OneIn)
+        .with_locale(locale())
+        .to_string()
+        .bright_yellow();
+
+    if probability.numerator() == &lqp::BigUint::one() {
+        println!(
+            "{}",
+            tr!(
+                "\nYour probability of matching {} balls is {}",
+                "\nSua probabilidade de acertar {} bolas é {}",
+                matched_balls,
+                odds
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            tr!(
+                "\nYour probability of matching {} balls is approximately {}",
+                "\nSua probabilidade de acertar {} bolas é de aproximadamente {}",
+                matched_balls,
+                odds
+            )
+        );
+    }
+}
+
+/// Read tickets from a file, or from standard input when the path is `-`.
+fn read_input(path: &Path, config: &Config) -> lqp::Result<Vec<lqp::Ticket>> {
+    let tickets = if path == Path::new("-") {
+        read_tickets(std::io::stdin().lock(), config.range(), config.pick())?
+    } else {
+        load_tickets(path, config.range(), config.pick())?
+    };
+    if tickets.is_empty() {
+        let path = if path == Path::new("-") {
+            tr!("standard input", "entrada padrão")
+        } else {
+            path.display().to_string()
+        };
+        return Err(lqp::LottoError::EmptyTicketFile { path });
+    }
+    Ok(tickets)
+}
+
+/// Write the tickets in a machine-readable format.
+fn write_report(
+    output: &OutputArgs,
+    config: &Config,
+    tickets: &[lqp::Ticket],
+    draw: PickCount,
+    seed: Option<u64>,
+    matched: Option<usize>,
+) {
+    let mut report = Report::new(config, tickets).with_draw_size(draw);
+    if let Some(seed) = seed {
+        report = report.with_seed(seed);
+    }
+    if output.rank {
+        report = report.with_ranks();
+    }
+    if output.code {
+        report = report.with_codes();
+    }
+    if let Some(matched_balls) = matched {
+        report = report.with_matched(matched_balls);
+    }
+
+    match report.render(output.format()) {
+        Ok(Some(output)) => print!("{}", output),
+        Ok(None) => {}
+        Err(e) => fail("Output error", &e),
+    }
+}
+
+/// Display generated tickets followed by their combinatorial rank and/or code.
+fn display_annotated(tickets: &[lqp::Ticket], config: &Config, show_rank: bool, show_code: bool) {
+    for ticket in tickets {
+        let mut line = ticket.to_string().bright_green().to_string();
+        if show_rank {
+            let rank = rank(ticket, config.range()).expect("tickets are in range");
+            let rank = rank.to_string().bright_yellow();
+            line.push_str(&tr!("  rank {}", "  posição {}", rank));
+        }
+        if show_code {
+            let code = encode(ticket, config.range()).expect("tickets are in range");
+            line.push_str(&tr!("  code {}", "  código {}", code.bright_yellow()));
+        }
+        println!("{}", line);
+    }
+}
+
+/// Display a ticket on its betslip, highlighting the marked balls.
+fn display_slip(ticket: &lqp::Ticket, layout: &BetslipLayout) {
+    println!("{}", ticket.to_string().bright_green());
+    let mut slip = layout.render(ticket);
+    for ball in ticket.balls() {
+        let marked = format!("[{}]", ball);
+        slip = slip.replace(&marked, &marked.bright_green().to_string());
+    }
+    for line in slip.lines() {
+        println!("  {}", line);
+    }
+    println!();
+}
+
+/// Display how many other players are expected to share each ticket's jackpot.
+fn display_popularity(tickets: &[lqp::Ticket], model: &PopularityModel) {
+    println!(
+        "{}",
+        tr!(
+            "\nExpected jackpot co-winners with {} tickets sold:",
+            "\nGanhadores esperados do prêmio principal com {} bilhetes vendidos:",
+            num(model.players())
+        )
+    );
+    for ticket in tickets {
+        let co_winners = dec(model.expected_co_winners(ticket), 4);
+        let share = format!("{}%", dec(model.jackpot_share(ticket) * 100.0, 2)).bright_yellow();
+        println!(
+            "{}",
+            tr!(
+                "{}  {:>10} co-winners, keeps {}",
+                "{}  {:>10} coganhadores, fica com {}",
+                ticket,
+                co_winners,
+                share
+            )
+        );
+    }
+}
+
+/// Get the draw size, which defaults to the game's.
+fn draw_pick(config: &Config, draw_size: Option<usize>) -> lqp::Result<PickCount> {
+    let Some(draw_size) = draw_size else {
+        return Ok(*config.draw_size());
+    };
+    let draw = PickCount::new(draw_size, config.range())?;
+    if draw.value() > config.pick().value() {
+        return Err(lqp::LottoError::BetTooSmall {
+            bet_size: config.pick().value(),
+            draw_size: draw.value(),
+        });
+    }
+    Ok(draw)
+}
+
+/// Display the price of multi-number bets and, optionally, their simple bets.
+fn display_multi_bets(tickets: &[lqp::Ticket], config: &Config, draw: &PickCount, expand: bool) {
+    let bets: Vec<MultiBet> = tickets
+        .iter()
+        .map(|ticket| MultiBet::try_new(ticket.balls().to_vec(), config.range(), draw))
+        .collect::<lqp::Result<_>>()
+        .expect("generated tickets are valid bets");
+
+    let simple_bets = bets[0].simple_bet_count();
+    let total = &simple_bets * &lqp::BigUint::from(bets.len());
+    println!(
+        "{}",
+        tr!(
+            "\nEach ticket marks {} numbers, covering {} simple bets of {} ({} in total)",
+            "\nCada bilhete marca {} números, cobrindo {} apostas simples de {} ({} no total)",
+            config.pick().value(),
+            num(&simple_bets).bright_yellow(),
+            draw.value(),
+            num(&total).bright_yellow()
+        )
+    );
+
+    if expand {
+        for (i, bet) in bets.iter().enumerate() {
+            println!(
+                "{}",
+                tr!("\nBet {}: {}", "\nAposta {}: {}", i + 1, bet.ticket())
+            );
+            display_tickets(&bet.simple_tickets().collect::<Vec<_>>());
+        }
+    }
+}
+
+/// Display the probability that at least one ticket of the batch wins.
+fn display_batch_probability(
+    rng: &mut impl lqp::RandomNumberGenerator,
+    tickets: &[lqp::Ticket],
+    config: &Config,
+    matched_balls: usize,
+) {
+    match batch_win_probability(rng, tickets, config.range(), config.pick(), matched_balls) {
+        Ok(result) => {
+            let probability = match result.exact_probability() {
+                Some(probability) => probability.to_string(),
+                None => {
+                    let (lower, upper) = result.bounds();
+                    tr!(
+                        "about {}% (95% CI {}%..{}%)",
+                        "cerca de {}% (IC 95% {}%..{}%)",
+                        dec(result.probability() * 100.0, 6),
+                        dec(lower * 100.0, 6),
+                        dec(upper * 100.0, 6)
+                    )
                 }
+            };
+            println!(
+                "{}",
+                tr!(
+                    "Probability that at least one of your {} tickets matches {} or more balls is {}",
+                    "Probabilidade de pelo menos um dos seus {} bilhetes acertar {} ou mais bolas: {}",
+                    num(tickets.len()),
+                    matched_balls,
+                    probability.bright_yellow()
+                )
+            );
+        }
+        Err(e) => fail("Batch probability calculation error", &e),
+    }
+}
+
+/// Show the expected value report for a single ticket.
+fn run_ev(args: &EvArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    let mut jackpot_share = 1.0;
+    let mut table = args.tier.iter().fold(
+        PrizeTable::new(args.price, args.jackpot),
+        |table, &(m, p)| table.with_tier(m, p),
+    );
+
+    // Shrink the jackpot by the share a specific ticket expects to keep
+    if let Some(ticket) = &args.ticket {
+        let shared = lqp::Ticket::try_new(ticket.balls().to_vec(), config.range(), config.pick())
+            .and_then(|ticket| {
+                let model = args.popularity.model(&config)?;
+                Ok((
+                    model.jackpot_share(&ticket),
+                    model.adjust_prize_table(&table, &ticket),
+                ))
+            });
+        match shared {
+            Ok((share, adjusted)) => {
+                println!(
+                    "{}",
+                    tr!(
+                        "Jackpot share:       {} (jackpot {} -> {})",
+                        "Parcela do prêmio:      {} (prêmio {} -> {})",
+                        format!("{}%", dec(share * 100.0, 2)).bright_yellow(),
+                        dec(table.jackpot(), 2),
+                        dec(adjusted.jackpot(), 2)
+                    )
+                );
+                jackpot_share = share;
+                table = adjusted;
+            }
+            Err(e) => fail("Popularity model error", &e),
+        }
+    }
+
+    let report = match expected_value_for_config(&config, &table) {
+        Ok(report) => report,
+        Err(e) => fail("Expected value error", &e),
+    };
+
+    let price = dec(report.ticket_price(), 2);
+    println!(
+        "{}",
+        tr!(
+            "Ticket price:        {}",
+            "Preço do bilhete:       {}",
+            price
+        )
+    );
+    for tier in std::iter::once(report.jackpot()).chain(report.tiers()) {
+        println!(
+            "{}",
+            tr!(
+                "  {} matches: pays {} with odds {} -> {} per ticket",
+                "  {} acertos: paga {} com chance {} -> {} por bilhete",
+                tier.tier().matches(),
+                dec(tier.tier().payout(), 2),
+                tier.exact_probability(),
+                dec(tier.contribution(), 4)
+            )
+        );
+    }
+    let expected = dec(report.expected_return(), 4).bright_yellow();
+    println!(
+        "{}",
+        tr!(
+            "Expected return:     {}",
+            "Retorno esperado:       {}",
+            expected
+        )
+    );
+    let net = dec(report.net_expected_value(), 4);
+    println!(
+        "{}",
+        tr!("Net expected value:  {}", "Valor esperado líquido: {}", net)
+    );
+    let rtp = format!("{}%", dec(report.return_to_player(), 2)).bright_yellow();
+    println!(
+        "{}",
+        tr!("Return to player:    {}", "Retorno ao jogador:     {}", rtp)
+    );
+    // Quote the advertised jackpot that breaks even after sharing
+    let break_even = dec(report.break_even_jackpot() / jackpot_share, 2);
+    println!(
+        "{}",
+        tr!(
+            "Break-even jackpot:  {}",
+            "Prêmio de equilíbrio:   {}",
+            break_even
+        )
+    );
+}
+
+/// Show the odds of every match count for a game.
+fn run_odds(args: &OddsArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    let draw = match draw_pick(&config, args.draw_size) {
+        Ok(draw) => draw,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    let table = match match_distribution_for_bet(
+        config.range().size(),
+        config.pick().value(),
+        draw.value(),
+    ) {
+        Ok(table) => table,
+        Err(e) => fail("Probability calculation error", &e),
+    };
+
+    let headers = match locale() {
+        Locale::English => ["Matches", "Exact", "Odds", "Percent", "At least"],
+        Locale::Portuguese => ["Acertos", "Exata", "Chance", "Percentual", "Pelo menos"],
+    };
+    println!(
+        "{:>7}  {:>24}  {:>18}  {:>12}  {:>18}  {:>12}",
+        headers[0], headers[1], headers[2], headers[3], headers[4], headers[3]
+    );
+    let locale = locale();
+    for row in table.iter().rev() {
+        let exact = row.probability();
+        let at_least = row.at_least_probability();
+        println!(
+            "{:>7}  {:>24}  {:>18.2}  {:>12.6}  {:>18.2}  {:>12.6}",
+            row.matches(),
+            exact,
+            exact.display(ProbabilityFormat::OneIn).with_locale(locale),
+            exact
+                .display(ProbabilityFormat::Percentage)
+                .with_locale(locale),
+            at_least
+                .display(ProbabilityFormat::OneIn)
+                .with_locale(locale),
+            at_least
+                .display(ProbabilityFormat::Percentage)
+                .with_locale(locale)
+        );
+    }
+
+    display_time_to_win(args, &config, &draw);
+}
+
+/// Show how long it takes to first match each number of balls.
+fn display_time_to_win(args: &OddsArgs, config: &Config, draw: &PickCount) {
+    let schedule =
+        match DrawSchedule::new(args.draws_per_week.unwrap_or(1.0), args.tickets_per_draw) {
+            Ok(schedule) => schedule,
+            Err(e) => fail("Configuration error", &e),
+        };
+
+    println!(
+        "{}",
+        tr!(
+            "\nTime to first win with {} ticket(s) per draw:",
+            "\nTempo até o primeiro acerto com {} bilhete(s) por sorteio:",
+            num(schedule.tickets_per_draw())
+        )
+    );
+    let headers = match locale() {
+        Locale::English => [
+            "Matches",
+            "Expected draws",
+            "Median draws",
+            "Expected years",
+            "Median years",
+            "Within 1 year",
+        ],
+        Locale::Portuguese => [
+            "Acertos",
+            "Média sorteios",
+            "Mediana sorteios",
+            "Média anos",
+            "Mediana anos",
+            "Em até 1 ano",
+        ],
+    };
+    if args.draws_per_week.is_some() {
+        println!(
+            "{:>7}  {:>16}  {:>16}  {:>14}  {:>14}  {:>14}",
+            headers[0], headers[1], headers[2], headers[3], headers[4], headers[5]
+        );
+    } else {
+        println!("{:>7}  {:>16}  {:>16}", headers[0], headers[1], headers[2]);
+    }
+
+    for matches in (0..=draw.value()).rev() {
+        let wait = match time_to_win_for_bet(
+            config.range().size(),
+            config.pick().value(),
+            draw.value(),
+            matches,
+            &schedule,
+        ) {
+            Ok(wait) => wait,
+            Err(e) => fail("Probability calculation error", &e),
+        };
+        print!(
+            "{:>7}  {:>16}  {:>16}",
+            matches,
+            format_wait(wait.expected_draws(), 0),
+            format_wait(wait.median_draws(), 0)
+        );
+        if args.draws_per_week.is_some() {
+            print!(
+                "  {:>14}  {:>14}  {:>13}%",
+                format_wait(wait.expected_years(), 2),
+                format_wait(wait.median_years(), 2),
+                dec(wait.probability_within_years(1.0) * 100.0, 6)
+            );
+        }
+        println!();
+    }
+}
+
+/// Format a waiting time, switching to scientific notation when it is huge.
+fn format_wait(value: f64, decimals: usize) -> String {
+    if value.is_infinite() {
+        tr!("never", "nunca")
+    } else if value >= 1e12 {
+        locale().format_number(&format!("{:.2e}", value))
+    } else {
+        dec(value, decimals)
+    }
+}
+
+/// Check tickets against a draw result and summarize the prizes reached.
+fn run_check(args: &CheckArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+    let draw = match draw_pick(&config, args.draw_size) {
+        Ok(draw) => draw,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    // Validate the result and the tickets against the game
+    let result = match lqp::Ticket::try_new(args.result.balls().to_vec(), config.range(), &draw) {
+        Ok(result) => result,
+        Err(e) => fail("Result error", &e),
+    };
+    let tickets = played_tickets(&config, args.input.as_deref(), &args.ticket);
+    let table = prize_table(0.0, args.jackpot, &args.tier);
+
+    let checked = check_tickets(&tickets, &result);
+    for ticket in &checked {
+        display_checked(ticket, draw.value(), table.as_ref());
+    }
+
+    let summary = CheckSummary::new(&checked, draw.value());
+    display_check_summary(&summary, &result, draw.value(), table.as_ref());
+}
+
+/// Read the tickets given by file and on the command line, validated against the game.
+fn played_tickets(
+    config: &Config,
+    input: Option<&Path>,
+    given: &[lqp::Ticket],
+) -> Vec<lqp::Ticket> {
+    let mut tickets = match input {
+        Some(path) => match read_input(path, config) {
+            Ok(tickets) => tickets,
+            Err(e) => fail("Input error", &e),
+        },
+        None => Vec::new(),
+    };
+    for ticket in given {
+        match lqp::Ticket::try_new(ticket.balls().to_vec(), config.range(), config.pick()) {
+            Ok(ticket) => tickets.push(ticket),
+            Err(e) => fail("Ticket error", &e),
+        }
+    }
+    tickets
+}
+
+/// Build the prize table from `--jackpot` and `--tier`, if either is given.
+fn prize_table(price: f64, jackpot: Option<f64>, tiers: &[(usize, f64)]) -> Option<PrizeTable> {
+    (jackpot.is_some() || !tiers.is_empty()).then(|| {
+        tiers.iter().fold(
+            PrizeTable::new(price, jackpot.unwrap_or(0.0)),
+            |table, &(matches, payout)| table.with_tier(matches, payout),
+        )
+    })
+}
+
+/// Display a checked ticket: hits highlighted, misses dimmed, then its tier.
+fn display_checked(ticket: &CheckedTicket, draw_size: usize, table: Option<&PrizeTable>) {
+    let balls: Vec<String> = ticket
+        .ticket()
+        .balls()
+        .iter()
+        .map(|&ball| {
+            if ticket.is_hit(ball) {
+                ball.to_string().bright_green().bold().to_string()
+            } else {
+                ball.to_string().dimmed().to_string()
+            }
+        })
+        .collect();
+
+    let tier = ticket.tier(draw_size);
+    let mut line = tr!(
+        "{}  {:>2} hits",
+        "{}  {:>2} acertos",
+        balls.join(" "),
+        ticket.matches()
+    );
+    // A multi-number bet is paid for each of its simple bets
+    let label = match table {
+        Some(table) if ticket.ticket().len() > draw_size => {
+            let winnings = ticket.winnings(table, draw_size);
+            (winnings > 0.0).then(|| tr!("prizes {}", "prêmios {}", dec(winnings, 2)))
+        }
+        _ => tier_label(tier, draw_size, table),
+    };
+    if let Some(label) = label {
+        line.push_str(&format!("  {}", label.bright_yellow().bold()));
+    }
+    println!("{}", line);
+}
+
+/// Display how many tickets reached each tier and what they won.
+fn display_check_summary(
+    summary: &CheckSummary,
+    result: &lqp::Ticket,
+    draw_size: usize,
+    table: Option<&PrizeTable>,
+) {
+    println!(
+        "{}",
+        tr!(
+            "\nChecked {} against {}",
+            "\nConferidos {} com o resultado {}",
+            count_tickets(summary.tickets()),
+            result.to_string().bright_green()
+        )
+    );
+    for tier in (0..=draw_size).rev() {
+        let count = summary.count(tier);
+        if count == 0 {
+            continue;
+        }
+        let mut line = tr!(
+            "  {:>2} hits: {}",
+            "  {:>2} acertos: {}",
+            tier,
+            count_tickets(count)
+        );
+        if let Some(label) = tier_label(tier, draw_size, table) {
+            line.push_str(&format!(" ({})", label));
+        }
+        println!("{}", line);
+    }
+    if let Some(best) = summary.best() {
+        let best = best.to_string().bright_yellow();
+        println!(
+            "{}",
+            tr!("Best result: {} hits", "Melhor resultado: {} acertos", best)
+        );
+    }
+    if let Some(table) = table {
+        let winnings = dec(summary.winnings(table), 2).bright_yellow();
+        println!("{}", tr!("Winnings: {}", "Prêmios: {}", winnings));
+    }
+}
+
+/// Format a number of tickets with the right noun.
+fn count_tickets(count: usize) -> String {
+    match count {
+        1 => tr!("1 ticket", "1 bilhete"),
+        _ => tr!("{} tickets", "{} bilhetes", num(count)),
+    }
+}
+
+/// Name the prize tier of `tier` hits, if it wins anything.
+///
+/// With a prize table only paying tiers are named; without one the top
+/// three tiers are.
+fn tier_label(tier: usize, draw_size: usize, table: Option<&PrizeTable>) -> Option<String> {
+    match table {
+        Some(table) => {
+            let payout = table.payout(tier, draw_size);
+            if payout <= 0.0 {
+                None
+            } else if tier == draw_size {
+                Some(tr!("JACKPOT {}", "PRÊMIO MÁXIMO {}", dec(payout, 2)))
+            } else {
+                Some(tr!("prize {}", "prêmio {}", dec(payout, 2)))
+            }
+        }
+        None => match draw_size - tier {
+            0 => Some(tr!("JACKPOT", "PRÊMIO MÁXIMO")),
+            1 if draw_size >= 2 => Some(tr!("2nd prize", "2º prêmio")),
+            2 if draw_size >= 4 => Some(tr!("3rd prize", "3º prêmio")),
+            _ => None,
+        },
+    }
+}
+
+/// Play tickets against random draws and compare the hits with the exact odds.
+fn run_simulate(args: &SimulateArgs) {
+    let config = match args.game.config_with_games(args.tickets.unwrap_or(1)) {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+    let draw = match draw_pick(&config, args.draw_size) {
+        Ok(draw) => draw,
+        Err(e) => fail("Configuration error", &e),
+    };
+    let odds = match match_distribution_for_bet(
+        config.range().size(),
+        config.pick().value(),
+        draw.value(),
+    ) {
+        Ok(odds) => odds,
+        Err(e) => fail("Probability calculation error", &e),
+    };
+
+    // Play the given tickets, or random ones drawn from the same seed
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let tickets = if args.input.is_some() || !args.ticket.is_empty() {
+        played_tickets(&config, args.input.as_deref(), &args.ticket)
+    } else {
+        match lqp::generate_tickets(&mut rng, &config) {
+            Ok(tickets) => tickets,
+            Err(e) => fail("Generation error", &e),
+        }
+    };
+    let table = prize_table(args.price.unwrap_or(0.0), args.jackpot, &args.tier);
+
+    // Compare bitmaps: each ticket's key is built once, each draw's once per draw
+    let keys: Vec<TicketKey> = tickets
+        .iter()
+        .map(|ticket| TicketKey::from_balls(ticket.balls(), config.range()))
+        .collect();
+    // Payout of a ticket by its hits, paying every simple bet of a multi-number bet
+    let payouts: Vec<f64> = match &table {
+        Some(table) => (0..=config.pick().value())
+            .map(|hits| table.bet_payout(config.pick().value(), hits, draw.value()))
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut hits = vec![0u64; draw.value() + 1];
+    let mut winnings = 0.0;
+    for _ in 0..args.draws {
+        let result = generate_ticket(&mut rng, config.range(), &draw);
+        let drawn = TicketKey::from_balls(result.balls(), config.range());
+        for key in &keys {
+            let matches = key.matches(&drawn);
+            hits[matches] += 1;
+            winnings += payouts.get(matches).copied().unwrap_or(0.0);
+        }
+    }
+
+    let plays = args.draws * tickets.len() as u64;
+    println!(
+        "{}",
+        tr!(
+            "Simulated {} draws of {} (seed {})\n",
+            "{} sorteios simulados de {} (semente {})\n",
+            num(args.draws).bright_yellow(),
+            count_tickets(tickets.len()),
+            seed
+        )
+    );
+    let headers = match locale() {
+        Locale::English => ["Matches", "Plays", "Observed", "Expected"],
+        Locale::Portuguese => ["Acertos", "Jogadas", "Observado", "Esperado"],
+    };
+    println!(
+        "{:>7}  {:>14}  {:>12}  {:>12}",
+        headers[0], headers[1], headers[2], headers[3]
+    );
+    for row in odds.iter().rev() {
+        let count = hits[row.matches()];
+        println!(
+            "{:>7}  {:>14}  {:>11}%  {:>11}%",
+            row.matches(),
+            num(count),
+            dec(count as f64 / plays as f64 * 100.0, 6),
+            dec(row.percentage(), 6)
+        );
+    }
+
+    if table.is_some() {
+        let winnings = dec(winnings, 2).bright_yellow();
+        println!("{}", tr!("\nWinnings: {}", "\nPrêmios: {}", winnings));
+    }
+    if let Some(price) = args.price {
+        let cost = price * plays as f64;
+        println!("{}", tr!("Cost: {}", "Custo: {}", dec(cost, 2)));
+        if table.is_some() && cost > 0.0 {
+            let rate = dec(winnings / cost * 100.0, 2);
+            println!("{}", tr!("Return: {}%", "Retorno: {}%", rate));
+        }
+    }
+}
+
+/// List the tickets of a game in rank order, from `--from` up to `--limit` tickets.
+fn run_enumerate(args: &EnumerateArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+    let tickets = match combinations(config.range(), config.pick(), &args.from) {
+        Ok(tickets) => tickets.take(args.limit.unwrap_or(usize::MAX)),
+        Err(e) => fail("Enumeration error", &e),
+    };
+
+    if args.output.format() != OutputFormat::Text {
+        // Structured formats are built in memory, so bound their size
+        let remaining = &combination_big(config.range().size(), config.pick().value()) - &args.from;
+        let count = args.limit.map_or(remaining.clone(), |limit| {
+            remaining.clone().min(lqp::BigUint::from(limit))
+        });
+        if count > lqp::BigUint::from(MAX_REPORT_TICKETS) {
+            let error = lqp::LottoError::ParseError(format!(
+                "{} tickets are too many for {} output, use --limit {} or less",
+                count,
+                args.output.format(),
+                MAX_REPORT_TICKETS
+            ));
+            fail("Enumeration error", &error);
+        }
+        let tickets: Vec<lqp::Ticket> = tickets.collect();
+        write_report(&args.output, &config, &tickets, *config.pick(), None, None);
+        return;
+    }
+
+    // Stop quietly when the reading end of a pipe goes away (e.g. `| head`).
+    // The listing is data for other tools, so it is not localized.
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let written = (|| -> std::io::Result<()> {
+        for ticket in tickets {
+            let mut line = ticket.to_string();
+            if args.output.rank {
+                let rank = rank(&ticket, config.range()).expect("tickets are in range");
+                line.push_str(&format!("  rank {}", rank));
+            }
+            if args.output.code {
+                let code = encode(&ticket, config.range()).expect("tickets are in range");
+                line.push_str(&format!("  code {}", code));
+            }
+            writeln!(out, "{}", line)?;
+        }
+        out.flush()
+    })();
+    match written {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+        Err(e) => fail("Output error", &lqp::LottoError::IoError(e.to_string())),
+    }
+}
+
+/// Most tickets `enumerate` collects for a structured report.
+const MAX_REPORT_TICKETS: usize = 1_000_000;
+
+/// Verify every file, reporting each problem, and fail if any file has one.
+fn run_verify(args: &VerifyArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    // A file that cannot be read outranks one with bad tickets
+    let mut exit_code = None;
+    for path in &args.files {
+        match verify(path, &config, args.strict) {
+            Ok(true) => {}
+            Ok(false) => {
+                exit_code.get_or_insert(lqp::ErrorCategory::InvalidInput.exit_code());
             }
             Err(e) => {
-                eprintln!(
+                report(&path.display().to_string(), &e);
+                exit_code = Some(e.category().exit_code());
+            }
+        }
+    }
+    if let Some(code) = exit_code {
+        std::process::exit(code);
+    }
+}
+
+/// Verify one ticket file or store against the game, returning whether it has no problems.
+fn verify(path: &Path, config: &Config, strict: bool) -> lqp::Result<bool> {
+    let input: Box<dyn Read> = if path == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(open(path)?)
+    };
+    let mut input = BufReader::new(input);
+    let prefix = input
+        .fill_buf()
+        .map_err(|e| lqp::LottoError::IoError(e.to_string()))?;
+
+    let name = path.display().to_string();
+    let mut seen = std::collections::HashSet::new();
+    let (mut valid, mut invalid, mut repeated) = (0usize, 0usize, 0usize);
+    let mut tally = |ticket: lqp::Ticket| {
+        valid += 1;
+        if !seen.insert(TicketKey::from_balls(ticket.balls(), config.range())) {
+            repeated += 1;
+        }
+    };
+
+    let kind = if is_store(prefix) {
+        let reader = StoreReader::new(input)?;
+        let header = reader.header();
+        if header.range() != config.range() || header.pick() != config.pick() {
+            return Err(lqp::LottoError::InvalidStore {
+                reason: format!(
+                    "stores {} balls of {}..={}, not the game given",
+                    header.pick().value(),
+                    header.range().start().value(),
+                    header.range().end().value()
+                ),
+            });
+        }
+        let kind = tr!("{} store", "arquivo binário {}", header.encoding());
+        for ticket in reader {
+            match ticket {
+                Ok(ticket) => tally(ticket),
+                Err(e) => {
+                    invalid += 1;
+                    report(&name, &e);
+                }
+            }
+        }
+        kind
+    } else {
+        for ticket in TicketReader::new(input, config.range(), config.pick()) {
+            match ticket {
+                Ok(ticket) => tally(ticket),
+                Err(e) => {
+                    invalid += 1;
+                    report(&name, &e);
+                }
+            }
+        }
+        tr!("ticket file", "arquivo de bilhetes")
+    };
+
+    let ok = invalid == 0 && !(strict && repeated > 0);
+    let mut line = tr!(
+        "{}: {} with {}",
+        "{}: {} com {}",
+        name,
+        kind,
+        count_tickets(valid)
+    );
+    if invalid > 0 {
+        line.push_str(&match invalid {
+            1 => tr!(", 1 error", ", 1 erro"),
+            _ => tr!(", {} errors", ", {} erros", num(invalid)),
+        });
+    }
+    if repeated > 0 {
+        line.push_str(&tr!(", {} repeated", ", {} repetidos", num(repeated)));
+    }
+    if ok {
+        println!("{} {}", line, "OK".bright_green().bold());
+    } else {
+        println!("{} {}", line, tr!("FAILED", "FALHOU").red().bold());
+    }
+    Ok(ok)
+}
+
+/// Convert a text ticket file into a binary store.
+fn run_pack(args: &PackArgs) {
+    let config = match args.game.config() {
+        Ok(cfg) => cfg,
+        Err(e) => fail("Configuration error", &e),
+    };
+
+    let mut header = StoreHeader::new(config.range(), config.pick(), args.encoding);
+    if let Some(name) = &args.name {
+        header = header.with_name(name.as_str());
+    }
+    if let Some(generator) = &args.generator {
+        header = header.with_generator(generator.as_str());
+    }
+    if let Some(seed) = args.seed {
+        header = header.with_seed(seed);
+    }
+
+    match pack(args, &config, header) {
+        Ok(count) => eprintln!(
+            "{}",
+            tr!(
+                "Packed {} tickets into {} ({} encoding)",
+                "{} bilhetes gravados em {} (codificação {})",
+                num(count).bright_yellow(),
+                args.output.display(),
+                args.encoding
+            )
+        ),
+        Err(e) => fail("Pack error", &e),
+    }
+}
+
+/// Stream tickets from the text input into the store, returning their count.
+fn pack(args: &PackArgs, config: &Config, header: StoreHeader) -> lqp::Result<u64> {
+    let input: Box<dyn BufRead> = if args.input == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(BufReader::new(open(&args.input)?))
+    };
+    let output = File::create(&args.output)
+        .map_err(|e| lqp::LottoError::IoError(format!("{}: {}", args.output.display(), e)))?;
+
+    let mut writer = StoreWriter::new(BufWriter::new(output), header)?;
+    let mut count = 0;
+    for ticket in TicketReader::new(input, config.range(), config.pick()) {
+        writer.write_ticket(&ticket?)?;
+        count += 1;
+    }
+    writer.finish()?;
+    Ok(count)
+}
+
+/// Convert a binary store back into importable text.
+fn run_unpack(args: &UnpackArgs) {
+    if let Err(e) = unpack(args) {
+        fail("Unpack error", &e);
+    }
+}
+
+/// Write the store's metadata as a comment line, then one ticket per line.
+fn unpack(args: &UnpackArgs) -> lqp::Result<()> {
+    let input: Box<dyn Read> = if args.input == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(open(&args.input)?)
+    };
+    let reader = StoreReader::new(BufReader::new(input))?;
+
+    let header = reader.header();
+    let mut metadata = format!(
+        "# start={} end={} pick={} encoding={}",
+        header.range().start().value(),
+        header.range().end().value(),
+        header.pick().value(),
+        header.encoding()
+    );
+    if let Some(seed) = header.seed() {
+        metadata.push_str(&format!(" seed={}", seed));
+    }
+    if !header.name().is_empty() {
+        metadata.push_str(&format!(" name={}", header.name()));
+    }
+    if !header.generator().is_empty() {
+        metadata.push_str(&format!(" generator={}", header.generator()));
+    }
+
+    // Stop quietly when the reading end of a pipe goes away (e.g. `| head`)
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    let written = (|| -> std::io::Result<lqp::Result<()>> {
+        writeln!(out, "{}", metadata)?;
+        for ticket in reader {
+            match ticket {
+                Ok(ticket) => writeln!(out, "{}", ticket)?,
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+        out.flush()?;
+        Ok(Ok(()))
+    })();
+    match written {
+        Ok(result) => result,
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(lqp::LottoError::IoError(e.to_string())),
+    }
+}
+
+/// Open a file, naming it in the error.
+fn open(path: &Path) -> lqp::Result<File> {
+    File::open(path).map_err(|e| lqp::LottoError::IoError(format!("{}: {}", path.display(), e)))
+}
+
+/// Run the interactive menu on standard input until the user exits.
+fn run_interactive() {
+    let mut session = Interactive::new(std::io::stdin().lock());
+    if let Err(e) = session.run() {
+        fail("Interactive mode error", &e);
+    }
+}
+
+/// Serve the JSON API on localhost until the process is stopped.
+fn run_serve(args: &ServeArgs) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, args.port)) {
+        Ok(listener) => listener,
+        Err(e) => fail(
+            "Server error",
+            &lqp::LottoError::IoError(format!("port {}: {}", args.port, e)),
+        ),
+    };
+    if let Ok(address) = listener.local_addr() {
+        eprintln!(
+            "{}",
+            tr!(
+                "Serving the JSON API on http://{} (Ctrl-C to stop)",
+                "Servindo a API JSON em http://{} (Ctrl-C para parar)",
+                address
+            )
+        );
+    }
+    serve(listener);
+}
+
+/// An interactive session: prompts for game parameters and remembers the
+/// last valid game, so several batches can be generated in one session.
+struct Interactive<R> {
+    input: R,
+    config: Option<Config>,
+    games: usize,
+    rng: StdRng,
+}
+
+impl<R: BufRead> Interactive<R> {
+    fn new(input: R) -> Self {
+        Self {
+            input,
+            config: None,
+            games: 1,
+            rng: StdRng::seed_from_u64(rand::random()),
+        }
+    }
+
+    /// Show the menu until the user exits or the input ends.
+    fn run(&mut self) -> lqp::Result<()> {
+        let title = tr!(
+            "=== Lotto Quick Pick - Interactive Mode ===",
+            "=== Lotto Quick Pick - Modo Interativo ==="
+        );
+        println!("{}", title.bold());
+        loop {
+            println!();
+            println!("{}", tr!("1. Generate tickets", "1. Gerar bilhetes"));
+            println!(
+                "{}",
+                tr!("2. Calculate probability", "2. Calcular probabilidade")
+            );
+            println!("{}", tr!("3. Exit", "3. Sair"));
+
+            let Some(choice) = self.read_line(&tr!("Choose an option", "Escolha uma opção"))?
+            else {
+                break;
+            };
+            match choice.to_lowercase().as_str() {
+                "1" | "g" | "generate" | "gerar" => self.generate()?,
+                "2" | "p" | "probability" | "probabilidade" => self.probability()?,
+                "3" | "q" | "exit" | "quit" | "sair" => break,
+                other => eprintln!(
                     "{}",
-                    format!("Probability calculation error: {}", e).red().bold()
-                );
+                    tr!(
+                        "Unknown option '{}', choose 1, 2 or 3",
+                        "Opção desconhecida '{}', escolha 1, 2 ou 3",
+                        other
+                    )
+                    .yellow()
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Generate batches of tickets until the user declines another one.
+    ///
+    /// A game that cannot give that many tickets is asked for again.
+    fn generate(&mut self) -> lqp::Result<()> {
+        let mut config = self.ask_config(true)?;
+        loop {
+            let tickets = match lqp::generate_tickets(&mut self.rng, &config) {
+                Ok(tickets) => tickets,
+                Err(e) => {
+                    self.complain(&e);
+                    config = self.ask_config(true)?;
+                    continue;
+                }
+            };
+            println!();
+            display_tickets(&tickets);
+
+            let question = tr!(
+                "Generate another batch with the same game?",
+                "Gerar outro lote com o mesmo jogo?"
+            );
+            if !self.ask_yes_no(&question)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Show the odds of matching a number of balls in a game.
+    fn probability(&mut self) -> lqp::Result<()> {
+        let config = self.ask_config(false)?;
+        loop {
+            let default = config.pick().value();
+            let matched_balls =
+                self.ask(&tr!("Balls to match", "Bolas a acertar"), Some(default))?;
+            match calculate_probability_big_for_config(&config, matched_balls) {
+                Ok((favorable, total)) => {
+                    display_match_probability(matched_balls, favorable, total);
+                    return Ok(());
+                }
+                Err(e) => self.complain(&e),
             }
         }
     }
+
+    /// Prompt for a game until `Config::new` accepts it, offering the last
+    /// valid answers as defaults.
+    fn ask_config(&mut self, with_games: bool) -> lqp::Result<Config> {
+        loop {
+            let games = if with_games {
+                self.ask(
+                    &tr!("Number of tickets", "Número de bilhetes"),
+                    Some(self.games),
+                )?
+            } else {
+                1
+            };
+            let last = self.config.as_ref();
+            let start_default = last.map(|c| c.range().start().value());
+            let end_default = last.map(|c| c.range().end().value());
+            let pick_default = last.map(|c| c.pick().value());
+            let start = self.ask(&tr!("Starting number", "Número inicial"), start_default)?;
+            let end = self.ask(&tr!("Ending number", "Número final"), end_default)?;
+            let pick = self.ask(&tr!("Balls per ticket", "Bolas por bilhete"), pick_default)?;
+
+            match Config::new(games, start, end, pick) {
+                Ok(config) => {
+                    if with_games {
+                        self.games = games;
+                    }
+                    self.config = Some(config.clone());
+                    return Ok(config);
+                }
+                Err(e) => self.complain(&e),
+            }
+        }
+    }
+
+    /// Prompt for a value until it parses, using `default` for an empty answer.
+    fn ask<T>(&mut self, label: &str, default: Option<T>) -> lqp::Result<T>
+    where
+        T: std::str::FromStr + std::fmt::Display + Copy,
+    {
+        let label = match default {
+            Some(value) => format!("{} [{}]", label, value),
+            None => label.to_string(),
+        };
+        loop {
+            let answer = self.expect_line(&label)?;
+            if answer.is_empty() {
+                match default {
+                    Some(value) => return Ok(value),
+                    None => continue,
+                }
+            }
+            match answer.parse() {
+                Ok(value) => return Ok(value),
+                Err(_) => self.complain(&lqp::LottoError::ParseError(tr!(
+                    "'{}' is not a valid number",
+                    "'{}' não é um número válido",
+                    answer
+                ))),
+            }
+        }
+    }
+
+    /// Ask a yes/no question; an empty answer means yes.
+    fn ask_yes_no(&mut self, question: &str) -> lqp::Result<bool> {
+        loop {
+            let Some(answer) = self.read_line(&tr!("{} [Y/n]", "{} [S/n]", question))? else {
+                return Ok(false);
+            };
+            match answer.to_lowercase().as_str() {
+                "" | "y" | "yes" | "s" | "sim" => return Ok(true),
+                "n" | "no" | "nao" | "não" => return Ok(false),
+                _ => self.complain(&lqp::LottoError::ParseError(tr!(
+                    "'{}' is not yes or no",
+                    "'{}' não é sim nem não",
+                    answer
+                ))),
+            }
+        }
+    }
+
+    /// Read an answer that is required to continue.
+    fn expect_line(&mut self, label: &str) -> lqp::Result<String> {
+        self.read_line(label)?.ok_or_else(|| {
+            lqp::LottoError::IoError(tr!(
+                "input ended before an answer",
+                "a entrada terminou antes de uma resposta"
+            ))
+        })
+    }
+
+    /// Print a prompt and read one trimmed line, or `None` at the end of input.
+    fn read_line(&mut self, label: &str) -> lqp::Result<Option<String>> {
+        print!("{}: ", label);
+        std::io::stdout()
+            .flush()
+            .map_err(|e| lqp::LottoError::IoError(e.to_string()))?;
+
+        let mut line = String::new();
+        let read = self
+            .input
+            .read_line(&mut line)
+            .map_err(|e| lqp::LottoError::IoError(e.to_string()))?;
+        if read == 0 {
+            println!();
+            return Ok(None);
+        }
+        Ok(Some(line.trim().to_string()))
+    }
+
+    /// Explain an invalid answer before prompting again.
+    fn complain(&self, error: &lqp::LottoError) {
+        let message = locale().error_message(error);
+        eprintln!(
+            "{}",
+            tr!("{}, please try again", "{}, tente novamente", message).yellow()
+        );
+    }
 }
 
-/// Calculate the greatest common divisor of two u128 numbers.
-fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
-    while b != 0 {
-        let temp = b;
-        b = a % b;
-        a = temp;
-    }
-    a
-}
-
-// TODO: Interactive mode - Implement interactive menu for user input
-// This will allow users to configure lottery parameters through prompts
-// instead of command-line arguments.
-//
-// Required features:
-// - TODO: Interactive mode - Input loop with prompts for games, start, end, pick
-// - TODO: Interactive mode - Validate user input with friendly error messages
-// - TODO: Interactive mode - Allow retry on invalid input instead of exit
-// - TODO: Interactive mode - Display menu with options (generate, calculate probability, exit)
-// - TODO: Interactive mode - Show generated tickets in formatted output
-// - TODO: Interactive mode - Calculate and display probabilities interactively
-// - TODO: Interactive mode - Option to generate multiple batches in one session
-//
-// Implementation notes:
-// - Use the existing Config::new() for validation (returns Result)
-// - Use display_tickets() for output formatting
-// - Use calculate_probability() for odds calculation
-// - Keep I/O separate from library code (already refactored)
-//
-// Example interactive flow:
-// ```
-// fn run_interactive_mode() -> lqp::Result<()> {
-//     loop {
-//         println!("=== Lotto Quick Pick - Interactive Mode ===");
-//         println!("1. Generate tickets");
-//         println!("2. Calculate probability");
-//         println!("3. Exit");
-//
-//         // Read user choice
-//         // If generate: prompt for parameters, create Config, generate, display
-//         // If calculate: prompt for parameters, call calculate_probability, display
-//         // If exit: break
-//     }
-//     Ok(())
-// }
-// ```
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_ticket_file_is_an_input_error() {
+        let path = std::env::temp_dir().join(format!("lqp-empty-{}.txt", std::process::id()));
+        std::fs::write(&path, "# only comments\n\n").unwrap();
+        let config = Config::new(1, 1, 60, 6).unwrap();
+
+        let error = read_input(&path, &config).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            error,
+            lqp::LottoError::EmptyTicketFile {
+                path: path.display().to_string()
+            }
+        );
+        assert_eq!(error.category().exit_code(), 3);
+    }
+
+    #[test]
+    fn test_reversed_range_is_rejected() {
+        let args = GameArgs {
+            game: None,
+            start_number: Some(60),
+            end_number: Some(1),
+            pick: Some(6),
+        };
+        assert_eq!(
+            args.config().unwrap_err(),
+            lqp::LottoError::InvalidRange { start: 60, end: 1 }
+        );
+
+        // An override can reverse a preset's range too
+        let args = GameArgs {
+            game: Some(GamePreset::MegaSena),
+            start_number: Some(61),
+            end_number: None,
+            pick: None,
+        };
+        assert_eq!(
+            args.config().unwrap_err(),
+            lqp::LottoError::InvalidRange { start: 61, end: 60 }
+        );
+    }
+
+    fn session(input: &str) -> Interactive<std::io::Cursor<&[u8]>> {
+        Interactive::new(std::io::Cursor::new(input.as_bytes()))
+    }
+
+    #[test]
+    fn test_interactive_quits_on_exit_or_end_of_input() {
+        assert!(session("3\n").run().is_ok());
+        assert!(session("quit\n").run().is_ok());
+        assert!(session("").run().is_ok());
+        // An unknown option shows the menu again
+        assert!(session("9\nsair\n").run().is_ok());
+    }
+
+    #[test]
+    fn test_interactive_generates_and_remembers_the_game() {
+        let mut interactive = session("1\n2\n1\n60\n6\nn\n3\n");
+        interactive.run().unwrap();
+
+        let config = interactive.config.as_ref().unwrap();
+        assert_eq!(config.range().start().value(), 1);
+        assert_eq!(config.range().end().value(), 60);
+        assert_eq!(config.pick().value(), 6);
+        assert_eq!(interactive.games, 2);
+    }
+
+    #[test]
+    fn test_interactive_offers_the_last_game_as_default() {
+        let mut interactive = session("3\n1\n25\n15\n\n\n\n\n");
+        let first = interactive.ask_config(true).unwrap();
+        let second = interactive.ask_config(true).unwrap();
+
+        assert_eq!(second.range(), first.range());
+        assert_eq!(second.pick(), first.pick());
+        assert_eq!(second.game_count(), first.game_count());
+        assert_eq!(interactive.games, 3);
+    }
+
+    #[test]
+    fn test_interactive_asks_again_after_invalid_input() {
+        let mut interactive = session("six\n\n6\n");
+        assert_eq!(interactive.ask::<usize>("Pick", None).unwrap(), 6);
+
+        let mut interactive = session("maybe\nn\n");
+        assert!(!interactive.ask_yes_no("Again?").unwrap());
+
+        // Picking more balls than the range holds is not a game
+        let mut interactive = session("1\n10\n11\n1\n60\n6\n");
+        let config = interactive.ask_config(false).unwrap();
+        assert_eq!(config.range().end().value(), 60);
+    }
+
+    #[test]
+    fn test_interactive_asks_for_the_game_again_when_generation_fails() {
+        // C(5, 4) = 5 tickets cannot fill a batch of 6
+        let mut interactive = session("6\n1\n5\n4\n1\n1\n60\n6\nn\n");
+        interactive.generate().unwrap();
+        assert_eq!(interactive.games, 1);
+    }
+
+    #[test]
+    fn test_interactive_input_ending_mid_game_is_an_io_error() {
+        let error = session("1\n1\n").run().unwrap_err();
+        assert!(matches!(error, lqp::LottoError::IoError(_)));
+    }
+}
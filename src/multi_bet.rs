@@ -0,0 +1,371 @@
+//! Multi-number bets (apostas múltiplas).
+//!
+//! Games such as Mega-Sena let a player mark more balls than are drawn on
+//! a single bet. A bet of `k` balls against a draw of `d` balls is priced
+//! and paid as the C(k, d) simple bets it contains.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+use crate::probability::{MatchOdds, combination_big, match_distribution_for_bet};
+use crate::rng::RandomNumberGenerator;
+use crate::ticket::generate_ticket;
+
+/// Count the simple bets matching each number of balls, for a bet of
+/// `bet_size` balls covering `hits` of `draw_size` drawn balls.
+///
+/// Entry `m` of the result is the number of simple bets matching exactly
+/// `m` balls: C(hits, m) * C(bet_size - hits, draw_size - m). A bet of
+/// exactly `draw_size` balls is a single simple bet, counted at its hits.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::multi_bet::simple_bet_breakdown;
+///
+/// // An 8-ball Mega-Sena bet covering 6 drawn balls
+/// let prizes = simple_bet_breakdown(8, 6, 6);
+/// assert_eq!(prizes[6].to_u128(), Some(1));
+/// assert_eq!(prizes[5].to_u128(), Some(12));
+/// assert_eq!(prizes[4].to_u128(), Some(15));
+/// ```
+pub fn simple_bet_breakdown(bet_size: usize, draw_size: usize, hits: usize) -> Vec<BigUint> {
+    let hits = hits.min(draw_size).min(bet_size);
+    let misses = bet_size - hits;
+    (0..=draw_size)
+        .map(|m| {
+            if m > hits || draw_size - m > misses {
+                BigUint::zero()
+            } else {
+                &combination_big(hits, m) * &combination_big(misses, draw_size - m)
+            }
+        })
+        .collect()
+}
+
+/// A bet marking at least as many balls as are drawn.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::multi_bet::MultiBet;
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let draw = PickCount::new(6, &range).unwrap();
+/// let balls = (1..=8).map(BallNumber::new).collect();
+///
+/// let bet = MultiBet::try_new(balls, &range, &draw).unwrap();
+/// assert_eq!(bet.simple_bet_count().to_u128(), Some(28)); // C(8, 6)
+/// assert_eq!(bet.cost(5.0), 140.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiBet {
+    ticket: Ticket,
+    draw_size: usize,
+}
+
+impl MultiBet {
+    /// Create a new validated MultiBet.
+    ///
+    /// # Arguments
+    ///
+    /// * `balls` - The marked ball numbers (will be sorted)
+    /// * `range` - The valid range for ball numbers
+    /// * `draw` - Number of balls drawn by the game
+    ///
+    /// # Errors
+    ///
+    /// Returns `BetTooSmall` if fewer balls than `draw` are marked, or the
+    /// same errors as [`Ticket::try_new`] for out-of-range or duplicate balls.
+    pub fn try_new(balls: Vec<BallNumber>, range: &BallRange, draw: &PickCount) -> Result<Self> {
+        if balls.len() < draw.value() {
+            return Err(LottoError::BetTooSmall {
+                bet_size: balls.len(),
+                draw_size: draw.value(),
+            });
+        }
+        let bet_size = PickCount::new(balls.len(), range)?;
+        let ticket = Ticket::try_new(balls, range, &bet_size)?;
+        Ok(Self {
+            ticket,
+            draw_size: draw.value(),
+        })
+    }
+
+    /// Get the marked balls.
+    pub fn ticket(&self) -> &Ticket {
+        &self.ticket
+    }
+
+    /// Get the number of marked balls.
+    pub fn bet_size(&self) -> usize {
+        self.ticket.len()
+    }
+
+    /// Get the number of balls drawn by the game.
+    pub fn draw_size(&self) -> usize {
+        self.draw_size
+    }
+
+    /// Get the number of simple bets covered: C(bet_size, draw_size).
+    pub fn simple_bet_count(&self) -> BigUint {
+        combination_big(self.bet_size(), self.draw_size)
+    }
+
+    /// Get the price of the bet, given the price of a simple bet.
+    pub fn cost(&self, simple_bet_price: f64) -> f64 {
+        self.simple_bet_count().to_f64() * simple_bet_price
+    }
+
+    /// Iterate over the simple tickets this bet covers, in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::multi_bet::MultiBet;
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount};
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let draw = PickCount::new(6, &range).unwrap();
+    /// let bet = MultiBet::try_new((1..=7).map(BallNumber::new).collect(), &range, &draw).unwrap();
+    ///
+    /// let tickets: Vec<_> = bet.simple_tickets().collect();
+    /// assert_eq!(tickets.len(), 7);
+    /// assert_eq!(tickets[0].to_string(), "01 02 03 04 05 06");
+    /// ```
+    pub fn simple_tickets(&self) -> SimpleTickets<'_> {
+        SimpleTickets {
+            balls: self.ticket.balls(),
+            indices: (0..self.draw_size).collect(),
+            done: false,
+        }
+    }
+
+    /// Count the simple bets matching each number of balls, for a bet that
+    /// covers `hits` of the drawn balls.
+    ///
+    /// Entry `m` of the result is the number of simple bets matching exactly
+    /// `m` balls: C(hits, m) * C(bet_size - hits, draw_size - m).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::multi_bet::MultiBet;
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount};
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let draw = PickCount::new(6, &range).unwrap();
+    /// let bet = MultiBet::try_new((1..=7).map(BallNumber::new).collect(), &range, &draw).unwrap();
+    ///
+    /// // Covering all six drawn balls: one sena and six quinas
+    /// let prizes = bet.prize_breakdown(6);
+    /// assert_eq!(prizes[6].to_u128(), Some(1));
+    /// assert_eq!(prizes[5].to_u128(), Some(6));
+    /// ```
+    pub fn prize_breakdown(&self, hits: usize) -> Vec<BigUint> {
+        simple_bet_breakdown(self.bet_size(), self.draw_size, hits)
+    }
+
+    /// Count the simple bets matching each number of balls for a given draw.
+    ///
+    /// See [`MultiBet::prize_breakdown`] for the layout of the result.
+    pub fn check(&self, draw: &Ticket) -> Vec<BigUint> {
+        self.prize_breakdown(self.ticket.matches(draw))
+    }
+
+    /// Calculate the odds of covering each number of drawn balls.
+    ///
+    /// Covering `m` drawn balls is what wins the `m`-match tier with at
+    /// least one of the simple bets.
+    pub fn odds(&self, range: &BallRange) -> Result<Vec<MatchOdds>> {
+        match_distribution_for_bet(range.size(), self.bet_size(), self.draw_size)
+    }
+}
+
+/// Iterator over the simple tickets covered by a [`MultiBet`].
+#[derive(Debug, Clone)]
+pub struct SimpleTickets<'a> {
+    balls: &'a [BallNumber],
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for SimpleTickets<'_> {
+    type Item = Ticket;
+
+    fn next(&mut self) -> Option<Ticket> {
+        if self.done {
+            return None;
+        }
+
+        // Balls are sorted, so increasing indices give a sorted ticket
+        let ticket = Ticket::from_sorted(self.indices.iter().map(|&i| self.balls[i]).collect());
+
+        // Advance to the next combination of indices
+        let n = self.balls.len();
+        let k = self.indices.len();
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(ticket)
+    }
+}
+
+/// Generate a random multi-number bet.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `range` - The range of ball numbers to choose from
+/// * `bet_size` - Number of balls to mark
+/// * `draw` - Number of balls drawn by the game
+///
+/// # Returns
+///
+/// A MultiBet, or `BetTooSmall` if `bet_size` is smaller than `draw`.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::multi_bet::generate_multi_bet;
+/// use lotto_quick_pick::newtypes::{BallRange, PickCount};
+///
+/// let mut rng = rand::rng();
+/// let range = BallRange::mega_sena();
+/// let bet_size = PickCount::new(10, &range).unwrap();
+/// let draw = PickCount::new(6, &range).unwrap();
+///
+/// let bet = generate_multi_bet(&mut rng, &range, &bet_size, &draw).unwrap();
+/// assert_eq!(bet.bet_size(), 10);
+/// assert_eq!(bet.simple_bet_count().to_u128(), Some(210));
+/// ```
+pub fn generate_multi_bet<R: RandomNumberGenerator>(
+    rng: &mut R,
+    range: &BallRange,
+    bet_size: &PickCount,
+    draw: &PickCount,
+) -> Result<MultiBet> {
+    if bet_size.value() < draw.value() {
+        return Err(LottoError::BetTooSmall {
+            bet_size: bet_size.value(),
+            draw_size: draw.value(),
+        });
+    }
+    Ok(MultiBet {
+        ticket: generate_ticket(rng, range, bet_size),
+        draw_size: draw.value(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn bet(values: &[u8]) -> MultiBet {
+        let range = BallRange::mega_sena();
+        let draw = PickCount::new(6, &range).unwrap();
+        MultiBet::try_new(
+            values.iter().map(|&v| BallNumber::new(v)).collect(),
+            &range,
+            &draw,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_mega_sena_bet_prices() {
+        let counts: Vec<u128> = (6..=15)
+            .map(|k| {
+                let values: Vec<u8> = (1..=k).collect();
+                bet(&values).simple_bet_count().to_u128().unwrap()
+            })
+            .collect();
+        assert_eq!(
+            counts,
+            vec![1, 7, 28, 84, 210, 462, 924, 1_716, 3_003, 5_005]
+        );
+    }
+
+    #[test]
+    fn test_simple_tickets_are_unique_and_complete() {
+        let bet = bet(&[3, 9, 14, 21, 33, 40, 47, 58, 60]);
+        let tickets: HashSet<Ticket> = bet.simple_tickets().collect();
+        assert_eq!(tickets.len(), 84);
+        assert!(
+            tickets
+                .iter()
+                .all(|t| { t.len() == 6 && t.balls().iter().all(|b| bet.ticket().contains(b)) })
+        );
+    }
+
+    #[test]
+    fn test_check_agrees_with_expansion() {
+        let bet = bet(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        let draw = Ticket::new([2, 4, 6, 8, 50, 60].map(BallNumber::new).to_vec());
+        let counts = bet.check(&draw);
+
+        let mut expected = vec![0u128; 7];
+        for ticket in bet.simple_tickets() {
+            let matches = ticket.balls().iter().filter(|b| draw.contains(b)).count();
+            expected[matches] += 1;
+        }
+        let counts: Vec<u128> = counts.iter().map(|c| c.to_u128().unwrap()).collect();
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_simple_bet_has_single_ticket() {
+        let bet = bet(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(bet.simple_tickets().count(), 1);
+        assert_eq!(
+            bet.odds(&BallRange::mega_sena()).unwrap()[6]
+                .favorable()
+                .to_u128(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_bet_too_small() {
+        let range = BallRange::mega_sena();
+        let draw = PickCount::new(6, &range).unwrap();
+        let result = MultiBet::try_new((1..=5).map(BallNumber::new).collect(), &range, &draw);
+        assert!(matches!(
+            result,
+            Err(LottoError::BetTooSmall {
+                bet_size: 5,
+                draw_size: 6
+            })
+        ));
+
+        let mut rng = rand::rng();
+        let small = PickCount::new(5, &range).unwrap();
+        let result = generate_multi_bet(&mut rng, &range, &small, &draw);
+        assert!(matches!(result, Err(LottoError::BetTooSmall { .. })));
+    }
+
+    #[test]
+    fn test_invalid_balls_rejected() {
+        let range = BallRange::mega_sena();
+        let draw = PickCount::new(6, &range).unwrap();
+        let balls = [1, 2, 3, 4, 5, 6, 61].map(BallNumber::new).to_vec();
+        assert!(matches!(
+            MultiBet::try_new(balls, &range, &draw),
+            Err(LottoError::BallOutOfRange { .. })
+        ));
+        let balls = [1, 2, 3, 4, 5, 6, 6].map(BallNumber::new).to_vec();
+        assert!(matches!(
+            MultiBet::try_new(balls, &range, &draw),
+            Err(LottoError::DuplicateBall { .. })
+        ));
+    }
+}
@@ -89,7 +89,8 @@ impl BallRange {
 
     /// Get the size of the range (number of possible values).
     pub fn size(&self) -> usize {
-        (self.end.value() - self.start.value() + 1) as usize
+        // Widen before adding one: the full 0..=255 range has 256 values
+        self.end.value() as usize - self.start.value() as usize + 1
     }
 }
 
@@ -184,8 +185,9 @@ impl GameCount {
 
 /// Represents a single lottery ticket containing unique ball numbers.
 ///
-/// The balls are stored in sorted order for consistency.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The balls are stored in sorted order for consistency. Tickets order
+/// lexicographically by their sorted balls, as they would in a printed list.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Ticket {
     balls: Vec<BallNumber>,
 }
@@ -479,6 +481,34 @@ impl Ticket {
     pub fn contains(&self, ball: &BallNumber) -> bool {
         self.balls.contains(ball)
     }
+
+    /// Count the balls this ticket shares with another ticket or draw.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::newtypes::{Ticket, BallNumber};
+    ///
+    /// let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+    /// let draw = Ticket::new([1, 8, 15, 30, 42, 60].map(BallNumber::new).to_vec());
+    /// assert_eq!(ticket.matches(&draw), 3);
+    /// ```
+    pub fn matches(&self, other: &Ticket) -> usize {
+        // Both ball lists are sorted, so a single merge pass suffices
+        let (mut i, mut j, mut count) = (0, 0, 0);
+        while i < self.balls.len() && j < other.balls.len() {
+            match self.balls[i].cmp(&other.balls[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    count += 1;
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        count
+    }
 }
 
 #[cfg(test)]
@@ -517,6 +547,12 @@ mod tests {
         assert!(matches!(result, Err(LottoError::InvalidRange { .. })));
     }
 
+    #[test]
+    fn test_ball_range_full_u8_size() {
+        let range = BallRange::new(BallNumber::new(0), BallNumber::new(255)).unwrap();
+        assert_eq!(range.size(), 256);
+    }
+
     #[test]
     fn test_pick_count_valid() {
         let range = BallRange::new(BallNumber::new(1), BallNumber::new(60)).unwrap();
@@ -689,4 +725,15 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn test_ticket_matches_edge_cases() {
+        let ticket =
+            |values: &[u8]| Ticket::new(values.iter().map(|&v| BallNumber::new(v)).collect());
+        assert_eq!(ticket(&[]).matches(&ticket(&[1, 2])), 0);
+        assert_eq!(ticket(&[7]).matches(&ticket(&[7])), 1);
+        assert_eq!(ticket(&[7]).matches(&ticket(&[8])), 0);
+        // Spanning more than 128 values
+        assert_eq!(ticket(&[0, 100, 255]).matches(&ticket(&[100, 200, 255])), 2);
+    }
 }
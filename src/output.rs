@@ -0,0 +1,478 @@
+//! Machine-readable reports of generated tickets.
+//!
+//! Besides the colored text meant for people, tickets can be written as a
+//! single JSON document, as newline-delimited JSON records or as CSV. Every
+//! format carries the game and seed that produced the tickets, each
+//! ticket's position and balls, optionally its combinatorial rank and short
+//! code, and the match odds table when a match count was asked for.
+
+use crate::Config;
+use crate::code::encode;
+use crate::error::{LottoError, Result};
+use crate::json::Json;
+use crate::newtypes::{BallRange, PickCount, Ticket};
+use crate::probability::{MatchOdds, ProbabilityFormat, match_distribution_for_bet};
+use crate::rank::rank;
+use std::fmt;
+use std::str::FromStr;
+
+/// How generated tickets are written.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::output::OutputFormat;
+///
+/// let format: OutputFormat = "ndjson".parse().unwrap();
+/// assert_eq!(format, OutputFormat::Ndjson);
+/// assert!("yaml".parse::<OutputFormat>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Colored, human-readable text.
+    #[default]
+    Text,
+    /// A single JSON document.
+    Json,
+    /// Comma-separated values, one ticket per row.
+    Csv,
+    /// One JSON record per line.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = LottoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(LottoError::ParseError(format!(
+                "unknown output format '{}' (expected text, json, csv or ndjson)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+/// Generated tickets together with the metadata needed to reproduce them.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::output::Report;
+/// use lotto_quick_pick::{BallNumber, Config, Ticket};
+///
+/// let config = Config::new(1, 1, 60, 6).unwrap();
+/// let tickets = vec![Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec())];
+/// let report = Report::new(&config, &tickets).with_seed(7);
+///
+/// assert_eq!(
+///     report.to_csv().unwrap(),
+///     "# start=1 end=60 pick=6 draw_size=6 seed=7\n\
+///      index,ball_1,ball_2,ball_3,ball_4,ball_5,ball_6\n\
+///      1,4,8,15,16,23,42\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    range: BallRange,
+    pick: PickCount,
+    draw: PickCount,
+    tickets: &'a [Ticket],
+    seed: Option<u64>,
+    ranks: bool,
+    codes: bool,
+    matched: Option<usize>,
+}
+
+impl<'a> Report<'a> {
+    /// Create a new Report of tickets generated for a configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration the tickets were generated with
+    /// * `tickets` - The generated tickets, in output order
+    pub fn new(config: &Config, tickets: &'a [Ticket]) -> Self {
+        Self {
+            range: *config.range(),
+            pick: *config.pick(),
+            draw: *config.draw_size(),
+            tickets,
+            seed: None,
+            ranks: false,
+            codes: false,
+            matched: None,
+        }
+    }
+
+    /// Record the seed the tickets were generated from.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the number of balls drawn, for multi-number bets.
+    pub fn with_draw_size(mut self, draw: PickCount) -> Self {
+        self.draw = draw;
+        self
+    }
+
+    /// Include each ticket's combinatorial rank.
+    pub fn with_ranks(mut self) -> Self {
+        self.ranks = true;
+        self
+    }
+
+    /// Include each ticket's short code.
+    pub fn with_codes(mut self) -> Self {
+        self.codes = true;
+        self
+    }
+
+    /// Include the match odds table, highlighting `matched` balls.
+    pub fn with_matched(mut self, matched: usize) -> Self {
+        self.matched = Some(matched);
+        self
+    }
+
+    /// Render the report in a machine-readable format.
+    ///
+    /// # Returns
+    ///
+    /// `None` for [`OutputFormat::Text`], which is left to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BallOutOfRange` if a ticket cannot be ranked, or
+    /// `InvalidMatchCount` if more balls are matched than drawn.
+    pub fn render(&self, format: OutputFormat) -> Result<Option<String>> {
+        match format {
+            OutputFormat::Text => Ok(None),
+            OutputFormat::Json => self.to_json().map(Some),
+            OutputFormat::Csv => self.to_csv().map(Some),
+            OutputFormat::Ndjson => self.to_ndjson().map(Some),
+        }
+    }
+
+    /// Render the report as a single JSON document.
+    ///
+    /// # Errors
+    ///
+    /// See [`Report::render`].
+    pub fn to_json(&self) -> Result<String> {
+        let tickets = (0..self.tickets.len())
+            .map(|i| self.ticket_record(i).map(Json::Object))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut members = self.metadata();
+        members.push(("tickets".to_string(), Json::Array(tickets)));
+        if let Some((matched, table)) = self.odds()? {
+            let rows = table.iter().map(|row| Json::Object(odds_record(row)));
+            members.push((
+                "odds".to_string(),
+                Json::object([
+                    ("matched", Json::from(matched)),
+                    ("table", Json::array(rows)),
+                ]),
+            ));
+        }
+        Ok(format!("{}\n", Json::Object(members)))
+    }
+
+    /// Render the report as newline-delimited JSON.
+    ///
+    /// The first record has `"type":"metadata"`, followed by one
+    /// `"type":"ticket"` record per ticket and one `"type":"odds"` record
+    /// per row of the odds table.
+    ///
+    /// # Errors
+    ///
+    /// See [`Report::render`].
+    pub fn to_ndjson(&self) -> Result<String> {
+        let mut lines = vec![typed("metadata", self.metadata())];
+        for i in 0..self.tickets.len() {
+            lines.push(typed("ticket", self.ticket_record(i)?));
+        }
+        if let Some((matched, table)) = self.odds()? {
+            for row in &table {
+                let mut record = vec![("matched".to_string(), Json::from(matched))];
+                record.extend(odds_record(row));
+                lines.push(typed("odds", record));
+            }
+        }
+        Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+
+    /// Render the report as CSV, one ticket per row.
+    ///
+    /// The metadata and the odds table are written as leading `#` comment
+    /// lines, so the rows below the header stay a single table.
+    ///
+    /// # Errors
+    ///
+    /// See [`Report::render`].
+    pub fn to_csv(&self) -> Result<String> {
+        let mut out = format!(
+            "# start={} end={} pick={} draw_size={}",
+            self.range.start().value(),
+            self.range.end().value(),
+            self.pick.value(),
+            self.draw.value()
+        );
+        if let Some(seed) = self.seed {
+            out.push_str(&format!(" seed={}", seed));
+        }
+        out.push('\n');
+        if let Some((matched, table)) = self.odds()? {
+            out.push_str(&format!("# odds matched={}\n", matched));
+            for row in &table {
+                out.push_str(&format!(
+                    "# matches={} favorable={} total={} probability={}\n",
+                    row.matches(),
+                    row.favorable(),
+                    row.total(),
+                    row.probability().display(ProbabilityFormat::Scientific)
+                ));
+            }
+        }
+
+        out.push_str("index");
+        for i in 1..=self.pick.value() {
+            out.push_str(&format!(",ball_{}", i));
+        }
+        if self.ranks {
+            out.push_str(",rank");
+        }
+        if self.codes {
+            out.push_str(",code");
+        }
+        out.push('\n');
+
+        for (i, ticket) in self.tickets.iter().enumerate() {
+            out.push_str(&(i + 1).to_string());
+            for ball in ticket.balls() {
+                out.push_str(&format!(",{}", ball.value()));
+            }
+            if self.ranks {
+                out.push_str(&format!(",{}", rank(ticket, &self.range)?));
+            }
+            if self.codes {
+                out.push_str(&format!(",{}", encode(ticket, &self.range)?));
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Game and seed members shared by the JSON formats.
+    fn metadata(&self) -> Vec<(String, Json)> {
+        vec![
+            (
+                "game".to_string(),
+                Json::object([
+                    ("start", Json::from(self.range.start().value())),
+                    ("end", Json::from(self.range.end().value())),
+                    ("pick", Json::from(self.pick.value())),
+                    ("draw_size", Json::from(self.draw.value())),
+                ]),
+            ),
+            ("seed".to_string(), Json::from(self.seed)),
+            ("count".to_string(), Json::from(self.tickets.len())),
+        ]
+    }
+
+    /// Members describing the ticket at `index`.
+    fn ticket_record(&self, index: usize) -> Result<Vec<(String, Json)>> {
+        let ticket = &self.tickets[index];
+        let balls = ticket.balls().iter().map(|ball| Json::from(ball.value()));
+        let mut record = vec![
+            ("index".to_string(), Json::from(index + 1)),
+            ("balls".to_string(), Json::array(balls)),
+        ];
+        if self.ranks {
+            record.push(("rank".to_string(), Json::from(&rank(ticket, &self.range)?)));
+        }
+        if self.codes {
+            record.push(("code".to_string(), Json::from(encode(ticket, &self.range)?)));
+        }
+        Ok(record)
+    }
+
+    /// The odds table, when a match count was requested.
+    fn odds(&self) -> Result<Option<(usize, Vec<MatchOdds>)>> {
+        let Some(matched) = self.matched else {
+            return Ok(None);
+        };
+        if matched > self.draw.value() {
+            return Err(LottoError::InvalidMatchCount {
+                match_count: matched,
+                pick_count: self.draw.value(),
+            });
+        }
+        let table =
+            match_distribution_for_bet(self.range.size(), self.pick.value(), self.draw.value())?;
+        Ok(Some((matched, table)))
+    }
+}
+
+/// Members describing one row of the odds table.
+pub(crate) fn odds_record(row: &MatchOdds) -> Vec<(String, Json)> {
+    vec![
+        ("matches".to_string(), Json::from(row.matches())),
+        ("favorable".to_string(), Json::from(row.favorable())),
+        (
+            "at_least_favorable".to_string(),
+            Json::from(row.at_least_favorable()),
+        ),
+        ("total".to_string(), Json::from(row.total())),
+        (
+            "probability".to_string(),
+            Json::from(row.probability().to_f64()),
+        ),
+        (
+            "at_least_probability".to_string(),
+            Json::from(row.at_least_probability().to_f64()),
+        ),
+    ]
+}
+
+/// Prefix a record's members with its NDJSON record type.
+fn typed(kind: &str, members: Vec<(String, Json)>) -> Json {
+    let mut record = vec![("type".to_string(), Json::from(kind))];
+    record.extend(members);
+    Json::Object(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newtypes::BallNumber;
+
+    fn tickets() -> Vec<Ticket> {
+        [[1, 2, 3, 4, 5, 6], [55, 56, 57, 58, 59, 60]]
+            .iter()
+            .map(|balls| Ticket::new(balls.map(BallNumber::new).to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_parsing_is_case_insensitive() {
+        assert_eq!("JSON".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(OutputFormat::Csv.to_string(), "csv");
+    }
+
+    #[test]
+    fn test_json_document() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let json = Report::new(&config, &tickets)
+            .with_seed(42)
+            .with_ranks()
+            .to_json()
+            .unwrap();
+
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"game":{"start":1,"end":60,"pick":6,"draw_size":6},"seed":42,"count":2,"#,
+                r#""tickets":[{"index":1,"balls":[1,2,3,4,5,6],"rank":0},"#,
+                r#"{"index":2,"balls":[55,56,57,58,59,60],"rank":50063859}]}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_ndjson_records_include_odds() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let ndjson = Report::new(&config, &tickets)
+            .with_matched(6)
+            .to_ndjson()
+            .unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+
+        assert_eq!(lines.len(), 1 + 2 + 7);
+        assert!(lines[0].starts_with(r#"{"type":"metadata","#));
+        assert!(lines[0].contains(r#""seed":null"#));
+        assert!(lines[1].starts_with(r#"{"type":"ticket","index":1,"#));
+        assert!(lines[9].starts_with(
+            r#"{"type":"odds","matched":6,"matches":6,"favorable":1,"at_least_favorable":1,"total":50063860,"#
+        ));
+    }
+
+    #[test]
+    fn test_csv_rows_and_odds_comments() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let csv = Report::new(&config, &tickets)
+            .with_ranks()
+            .with_matched(5)
+            .to_csv()
+            .unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "# start=1 end=60 pick=6 draw_size=6");
+        assert_eq!(lines[1], "# odds matched=5");
+        assert_eq!(
+            lines[7],
+            "# matches=5 favorable=324 total=50063860 probability=6.47e-6"
+        );
+        assert_eq!(
+            lines[9],
+            "index,ball_1,ball_2,ball_3,ball_4,ball_5,ball_6,rank"
+        );
+        assert_eq!(lines[11], "2,55,56,57,58,59,60,50063859");
+    }
+
+    #[test]
+    fn test_codes_column() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let report = Report::new(&config, &tickets).with_codes();
+
+        assert!(
+            report
+                .to_csv()
+                .unwrap()
+                .ends_with(",ball_6,code\n1,1,2,3,4,5,6,0000000\n2,55,56,57,58,59,60,1FQTFKA\n")
+        );
+        assert!(report.to_json().unwrap().contains(r#""code":"0000000""#));
+    }
+
+    #[test]
+    fn test_text_is_left_to_the_caller() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let report = Report::new(&config, &tickets);
+
+        assert_eq!(report.render(OutputFormat::Text).unwrap(), None);
+        assert!(report.render(OutputFormat::Json).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_matched_beyond_draw_size_is_rejected() {
+        let config = Config::new(2, 1, 60, 6).unwrap();
+        let tickets = tickets();
+        let report = Report::new(&config, &tickets).with_matched(7);
+
+        assert!(matches!(
+            report.to_json(),
+            Err(LottoError::InvalidMatchCount { .. })
+        ));
+    }
+}
@@ -0,0 +1,668 @@
+//! Number popularity and jackpot sharing.
+//!
+//! Human players do not pick uniformly: birthdays (balls up to 31), visual
+//! patterns and the previous draw are all over-represented. The chance of
+//! winning does not depend on the numbers, but the number of people a
+//! jackpot is split with does. This module models how popular a ticket is,
+//! generates tickets that avoid popular combinations, and adjusts the
+//! jackpot of a [`PrizeTable`] for the expected sharing.
+//!
+//! # Model
+//!
+//! A human ticket is assumed to be drawn with probability proportional to
+//! the product of its ball weights, times a factor for each pattern it
+//! contains. The weights are normalized with the elementary symmetric
+//! polynomial of degree `pick`, so a ticket of average popularity scores 1.
+//!
+//! # File format
+//!
+//! Models can be loaded from a text file with one `key = value` per line
+//! (blank lines and `#` comments are ignored):
+//!
+//! ```text
+//! players = 20000000      # tickets sold per draw
+//! balls 1-31 = 1.5        # weight of a range of balls
+//! ball 7 = 1.8            # weight of a single ball
+//! run = 2.0               # factor for three or more consecutive balls
+//! progression = 20.0      # factor for evenly spaced balls
+//! previous = 4 8 15 16 23 42
+//! previous_factor = 1.5   # factor per ball shared with the previous draw
+//! ```
+
+use crate::error::{LottoError, Result};
+use crate::expected_value::PrizeTable;
+use crate::newtypes::{BallNumber, BallRange, GameCount, PickCount, Ticket};
+use crate::probability::combination_big;
+use crate::rng::RandomNumberGenerator;
+use crate::ticket::generate_ticket;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Attempts per ticket before settling for the least popular candidate seen.
+const MAX_REJECTIONS: usize = 1_000;
+
+/// Model of how human players pick their numbers.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::popularity::PopularityModel;
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let model = PopularityModel::birthday_bias(&range, &pick, 20_000_000.0).unwrap();
+///
+/// let birthdays = Ticket::new([3, 7, 12, 19, 25, 31].map(BallNumber::new).to_vec());
+/// let high = Ticket::new([34, 39, 44, 47, 53, 58].map(BallNumber::new).to_vec());
+/// assert!(model.expected_co_winners(&birthdays) > model.expected_co_winners(&high));
+/// assert!(model.jackpot_share(&high) > model.jackpot_share(&birthdays));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PopularityModel {
+    range: BallRange,
+    pick: PickCount,
+    players: f64,
+    weights: Vec<f64>,
+    run_factor: f64,
+    progression_factor: f64,
+    previous_draw: Option<Ticket>,
+    previous_factor: f64,
+    normalizer: f64,
+}
+
+impl PopularityModel {
+    /// Create a model where every ticket is equally popular.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of ball numbers of the game
+    /// * `pick` - Number of balls picked per ticket
+    /// * `players` - Number of tickets sold per draw
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidAmount` if `players` is negative or not finite.
+    pub fn new(range: &BallRange, pick: &PickCount, players: f64) -> Result<Self> {
+        validate_factor("players", players)?;
+        let mut model = Self {
+            range: *range,
+            pick: *pick,
+            players,
+            weights: vec![1.0; range.size()],
+            run_factor: 1.0,
+            progression_factor: 1.0,
+            previous_draw: None,
+            previous_factor: 1.0,
+            normalizer: 1.0,
+        };
+        model.normalize();
+        Ok(model)
+    }
+
+    /// Create the built-in model of birthday and pattern bias.
+    ///
+    /// Balls up to 12 (months) and up to 31 (days) are favored, and runs
+    /// and arithmetic progressions are heavily over-played.
+    pub fn birthday_bias(range: &BallRange, pick: &PickCount, players: f64) -> Result<Self> {
+        let mut model = Self::new(range, pick, players)?;
+        for (weight, ball) in model.weights.iter_mut().zip(range.iter()) {
+            *weight = match ball.value() {
+                1..=12 => 1.8,
+                13..=31 => 1.5,
+                _ => 1.0,
+            };
+        }
+        model.run_factor = 2.0;
+        model.progression_factor = 20.0;
+        model.normalize();
+        Ok(model)
+    }
+
+    /// Load a model from a file (see the module documentation for the format).
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the file cannot be read and `ParseError` if a
+    /// line is malformed.
+    pub fn load(path: impl AsRef<Path>, range: &BallRange, pick: &PickCount) -> Result<Self> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| LottoError::IoError(format!("{}: {}", path.as_ref().display(), e)))?;
+        Self::parse(&text, range, pick)
+    }
+
+    /// Parse a model from text (see the module documentation for the format).
+    ///
+    /// Balls not mentioned keep a weight of 1 and there are no pattern
+    /// factors unless given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::popularity::PopularityModel;
+    /// use lotto_quick_pick::newtypes::{BallRange, PickCount};
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let pick = PickCount::new(6, &range).unwrap();
+    /// let model = PopularityModel::parse("players = 1000\nballs 1-31 = 2", &range, &pick).unwrap();
+    /// assert_eq!(model.players(), 1000.0);
+    /// ```
+    pub fn parse(text: &str, range: &BallRange, pick: &PickCount) -> Result<Self> {
+        let mut model = Self::new(range, pick, 0.0)?;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            model
+                .apply_line(line)
+                .map_err(|e| LottoError::ParseError(format!("line {}: {}", index + 1, e)))?;
+        }
+        model.normalize();
+        Ok(model)
+    }
+
+    fn apply_line(&mut self, line: &str) -> std::result::Result<(), String> {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("expected KEY = VALUE, got '{}'", line))?;
+        let (key, value) = (key.trim(), value.trim());
+
+        let number = |value: &str| -> std::result::Result<f64, String> {
+            let number: f64 = value
+                .parse()
+                .map_err(|e| format!("invalid number '{}': {}", value, e))?;
+            validate_factor(key, number).map_err(|e| e.to_string())?;
+            Ok(number)
+        };
+        let ball = |value: &str| -> std::result::Result<BallNumber, String> {
+            let ball = BallNumber::new(
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("invalid ball '{}': {}", value, e))?,
+            );
+            if self.range.contains(ball) {
+                Ok(ball)
+            } else {
+                Err(format!("ball {} is outside the game range", ball.value()))
+            }
+        };
+
+        match key.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["players"] => self.players = number(value)?,
+            ["run"] => self.run_factor = number(value)?,
+            ["progression"] => self.progression_factor = number(value)?,
+            ["previous_factor"] => self.previous_factor = number(value)?,
+            ["previous"] => {
+                let balls = value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|s| !s.is_empty())
+                    .map(ball)
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                self.previous_draw = Some(Ticket::new(balls));
+            }
+            ["ball", which] => {
+                let index = self.index(ball(which)?);
+                self.weights[index] = number(value)?;
+            }
+            ["balls", which] => {
+                let (first, last) = which
+                    .split_once('-')
+                    .ok_or_else(|| format!("expected FIRST-LAST, got '{}'", which))?;
+                let (first, last) = (self.index(ball(first)?), self.index(ball(last)?));
+                let weight = number(value)?;
+                for w in &mut self.weights[first.min(last)..=first.max(last)] {
+                    *w = weight;
+                }
+            }
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+        Ok(())
+    }
+
+    /// Set the weight of a single ball.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BallOutOfRange` for balls outside the game range and
+    /// `InvalidAmount` for negative or non-finite weights.
+    pub fn with_ball_weight(mut self, ball: BallNumber, weight: f64) -> Result<Self> {
+        if !self.range.contains(ball) {
+            return Err(LottoError::BallOutOfRange {
+                value: ball.value(),
+                start: self.range.start().value(),
+                end: self.range.end().value(),
+            });
+        }
+        validate_factor("ball weight", weight)?;
+        let index = self.index(ball);
+        self.weights[index] = weight;
+        self.normalize();
+        Ok(self)
+    }
+
+    /// Set the number of tickets sold per draw.
+    pub fn with_players(mut self, players: f64) -> Result<Self> {
+        validate_factor("players", players)?;
+        self.players = players;
+        Ok(self)
+    }
+
+    /// Set the previous draw and the factor applied per ball shared with it.
+    pub fn with_previous_draw(mut self, draw: Ticket, factor_per_ball: f64) -> Result<Self> {
+        validate_factor("previous draw factor", factor_per_ball)?;
+        self.previous_draw = Some(draw);
+        self.previous_factor = factor_per_ball;
+        Ok(self)
+    }
+
+    /// Get the number of tickets sold per draw.
+    pub fn players(&self) -> f64 {
+        self.players
+    }
+
+    /// Get the weight of a ball, or `None` if it is outside the range.
+    pub fn ball_weight(&self, ball: BallNumber) -> Option<f64> {
+        self.range
+            .contains(ball)
+            .then(|| self.weights[self.index(ball)])
+    }
+
+    /// How many times more popular than an average ticket this ticket is.
+    pub fn relative_popularity(&self, ticket: &Ticket) -> f64 {
+        let balls = ticket.balls();
+        let mut score: f64 = balls
+            .iter()
+            .filter(|&&ball| self.range.contains(ball))
+            .map(|&ball| self.weights[self.index(ball)])
+            .product();
+
+        if has_run(balls, 3) {
+            score *= self.run_factor;
+        }
+        if balls.len() >= 3 && is_progression(balls) {
+            score *= self.progression_factor;
+        }
+        if let Some(previous) = &self.previous_draw {
+            let shared = balls.iter().filter(|b| previous.contains(b)).count();
+            score *= self.previous_factor.powi(shared as i32);
+        }
+
+        score * self.normalizer
+    }
+
+    /// Probability that a single human-picked ticket equals this ticket.
+    pub fn ticket_probability(&self, ticket: &Ticket) -> f64 {
+        let combinations = combination_big(self.range.size(), self.pick.value()).to_f64();
+        self.relative_popularity(ticket) / combinations
+    }
+
+    /// Expected number of other players holding this exact ticket.
+    pub fn expected_co_winners(&self, ticket: &Ticket) -> f64 {
+        self.players * self.ticket_probability(ticket)
+    }
+
+    /// Expected fraction of the jackpot kept if this ticket wins.
+    ///
+    /// Co-winners are Poisson distributed with mean λ, so the expected share
+    /// E[1 / (1 + X)] is (1 - e^-λ) / λ.
+    pub fn jackpot_share(&self, ticket: &Ticket) -> f64 {
+        let lambda = self.expected_co_winners(ticket);
+        if lambda <= 0.0 {
+            1.0
+        } else {
+            -(-lambda).exp_m1() / lambda
+        }
+    }
+
+    /// Scale the jackpot of a prize table by the expected share for a ticket.
+    ///
+    /// Pass the result to [`crate::expected_value::expected_value`] to get
+    /// an EV that accounts for splitting the jackpot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::expected_value::{PrizeTable, expected_value};
+    /// use lotto_quick_pick::popularity::PopularityModel;
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let pick = PickCount::new(6, &range).unwrap();
+    /// let model = PopularityModel::birthday_bias(&range, &pick, 50_000_000.0).unwrap();
+    /// let ticket = Ticket::new([1, 2, 3, 4, 5, 6].map(BallNumber::new).to_vec());
+    ///
+    /// let table = PrizeTable::new(5.0, 100_000_000.0);
+    /// let shared = model.adjust_prize_table(&table, &ticket);
+    /// assert!(shared.jackpot() < table.jackpot());
+    /// let report = expected_value(60, 6, &shared).unwrap();
+    /// assert!(report.return_to_player() < 40.0);
+    /// ```
+    pub fn adjust_prize_table(&self, table: &PrizeTable, ticket: &Ticket) -> PrizeTable {
+        table
+            .clone()
+            .with_jackpot(table.jackpot() * self.jackpot_share(ticket))
+    }
+
+    fn index(&self, ball: BallNumber) -> usize {
+        (ball.value() - self.range.start().value()) as usize
+    }
+
+    /// Recompute the factor that makes an average ticket score 1.
+    fn normalize(&mut self) {
+        // Average weight of a ticket: e_k(w) / C(n, k), built up one ball
+        // at a time so the intermediate values stay close to 1
+        let k = self.pick.value();
+        let mut average = vec![0.0; k + 1];
+        average[0] = 1.0;
+        for (seen, &weight) in self.weights.iter().enumerate() {
+            let seen = seen + 1;
+            for j in (1..=k.min(seen)).rev() {
+                // Mean over C(seen, j) subsets, from the means over seen - 1 balls
+                let with = j as f64 / seen as f64;
+                average[j] = (1.0 - with) * average[j] + with * average[j - 1] * weight;
+            }
+        }
+        self.normalizer = if average[k] > 0.0 {
+            1.0 / average[k]
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Generate unique tickets that prefer unpopular combinations.
+///
+/// Candidates are drawn uniformly and accepted with probability
+/// `min(1, 1 / relative_popularity)`, so unpopular tickets are as likely as
+/// ever and popular ones are thinned out in proportion to their popularity.
+/// The result stays random, just tilted away from the crowd.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `model` - Popularity model of the game
+/// * `game_count` - Number of unique tickets to generate
+///
+/// # Returns
+///
+/// The tickets, or the same errors as [`crate::ticket::generate_unique_tickets`].
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::popularity::{PopularityModel, generate_unpopular_tickets};
+/// use lotto_quick_pick::newtypes::{BallRange, GameCount, PickCount};
+///
+/// let mut rng = rand::rng();
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let model = PopularityModel::birthday_bias(&range, &pick, 20_000_000.0).unwrap();
+///
+/// let tickets = generate_unpopular_tickets(&mut rng, &model, &GameCount::new(5).unwrap()).unwrap();
+/// assert_eq!(tickets.len(), 5);
+/// ```
+pub fn generate_unpopular_tickets<R: RandomNumberGenerator>(
+    rng: &mut R,
+    model: &PopularityModel,
+    game_count: &GameCount,
+) -> Result<Vec<Ticket>> {
+    generate_unpopular_tickets_filtered(rng, model, game_count, |_| true)
+}
+
+/// Generate unique unpopular tickets that also satisfy a filter, such as a
+/// [`crate::betslip::PatternFilter`].
+///
+/// Tickets are generated as in [`generate_unpopular_tickets`] and rejected
+/// when `filter` returns false.
+pub fn generate_unpopular_tickets_filtered<R, F>(
+    rng: &mut R,
+    model: &PopularityModel,
+    game_count: &GameCount,
+    filter: F,
+) -> Result<Vec<Ticket>>
+where
+    R: RandomNumberGenerator,
+    F: Fn(&Ticket) -> bool,
+{
+    let requested = game_count.value();
+    if let Some(maximum) = combination_big(model.range.size(), model.pick.value()).to_u128()
+        && requested as u128 > maximum
+    {
+        return Err(LottoError::TooManyUniqueGames { requested, maximum });
+    }
+
+    let mut seen = HashSet::with_capacity(requested);
+    let mut tickets = Vec::with_capacity(requested);
+    let mut attempts = 0;
+    while tickets.len() < requested {
+        if attempts >= requested * 10_000 {
+            return Err(LottoError::UniqueGenerationFailed {
+                requested,
+                generated: tickets.len(),
+            });
+        }
+        attempts += 1;
+
+        let ticket = generate_unpopular_ticket(rng, model);
+        if filter(&ticket) && seen.insert(ticket.clone()) {
+            tickets.push(ticket);
+        }
+    }
+    Ok(tickets)
+}
+
+/// Generate a single ticket that prefers unpopular combinations.
+///
+/// See [`generate_unpopular_tickets`] for the acceptance rule. After
+/// [`MAX_REJECTIONS`] rejected candidates the least popular one is returned.
+pub fn generate_unpopular_ticket<R: RandomNumberGenerator>(
+    rng: &mut R,
+    model: &PopularityModel,
+) -> Ticket {
+    let mut best: Option<(f64, Ticket)> = None;
+    for _ in 0..MAX_REJECTIONS {
+        let ticket = generate_ticket(rng, &model.range, &model.pick);
+        let popularity = model.relative_popularity(&ticket);
+        if popularity <= 1.0 || accept(rng, 1.0 / popularity) {
+            return ticket;
+        }
+        if best.as_ref().is_none_or(|(p, _)| popularity < *p) {
+            best = Some((popularity, ticket));
+        }
+    }
+    best.map(|(_, ticket)| ticket)
+        .unwrap_or_else(|| generate_ticket(rng, &model.range, &model.pick))
+}
+
+/// Return true with the given probability, using the crate's u8 RNG.
+fn accept<R: RandomNumberGenerator>(rng: &mut R, probability: f64) -> bool {
+    // Build a uniform value in [0, 1) from four random bytes
+    let value = (0..4).fold(0u32, |acc, _| (acc << 8) | rng.gen_range_u8(0, 255) as u32);
+    (value as f64 / 4_294_967_296.0) < probability
+}
+
+/// Check for `length` or more consecutive balls (balls must be sorted).
+fn has_run(balls: &[BallNumber], length: usize) -> bool {
+    let mut current = 1;
+    for pair in balls.windows(2) {
+        if pair[1].value() == pair[0].value() + 1 {
+            current += 1;
+            if current >= length {
+                return true;
+            }
+        } else {
+            current = 1;
+        }
+    }
+    false
+}
+
+/// Check if sorted balls are evenly spaced.
+fn is_progression(balls: &[BallNumber]) -> bool {
+    let step = balls[1].value() - balls[0].value();
+    balls
+        .windows(2)
+        .all(|pair| pair[1].value() - pair[0].value() == step)
+}
+
+fn validate_factor(field: &str, value: f64) -> Result<()> {
+    if !value.is_finite() || value < 0.0 {
+        return Err(LottoError::InvalidAmount {
+            field: field.to_string(),
+            value,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mega_sena() -> (BallRange, PickCount) {
+        let range = BallRange::mega_sena();
+        let pick = PickCount::new(6, &range).unwrap();
+        (range, pick)
+    }
+
+    fn ticket(values: [u8; 6]) -> Ticket {
+        Ticket::new(values.map(BallNumber::new).to_vec())
+    }
+
+    #[test]
+    fn test_uniform_model_scores_one() {
+        let (range, pick) = mega_sena();
+        let model = PopularityModel::new(&range, &pick, 50_063_860.0).unwrap();
+        let t = ticket([5, 17, 22, 38, 41, 59]);
+        assert!((model.relative_popularity(&t) - 1.0).abs() < 1e-12);
+        assert!((model.expected_co_winners(&t) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_normalization_averages_to_one() {
+        // 6 balls, pick 2: average the score over all 15 tickets
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(6)).unwrap();
+        let pick = PickCount::new(2, &range).unwrap();
+        let model = PopularityModel::new(&range, &pick, 0.0)
+            .unwrap()
+            .with_ball_weight(BallNumber::new(1), 3.0)
+            .unwrap()
+            .with_ball_weight(BallNumber::new(2), 0.5)
+            .unwrap();
+
+        let mut total = 0.0;
+        for a in 1..=6u8 {
+            for b in a + 1..=6 {
+                let t = Ticket::new(vec![BallNumber::new(a), BallNumber::new(b)]);
+                total += model.relative_popularity(&t);
+            }
+        }
+        assert!((total / 15.0 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_patterns_are_penalized() {
+        let (range, pick) = mega_sena();
+        let model = PopularityModel::birthday_bias(&range, &pick, 1.0).unwrap();
+        let plain = ticket([33, 37, 42, 48, 51, 59]);
+        let run = ticket([33, 34, 35, 48, 51, 59]);
+        let progression = ticket([35, 40, 45, 50, 55, 60]);
+        assert!(model.relative_popularity(&run) > model.relative_popularity(&plain));
+        assert!(model.relative_popularity(&progression) > 10.0 * model.relative_popularity(&plain));
+    }
+
+    #[test]
+    fn test_jackpot_share() {
+        let (range, pick) = mega_sena();
+        let nobody = PopularityModel::new(&range, &pick, 0.0).unwrap();
+        let t = ticket([5, 17, 22, 38, 41, 59]);
+        assert_eq!(nobody.jackpot_share(&t), 1.0);
+
+        // λ = 1: (1 - 1/e) / 1
+        let crowd = PopularityModel::new(&range, &pick, 50_063_860.0).unwrap();
+        assert!((crowd.jackpot_share(&t) - (1.0 - (-1.0f64).exp())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_adjust_prize_table_keeps_lower_tiers() {
+        let (range, pick) = mega_sena();
+        let model = PopularityModel::new(&range, &pick, 50_063_860.0).unwrap();
+        let table = PrizeTable::new(5.0, 1_000.0).with_tier(5, 50.0);
+        let adjusted = model.adjust_prize_table(&table, &ticket([5, 17, 22, 38, 41, 59]));
+        assert!((adjusted.jackpot() - 1_000.0 * (1.0 - (-1.0f64).exp())).abs() < 1e-6);
+        assert_eq!(adjusted.tiers(), table.tiers());
+    }
+
+    #[test]
+    fn test_parse_model() {
+        let (range, pick) = mega_sena();
+        let text = "# sample\nplayers = 1e6\nballs 1-31 = 2 # birthdays\nball 60 = 0.5\n\
+                    previous = 1, 2, 3, 4, 5, 6\nprevious_factor = 1.5\n";
+        let model = PopularityModel::parse(text, &range, &pick).unwrap();
+        assert_eq!(model.players(), 1e6);
+        assert_eq!(model.ball_weight(BallNumber::new(31)), Some(2.0));
+        assert_eq!(model.ball_weight(BallNumber::new(32)), Some(1.0));
+        assert_eq!(model.ball_weight(BallNumber::new(60)), Some(0.5));
+
+        let shares_previous = ticket([1, 2, 40, 45, 50, 55]);
+        let fresh = ticket([36, 39, 40, 45, 50, 55]);
+        let ratio = model.relative_popularity(&shares_previous) / model.relative_popularity(&fresh);
+        assert!((ratio - 2.0 * 2.0 * 1.5 * 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let (range, pick) = mega_sena();
+        for text in [
+            "players = 1\nball 61 = 2",
+            "players = 1\nspeed = 3",
+            "\nrun = -1",
+        ] {
+            match PopularityModel::parse(text, &range, &pick) {
+                Err(LottoError::ParseError(message)) => assert!(message.starts_with("line 2")),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let (range, pick) = mega_sena();
+        let result = PopularityModel::load("/nonexistent/popularity.txt", &range, &pick);
+        assert!(matches!(result, Err(LottoError::IoError(_))));
+    }
+
+    #[test]
+    fn test_unpopular_generation_avoids_birthdays() {
+        let (range, pick) = mega_sena();
+        let model = PopularityModel::birthday_bias(&range, &pick, 1.0).unwrap();
+        let mut rng = rand::rng();
+        let count = GameCount::new(1_000).unwrap();
+
+        let tickets = generate_unpopular_tickets(&mut rng, &model, &count).unwrap();
+        assert_eq!(tickets.iter().collect::<HashSet<_>>().len(), 1_000);
+
+        // A uniformly random ticket averages a relative popularity of 1
+        let mean = tickets
+            .iter()
+            .map(|t| model.relative_popularity(t))
+            .sum::<f64>()
+            / 1_000.0;
+        assert!(mean < 0.95, "mean relative popularity {}", mean);
+    }
+
+    #[test]
+    fn test_unpopular_generation_too_many() {
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(5)).unwrap();
+        let pick = PickCount::new(2, &range).unwrap();
+        let model = PopularityModel::birthday_bias(&range, &pick, 1.0).unwrap();
+        let mut rng = rand::rng();
+        let result = generate_unpopular_tickets(&mut rng, &model, &GameCount::new(11).unwrap());
+        assert!(matches!(
+            result,
+            Err(LottoError::TooManyUniqueGames { maximum: 10, .. })
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+//! Catalog of well-known lottery games.
+//!
+//! A [`GamePreset`] names a game's ball range, the number of balls drawn
+//! and the smallest bet. It is the usual starting point of a
+//! [`ConfigBuilder`](crate::ConfigBuilder).
+
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, PickCount};
+use std::fmt;
+use std::str::FromStr;
+
+/// A well-known lottery game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePreset {
+    /// Mega-Sena: 6 of 1-60
+    MegaSena,
+    /// Lotofácil: 15 of 1-25
+    Lotofacil,
+    /// Quina: 5 of 1-80
+    Quina,
+    /// Dupla Sena: 6 of 1-50
+    DuplaSena,
+    /// Powerball white balls: 5 of 1-69
+    Powerball,
+    /// Lotomania: 50 marked of 0-99, 20 drawn
+    Lotomania,
+}
+
+impl GamePreset {
+    /// Every game in the catalog.
+    pub const ALL: [GamePreset; 6] = [
+        GamePreset::MegaSena,
+        GamePreset::Lotofacil,
+        GamePreset::Quina,
+        GamePreset::DuplaSena,
+        GamePreset::Powerball,
+        GamePreset::Lotomania,
+    ];
+
+    /// Get the name the game is parsed from and displayed as.
+    pub fn name(self) -> &'static str {
+        match self {
+            GamePreset::MegaSena => "mega-sena",
+            GamePreset::Lotofacil => "lotofacil",
+            GamePreset::Quina => "quina",
+            GamePreset::DuplaSena => "dupla-sena",
+            GamePreset::Powerball => "powerball",
+            GamePreset::Lotomania => "lotomania",
+        }
+    }
+
+    /// Get the range of balls in the game.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::newtypes::BallRange;
+    /// use lotto_quick_pick::preset::GamePreset;
+    ///
+    /// assert_eq!(GamePreset::MegaSena.range(), BallRange::mega_sena());
+    /// assert_eq!(GamePreset::Quina.range().size(), 80);
+    /// assert_eq!(GamePreset::Lotomania.range().start().value(), 0);
+    /// ```
+    pub fn range(self) -> BallRange {
+        let (start, end) = match self {
+            GamePreset::MegaSena => (1, 60),
+            GamePreset::Lotofacil => (1, 25),
+            GamePreset::Quina => (1, 80),
+            GamePreset::DuplaSena => (1, 50),
+            GamePreset::Powerball => (1, 69),
+            GamePreset::Lotomania => (0, 99),
+        };
+        BallRange::new(BallNumber::new(start), BallNumber::new(end))
+            .expect("preset ranges are valid")
+    }
+
+    /// Get the number of balls marked by the smallest bet.
+    pub fn pick(self) -> PickCount {
+        let pick = match self {
+            GamePreset::Lotofacil => 15,
+            GamePreset::Quina | GamePreset::Powerball => 5,
+            GamePreset::MegaSena | GamePreset::DuplaSena => 6,
+            GamePreset::Lotomania => 50,
+        };
+        PickCount::new(pick, &self.range()).expect("preset picks fit their ranges")
+    }
+
+    /// Get the number of balls drawn.
+    ///
+    /// Most games draw as many balls as their smallest bet marks, and bets
+    /// marking more balls are multi-number bets. Lotomania draws 20 balls
+    /// against bets of 50.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::preset::GamePreset;
+    ///
+    /// assert_eq!(GamePreset::MegaSena.draw_size().value(), 6);
+    /// assert_eq!(GamePreset::Lotomania.draw_size().value(), 20);
+    /// ```
+    pub fn draw_size(self) -> PickCount {
+        let draw = match self {
+            GamePreset::Lotofacil => 15,
+            GamePreset::Quina | GamePreset::Powerball => 5,
+            GamePreset::MegaSena | GamePreset::DuplaSena => 6,
+            GamePreset::Lotomania => 20,
+        };
+        PickCount::new(draw, &self.range()).expect("preset draws fit their ranges")
+    }
+}
+
+impl FromStr for GamePreset {
+    type Err = LottoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.to_ascii_lowercase();
+        GamePreset::ALL
+            .into_iter()
+            .find(|game| game.name() == name)
+            .ok_or_else(|| {
+                let known: Vec<&str> = GamePreset::ALL.iter().map(|game| game.name()).collect();
+                LottoError::ParseError(format!(
+                    "unknown game '{}' (expected {})",
+                    s,
+                    known.join(", ")
+                ))
+            })
+    }
+}
+
+impl fmt::Display for GamePreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_consistent() {
+        for game in GamePreset::ALL {
+            assert!(game.pick().value() <= game.range().size());
+            assert!(game.draw_size().value() <= game.pick().value());
+            assert_eq!(game.name().parse::<GamePreset>().unwrap(), game);
+        }
+        assert_eq!(GamePreset::Lotofacil.range().size(), 25);
+        assert_eq!(GamePreset::DuplaSena.range().end().value(), 50);
+        assert_eq!(GamePreset::Powerball.pick().value(), 5);
+        assert_eq!(GamePreset::Lotomania.range(), BallRange::lotomania());
+        assert_eq!(GamePreset::Lotomania.pick().value(), 50);
+        assert_eq!(GamePreset::Lotomania.draw_size().value(), 20);
+    }
+
+    #[test]
+    fn test_parse_ignores_case_and_rejects_unknown() {
+        assert_eq!("Mega-Sena".parse::<GamePreset>(), Ok(GamePreset::MegaSena));
+        let error = "megasena".parse::<GamePreset>().unwrap_err();
+        assert!(error.to_string().contains("expected mega-sena, lotofacil"));
+    }
+}
@@ -3,7 +3,12 @@
 //! This module provides functions for calculating lottery probabilities
 //! using efficient algorithms that avoid factorial calculation.
 
+use crate::bigint::BigUint;
 use crate::error::{LottoError, Result};
+use crate::locale::Locale;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Mul;
 
 /// Calculate the binomial coefficient C(n, k) without using factorial.
 ///
@@ -51,32 +56,56 @@ pub fn combination(n: usize, k: usize) -> Result<u128> {
     let mut result: u128 = 1;
 
     for i in 0..k {
-        // Calculate (n - i) / (i + 1) iteratively with overflow checking
-        result =
-            result
-                .checked_mul((n - i) as u128)
-                .ok_or_else(|| LottoError::CalculationOverflow {
-                    operation: format!(
-                        "combination C({},{}) multiplication overflow at iteration {}",
-                        n, k, i
-                    ),
-                })?;
-
-        // Division should never fail, but check for safety
-        result =
-            result
-                .checked_div((i + 1) as u128)
-                .ok_or_else(|| LottoError::CalculationOverflow {
-                    operation: format!(
-                        "combination C({},{}) division by zero at iteration {}",
-                        n, k, i
-                    ),
-                })?;
+        // Calculate (n - i) / (i + 1) iteratively with overflow checking.
+        // The intermediate product can overflow even when C(n,k) itself
+        // fits, so fall back to exact big-integer arithmetic in that case.
+        result = match result.checked_mul((n - i) as u128) {
+            Some(product) => product / (i + 1) as u128,
+            None => {
+                return combination_big(n, k).to_u128().ok_or_else(|| {
+                    LottoError::CalculationOverflow {
+                        operation: format!("combination C({},{}) exceeds u128", n, k),
+                    }
+                });
+            }
+        };
     }
 
     Ok(result)
 }
 
+/// Calculate the binomial coefficient C(n, k) with arbitrary precision.
+///
+/// Uses the same iterative algorithm as [`combination`], but never
+/// overflows. Useful for wide ranges such as C(255, 127), which exceeds u128.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::{combination, combination_big};
+///
+/// assert_eq!(combination_big(60, 6).to_u128(), Some(50_063_860));
+///
+/// // Beyond u128: combination() reports overflow, combination_big() does not
+/// assert!(combination(255, 127).is_err());
+/// assert_eq!(combination_big(255, 127).bits(), 251);
+/// ```
+pub fn combination_big(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+
+    let k = k.min(n - k);
+    let mut result = BigUint::one();
+
+    for i in 0..k {
+        // Each partial product is C(n, i+1) * (i+1)!/(i+1)!, so the division is exact
+        result = &(&result * &BigUint::from(n - i)) / &BigUint::from(i + 1);
+    }
+
+    result
+}
+
 /// Calculate the probability of matching exactly `match_count` balls
 /// in a lottery game.
 ///
@@ -91,6 +120,11 @@ pub fn combination(n: usize, k: usize) -> Result<u128> {
 /// A tuple of (favorable_outcomes, total_outcomes) representing the probability.
 /// The actual probability is favorable_outcomes / total_outcomes.
 ///
+/// # Errors
+///
+/// `CalculationOverflow` if an outcome count exceeds u128, as in ranges
+/// near 256 balls; [`calculate_probability_big`] handles every game.
+///
 /// # Examples
 ///
 /// ```
@@ -130,6 +164,45 @@ pub fn calculate_probability(
     Ok((favorable_outcomes, total_outcomes))
 }
 
+/// Calculate the probability of matching exactly `match_count` balls
+/// with arbitrary precision.
+///
+/// Unlike [`calculate_probability`], this never overflows, so it works for
+/// every valid game, including ranges of 256 balls.
+///
+/// # Returns
+///
+/// A tuple of (favorable_outcomes, total_outcomes), or an error if
+/// `match_count` exceeds `pick_count`.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::calculate_probability_big;
+///
+/// let (favorable, total) = calculate_probability_big(256, 128, 128).unwrap();
+/// assert_eq!(favorable.to_u128(), Some(1));
+/// assert!(total.to_u128().is_none());
+/// ```
+pub fn calculate_probability_big(
+    total_balls: usize,
+    pick_count: usize,
+    match_count: usize,
+) -> Result<(BigUint, BigUint)> {
+    if match_count > pick_count {
+        return Err(LottoError::InvalidMatchCount {
+            match_count,
+            pick_count,
+        });
+    }
+
+    let total_outcomes = combination_big(total_balls, pick_count);
+    let ways_to_match = combination_big(pick_count, match_count);
+    let ways_to_miss = combination_big(total_balls - pick_count, pick_count - match_count);
+
+    Ok((&ways_to_match * &ways_to_miss, total_outcomes))
+}
+
 /// Calculate probability using Config object.
 ///
 /// This is a convenience function that extracts the necessary values
@@ -145,6 +218,11 @@ pub fn calculate_probability(
 ///
 /// A tuple of (favorable_outcomes, total_outcomes) or an error
 ///
+/// # Errors
+///
+/// `CalculationOverflow` if an outcome count exceeds u128;
+/// [`calculate_probability_big_for_config`] succeeds for every valid Config.
+///
 /// # Examples
 ///
 /// ```
@@ -161,6 +239,571 @@ pub fn calculate_probability_for_config(
     calculate_probability(config.range().size(), config.pick().value(), match_count)
 }
 
+/// Calculate probability with arbitrary precision using Config object.
+///
+/// Never overflows, so it succeeds for every valid Config.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::{Config, probability::calculate_probability_big_for_config};
+///
+/// let config = Config::new(1, 0, 255, 127).unwrap();
+/// let (favorable, _total) = calculate_probability_big_for_config(&config, 127).unwrap();
+/// assert_eq!(favorable.to_u128(), Some(1));
+/// ```
+pub fn calculate_probability_big_for_config(
+    config: &crate::Config,
+    match_count: usize,
+) -> Result<(BigUint, BigUint)> {
+    calculate_probability_big(config.range().size(), config.pick().value(), match_count)
+}
+
+/// An exact probability, stored as a fraction in lowest terms.
+///
+/// Built from favorable and total outcome counts, it reduces itself on
+/// construction, so equal probabilities compare equal regardless of how
+/// they were obtained. Formatting is exact as well: "1 in N" and
+/// percentages are rounded from the fraction, not from an f64.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::{Probability, ProbabilityFormat};
+///
+/// let quina = Probability::new(324u32, 50_063_860u32).unwrap();
+/// assert_eq!(quina.to_string(), "81/12515965");
+/// assert_eq!(quina.display(ProbabilityFormat::OneIn).to_string(), "1 in 154518");
+/// assert_eq!(format!("{:.4}", quina.display(ProbabilityFormat::Percentage)), "0.0006%");
+/// assert_eq!(quina.display(ProbabilityFormat::Scientific).to_string(), "6.47e-6");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Probability {
+    numerator: BigUint,
+    denominator: BigUint,
+}
+
+impl Probability {
+    /// Create a probability from favorable and total outcome counts.
+    ///
+    /// # Arguments
+    ///
+    /// * `favorable` - Number of favorable outcomes
+    /// * `total` - Number of possible outcomes
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidProbability` if `total` is zero or smaller than
+    /// `favorable`.
+    pub fn new(favorable: impl Into<BigUint>, total: impl Into<BigUint>) -> Result<Self> {
+        let (favorable, total) = (favorable.into(), total.into());
+        if total.is_zero() || favorable > total {
+            return Err(LottoError::InvalidProbability {
+                favorable: favorable.to_string(),
+                total: total.to_string(),
+            });
+        }
+        Ok(Self::reduced(favorable, total))
+    }
+
+    /// The impossible event.
+    pub fn zero() -> Self {
+        Self {
+            numerator: BigUint::zero(),
+            denominator: BigUint::one(),
+        }
+    }
+
+    /// The certain event.
+    pub fn one() -> Self {
+        Self {
+            numerator: BigUint::one(),
+            denominator: BigUint::one(),
+        }
+    }
+
+    /// Reduce a fraction already known to be valid.
+    pub(crate) fn reduced(numerator: BigUint, denominator: BigUint) -> Self {
+        let gcd = numerator.gcd(&denominator);
+        Self {
+            numerator: &numerator / &gcd,
+            denominator: &denominator / &gcd,
+        }
+    }
+
+    /// Get the numerator in lowest terms.
+    pub fn numerator(&self) -> &BigUint {
+        &self.numerator
+    }
+
+    /// Get the denominator in lowest terms.
+    pub fn denominator(&self) -> &BigUint {
+        &self.denominator
+    }
+
+    /// Check if the event is impossible.
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    /// Probability of the event not happening.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::probability::Probability;
+    ///
+    /// let p = Probability::new(1u32, 4u32).unwrap();
+    /// assert_eq!(p.complement(), Probability::new(3u32, 4u32).unwrap());
+    /// ```
+    pub fn complement(&self) -> Self {
+        // gcd(d - n, d) = gcd(n, d) = 1, so the result is already reduced
+        Self {
+            numerator: &self.denominator - &self.numerator,
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    /// Odds as "1 in N", rounded to the nearest integer.
+    ///
+    /// Returns `None` when the event is impossible.
+    pub fn one_in(&self) -> Option<BigUint> {
+        if self.is_zero() {
+            return None;
+        }
+        Some(round_div(&self.denominator, &self.numerator))
+    }
+
+    /// Convert to the nearest f64.
+    ///
+    /// Stays accurate when both counts are too large for f64 on their own.
+    pub fn to_f64(&self) -> f64 {
+        // Drop low bits shared by both sides so neither converts to infinity
+        let excess = self
+            .denominator
+            .bits()
+            .saturating_sub(f64::MAX_EXP as usize - 2);
+        if excess == 0 {
+            return self.numerator.to_f64() / self.denominator.to_f64();
+        }
+        let scale = BigUint::from(2u32).pow(excess as u32);
+        (&self.numerator / &scale).to_f64() / (&self.denominator / &scale).to_f64()
+    }
+
+    /// Probability as a percentage, as an f64.
+    pub fn percentage(&self) -> f64 {
+        self.to_f64() * 100.0
+    }
+
+    /// Wrap the probability for display in the given format.
+    ///
+    /// The formatter precision sets the number of decimals for
+    /// [`ProbabilityFormat::OneIn`] (default 0), [`ProbabilityFormat::Percentage`]
+    /// (default 2) and [`ProbabilityFormat::Scientific`] (default 2). Width and
+    /// alignment are honored for every format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::probability::{Probability, ProbabilityFormat};
+    ///
+    /// let p = Probability::new(2u32, 3u32).unwrap();
+    /// assert_eq!(p.display(ProbabilityFormat::Fraction).to_string(), "2/3");
+    /// assert_eq!(format!("{:.2}", p.display(ProbabilityFormat::OneIn)), "1 in 1.50");
+    /// assert_eq!(p.display(ProbabilityFormat::Percentage).to_string(), "66.67%");
+    /// assert_eq!(format!("{:.3}", p.display(ProbabilityFormat::Scientific)), "6.667e-1");
+    /// ```
+    pub fn display(&self, format: ProbabilityFormat) -> ProbabilityDisplay<'_> {
+        ProbabilityDisplay {
+            probability: self,
+            format,
+            locale: None,
+        }
+    }
+}
+
+impl fmt::Display for Probability {
+    /// Formats as a fraction in lowest terms, e.g. `1/50063860`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(ProbabilityFormat::Fraction).fmt(f)
+    }
+}
+
+impl Ord for Probability {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Probability {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Mul for &Probability {
+    type Output = Probability;
+
+    /// Probability of two independent events both happening.
+    fn mul(self, other: &Probability) -> Probability {
+        Probability::reduced(
+            &self.numerator * &other.numerator,
+            &self.denominator * &other.denominator,
+        )
+    }
+}
+
+impl Mul for Probability {
+    type Output = Probability;
+
+    fn mul(self, other: Probability) -> Probability {
+        &self * &other
+    }
+}
+
+/// Output style for [`Probability::display`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbabilityFormat {
+    /// Fraction in lowest terms, e.g. `81/12515965`.
+    Fraction,
+    /// Odds as "1 in N", e.g. `1 in 154518`, or `never` if impossible.
+    OneIn,
+    /// Percentage, e.g. `0.00%`.
+    Percentage,
+    /// Scientific notation, e.g. `6.47e-6`.
+    Scientific,
+}
+
+/// Helper returned by [`Probability::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProbabilityDisplay<'a> {
+    probability: &'a Probability,
+    format: ProbabilityFormat,
+    locale: Option<Locale>,
+}
+
+impl ProbabilityDisplay<'_> {
+    /// Write the words and numbers for a locale.
+    ///
+    /// Without a locale the output is plain English with ungrouped digits;
+    /// with one, numbers are grouped and use its decimal separator.
+    /// Fractions are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::locale::Locale;
+    /// use lotto_quick_pick::probability::{Probability, ProbabilityFormat};
+    ///
+    /// let mega_sena = Probability::new(1u32, 50063860u32).unwrap();
+    /// let odds = mega_sena.display(ProbabilityFormat::OneIn);
+    /// assert_eq!(odds.to_string(), "1 in 50063860");
+    /// assert_eq!(odds.with_locale(Locale::English).to_string(), "1 in 50,063,860");
+    /// assert_eq!(odds.with_locale(Locale::Portuguese).to_string(), "1 em 50.063.860");
+    /// ```
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+}
+
+impl fmt::Display for ProbabilityDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = self.probability;
+        let number = |text: String| match self.locale {
+            Some(locale) => locale.format_number(&text),
+            None => text,
+        };
+        let (one_in, never) = match self.locale {
+            Some(Locale::Portuguese) => ("1 em", "nunca"),
+            _ => ("1 in", "never"),
+        };
+        let text = match self.format {
+            ProbabilityFormat::Fraction => format!("{}/{}", p.numerator, p.denominator),
+            ProbabilityFormat::OneIn if p.is_zero() => never.to_string(),
+            ProbabilityFormat::OneIn => format!(
+                "{} {}",
+                one_in,
+                number(fixed_point(
+                    &p.denominator,
+                    &p.numerator,
+                    f.precision().unwrap_or(0)
+                ))
+            ),
+            ProbabilityFormat::Percentage => format!(
+                "{}%",
+                number(fixed_point(
+                    &(&p.numerator * &BigUint::from(100u32)),
+                    &p.denominator,
+                    f.precision().unwrap_or(2)
+                ))
+            ),
+            ProbabilityFormat::Scientific => number(scientific(
+                &p.numerator,
+                &p.denominator,
+                f.precision().unwrap_or(2),
+            )),
+        };
+        // pad_integral honors width and alignment without truncating to the precision
+        f.pad_integral(true, "", &text)
+    }
+}
+
+/// Divide, rounding half up.
+fn round_div(numerator: &BigUint, denominator: &BigUint) -> BigUint {
+    let two = BigUint::from(2u32);
+    &(&(numerator * &two) + denominator) / &(denominator * &two)
+}
+
+/// Format numerator / denominator with a fixed number of decimals.
+fn fixed_point(numerator: &BigUint, denominator: &BigUint, decimals: usize) -> String {
+    let scale = BigUint::from(10u32).pow(decimals as u32);
+    let digits = round_div(&(numerator * &scale), denominator).to_string();
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    format!("{}.{}", whole, fraction)
+}
+
+/// Format numerator / denominator in scientific notation, like `{:.Ne}` for f64.
+fn scientific(numerator: &BigUint, denominator: &BigUint, decimals: usize) -> String {
+    if numerator.is_zero() {
+        return format!("{:.*e}", decimals, 0.0);
+    }
+
+    let ten = BigUint::from(10u32);
+    let lower = ten.pow(decimals as u32);
+    let upper = &lower * &ten;
+
+    // The decimal lengths give the exponent up to one either way
+    let mut exponent = numerator.to_string().len() as i64 - denominator.to_string().len() as i64;
+    loop {
+        let shift = decimals as i64 - exponent;
+        let mantissa = if shift >= 0 {
+            round_div(&(numerator * &ten.pow(shift as u32)), denominator)
+        } else {
+            round_div(numerator, &(denominator * &ten.pow((-shift) as u32)))
+        };
+        if mantissa >= upper {
+            exponent += 1;
+        } else if mantissa < lower {
+            exponent -= 1;
+        } else {
+            let digits = mantissa.to_string();
+            let (first, rest) = digits.split_at(1);
+            return if rest.is_empty() {
+                format!("{}e{}", first, exponent)
+            } else {
+                format!("{}.{}e{}", first, rest, exponent)
+            };
+        }
+    }
+}
+
+/// Odds of matching exactly a given number of balls, together with the
+/// cumulative odds of matching at least that many.
+///
+/// Counts are exact big integers, so the table is available for every
+/// valid game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchOdds {
+    matches: usize,
+    favorable: BigUint,
+    at_least_favorable: BigUint,
+    total: BigUint,
+}
+
+impl MatchOdds {
+    /// Get the number of matched balls.
+    pub fn matches(&self) -> usize {
+        self.matches
+    }
+
+    /// Get the number of draws matching exactly `matches` balls.
+    pub fn favorable(&self) -> &BigUint {
+        &self.favorable
+    }
+
+    /// Get the number of draws matching at least `matches` balls.
+    pub fn at_least_favorable(&self) -> &BigUint {
+        &self.at_least_favorable
+    }
+
+    /// Get the total number of possible draws.
+    pub fn total(&self) -> &BigUint {
+        &self.total
+    }
+
+    /// Exact probability of matching exactly `matches` balls.
+    pub fn probability(&self) -> Probability {
+        Probability::reduced(self.favorable.clone(), self.total.clone())
+    }
+
+    /// Exact probability of matching at least `matches` balls.
+    pub fn at_least_probability(&self) -> Probability {
+        Probability::reduced(self.at_least_favorable.clone(), self.total.clone())
+    }
+
+    /// Odds of matching exactly `matches` balls, expressed as "1 in N".
+    ///
+    /// Returns infinity when the outcome is impossible.
+    pub fn one_in(&self) -> f64 {
+        self.total.to_f64() / self.favorable.to_f64()
+    }
+
+    /// Probability of matching exactly `matches` balls, as a percentage.
+    pub fn percentage(&self) -> f64 {
+        self.favorable.to_f64() / self.total.to_f64() * 100.0
+    }
+
+    /// Odds of matching at least `matches` balls, expressed as "1 in N".
+    pub fn at_least_one_in(&self) -> f64 {
+        self.total.to_f64() / self.at_least_favorable.to_f64()
+    }
+
+    /// Probability of matching at least `matches` balls, as a percentage.
+    pub fn at_least_percentage(&self) -> f64 {
+        self.at_least_favorable.to_f64() / self.total.to_f64() * 100.0
+    }
+}
+
+/// Calculate the full match distribution for 0..=pick_count matched balls.
+///
+/// This is the hypergeometric distribution of the number of picked balls
+/// that appear in the draw. The favorable counts of all rows add up to the
+/// total number of draws.
+///
+/// # Arguments
+///
+/// * `total_balls` - Total number of balls in the lottery
+/// * `pick_count` - Number of balls picked per game
+///
+/// # Returns
+///
+/// One MatchOdds per match count, in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::match_distribution;
+///
+/// let table = match_distribution(60, 6).unwrap();
+/// assert_eq!(table.len(), 7);
+/// assert_eq!(table[6].favorable().to_u128(), Some(1));
+/// assert_eq!(table[0].at_least_favorable(), table[0].total());
+/// ```
+pub fn match_distribution(total_balls: usize, pick_count: usize) -> Result<Vec<MatchOdds>> {
+    match_distribution_for_bet(total_balls, pick_count, pick_count)
+}
+
+/// Calculate the probability that a bet of `bet_size` balls covers exactly
+/// `match_count` of the `draw_size` drawn balls.
+///
+/// With `bet_size == draw_size` this is [`calculate_probability_big`]. Larger
+/// bets are multi-number bets, which mark more balls than are drawn.
+///
+/// # Arguments
+///
+/// * `total_balls` - Total number of balls in the lottery
+/// * `bet_size` - Number of balls marked on the bet
+/// * `draw_size` - Number of balls drawn
+/// * `match_count` - Number of drawn balls covered by the bet
+///
+/// # Returns
+///
+/// A tuple of (favorable_outcomes, total_outcomes), or an error if
+/// `match_count` exceeds `draw_size`.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::calculate_probability_for_bet;
+///
+/// // A 7-number Mega-Sena bet covers all 6 drawn balls in 7 of C(60, 6) draws
+/// let (favorable, total) = calculate_probability_for_bet(60, 7, 6, 6).unwrap();
+/// assert_eq!(favorable.to_u128(), Some(7));
+/// assert_eq!(total.to_u128(), Some(50_063_860));
+/// ```
+pub fn calculate_probability_for_bet(
+    total_balls: usize,
+    bet_size: usize,
+    draw_size: usize,
+    match_count: usize,
+) -> Result<(BigUint, BigUint)> {
+    if match_count > draw_size {
+        return Err(LottoError::InvalidMatchCount {
+            match_count,
+            pick_count: draw_size,
+        });
+    }
+
+    let total_outcomes = combination_big(total_balls, draw_size);
+    let ways_to_match = combination_big(bet_size, match_count);
+    let ways_to_miss = combination_big(
+        total_balls.saturating_sub(bet_size),
+        draw_size - match_count,
+    );
+
+    Ok((&ways_to_match * &ways_to_miss, total_outcomes))
+}
+
+/// Calculate the match distribution of a bet of `bet_size` balls against a
+/// draw of `draw_size` balls, for 0..=draw_size covered balls.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::probability::match_distribution_for_bet;
+///
+/// // 15-number Mega-Sena bet
+/// let table = match_distribution_for_bet(60, 15, 6).unwrap();
+/// assert_eq!(table.len(), 7);
+/// assert_eq!(table[6].favorable().to_u128(), Some(5_005));
+/// ```
+pub fn match_distribution_for_bet(
+    total_balls: usize,
+    bet_size: usize,
+    draw_size: usize,
+) -> Result<Vec<MatchOdds>> {
+    let mut rows = Vec::with_capacity(draw_size + 1);
+    for match_count in 0..=draw_size {
+        let (favorable, total) =
+            calculate_probability_for_bet(total_balls, bet_size, draw_size, match_count)?;
+        rows.push(MatchOdds {
+            matches: match_count,
+            favorable,
+            at_least_favorable: BigUint::zero(),
+            total,
+        });
+    }
+
+    // Accumulate "at least k" counts from the highest match count down
+    let mut at_least = BigUint::zero();
+    for row in rows.iter_mut().rev() {
+        at_least = &at_least + &row.favorable;
+        row.at_least_favorable = at_least.clone();
+    }
+
+    Ok(rows)
+}
+
+/// Calculate the full match distribution using Config object.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::{Config, probability::match_distribution_for_config};
+///
+/// let config = Config::new(1, 1, 60, 6).unwrap();
+/// let table = match_distribution_for_config(&config).unwrap();
+/// assert_eq!(table[5].favorable().to_u128(), Some(324)); // Quina
+/// ```
+pub fn match_distribution_for_config(config: &crate::Config) -> Result<Vec<MatchOdds>> {
+    match_distribution(config.range().size(), config.pick().value())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +897,240 @@ mod tests {
         let result = calculate_probability_for_config(&config, 7);
         assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
     }
+
+    #[test]
+    fn test_match_distribution_sums_to_total() {
+        let table = match_distribution(60, 6).unwrap();
+        let sum = table
+            .iter()
+            .fold(BigUint::zero(), |sum, row| &sum + row.favorable());
+        assert_eq!(sum.to_u128(), Some(50_063_860));
+        assert_eq!(table[0].at_least_favorable().to_u128(), Some(50_063_860));
+    }
+
+    #[test]
+    fn test_match_distribution_mega_sena_tiers() {
+        let table = match_distribution(60, 6).unwrap();
+        assert_eq!(table[6].favorable().to_u128(), Some(1));
+        assert_eq!(table[5].favorable().to_u128(), Some(324));
+        assert_eq!(table[4].favorable().to_u128(), Some(21_465));
+        assert_eq!(table[4].at_least_favorable().to_u128(), Some(21_790));
+    }
+
+    #[test]
+    fn test_match_distribution_percentages() {
+        let table = match_distribution(10, 3).unwrap();
+        assert!((table[2].percentage() - 17.5).abs() < 1e-9); // 21/120
+        assert!((table[2].at_least_percentage() - 55.0 / 3.0).abs() < 1e-9); // 22/120
+        assert!((table[3].one_in() - 120.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_match_distribution_impossible_row() {
+        // Picking 8 of 10 always matches at least 6 of the 8 drawn balls
+        let table = match_distribution(10, 8).unwrap();
+        assert!(table[0].favorable().is_zero());
+        assert!(table[0].one_in().is_infinite());
+        assert_eq!(table[6].at_least_favorable(), table[6].total());
+    }
+
+    #[test]
+    fn test_combination_intermediate_overflow_falls_back() {
+        // C(128, 64) fits in u128, but the running product overflows
+        let exact = combination_big(128, 64);
+        assert_eq!(combination(128, 64).unwrap(), exact.to_u128().unwrap());
+    }
+
+    #[test]
+    fn test_combination_beyond_u128() {
+        let result = combination(255, 127);
+        assert!(matches!(
+            result,
+            Err(LottoError::CalculationOverflow { .. })
+        ));
+        assert!(combination_big(255, 127).to_u128().is_none());
+    }
+
+    #[test]
+    fn test_combination_big_matches_combination() {
+        for (n, k) in [(0, 0), (10, 11), (60, 6), (100, 50), (25, 15)] {
+            assert_eq!(
+                combination_big(n, k).to_u128(),
+                Some(combination(n, k).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_calculate_probability_big_full_range() {
+        let (favorable, total) = calculate_probability_big(256, 128, 128).unwrap();
+        assert_eq!(favorable, BigUint::one());
+        assert_eq!(total, combination_big(256, 128));
+    }
+
+    #[test]
+    fn test_calculate_probability_big_invalid_match_count() {
+        let result = calculate_probability_big(60, 6, 7);
+        assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
+    }
+
+    #[test]
+    fn test_match_distribution_beyond_u128() {
+        let table = match_distribution(255, 127).unwrap();
+        let sum = table
+            .iter()
+            .fold(BigUint::zero(), |sum, row| &sum + row.favorable());
+        assert_eq!(&sum, table[0].total());
+    }
+
+    #[test]
+    fn test_probability_reduces() {
+        let p = Probability::new(324u32, 50_063_860u32).unwrap();
+        assert_eq!(p.numerator(), &BigUint::from(81u32));
+        assert_eq!(p.denominator(), &BigUint::from(12_515_965u32));
+        assert_eq!(p, Probability::new(81u32, 12_515_965u32).unwrap());
+    }
+
+    #[test]
+    fn test_probability_invalid() {
+        assert!(matches!(
+            Probability::new(1u32, 0u32),
+            Err(LottoError::InvalidProbability { .. })
+        ));
+        assert!(matches!(
+            Probability::new(3u32, 2u32),
+            Err(LottoError::InvalidProbability { .. })
+        ));
+    }
+
+    #[test]
+    fn test_probability_ordering_and_arithmetic() {
+        let third = Probability::new(1u32, 3u32).unwrap();
+        let half = Probability::new(1u32, 2u32).unwrap();
+        assert!(third < half);
+        assert_eq!(&third * &half, Probability::new(1u32, 6u32).unwrap());
+        assert_eq!(half.complement(), half);
+        assert_eq!(Probability::zero().complement(), Probability::one());
+    }
+
+    #[test]
+    fn test_probability_one_in_rounds() {
+        // 1 in 154518.09 rounds down, 1 in 1.5 rounds up
+        let quina = Probability::new(324u32, 50_063_860u32).unwrap();
+        assert_eq!(quina.one_in(), Some(BigUint::from(154_518u32)));
+        let two_thirds = Probability::new(2u32, 3u32).unwrap();
+        assert_eq!(two_thirds.one_in(), Some(BigUint::from(2u32)));
+        assert_eq!(Probability::zero().one_in(), None);
+        assert_eq!(
+            Probability::zero()
+                .display(ProbabilityFormat::OneIn)
+                .to_string(),
+            "never"
+        );
+    }
+
+    #[test]
+    fn test_probability_percentage_format() {
+        let p = Probability::new(1u32, 3u32).unwrap();
+        assert_eq!(
+            p.display(ProbabilityFormat::Percentage).to_string(),
+            "33.33%"
+        );
+        assert_eq!(
+            format!("{:.0}", p.display(ProbabilityFormat::Percentage)),
+            "33%"
+        );
+        let tiny = Probability::new(1u32, 50_063_860u32).unwrap();
+        assert_eq!(
+            format!("{:.8}", tiny.display(ProbabilityFormat::Percentage)),
+            "0.00000200%"
+        );
+        assert_eq!(
+            format!(
+                "{:>8}",
+                Probability::one().display(ProbabilityFormat::Percentage)
+            ),
+            " 100.00%"
+        );
+    }
+
+    #[test]
+    fn test_probability_scientific_format() {
+        let mega = Probability::new(1u32, 50_063_860u32).unwrap();
+        assert_eq!(
+            mega.display(ProbabilityFormat::Scientific).to_string(),
+            "2.00e-8"
+        );
+        // Rounding carries into the exponent
+        let almost = Probability::new(9_999u32, 10_000u32).unwrap();
+        assert_eq!(
+            almost.display(ProbabilityFormat::Scientific).to_string(),
+            "1.00e0"
+        );
+        assert_eq!(
+            format!(
+                "{:.0}",
+                Probability::one().display(ProbabilityFormat::Scientific)
+            ),
+            "1e0"
+        );
+        assert_eq!(
+            Probability::zero()
+                .display(ProbabilityFormat::Scientific)
+                .to_string(),
+            "0.00e0"
+        );
+    }
+
+    #[test]
+    fn test_probability_beyond_f64() {
+        // Both counts overflow f64, the ratio does not
+        let two = BigUint::from(2u32);
+        let numerator = &two.pow(1100) - &BigUint::one();
+        let denominator = &two.pow(1101) - &BigUint::one();
+        let p = Probability::new(numerator, denominator).unwrap();
+        assert!(p.numerator().to_f64().is_infinite());
+        assert!((p.to_f64() - 0.5).abs() < 1e-12);
+
+        let (favorable, total) = calculate_probability_big(256, 128, 128).unwrap();
+        let p = Probability::new(favorable, total).unwrap();
+        assert!(p.to_f64() > 0.0);
+        assert!(
+            p.display(ProbabilityFormat::Scientific)
+                .to_string()
+                .ends_with("e-76")
+        );
+    }
+
+    #[test]
+    fn test_match_odds_probability() {
+        let table = match_distribution(60, 6).unwrap();
+        assert_eq!(table[5].probability().to_string(), "81/12515965");
+        assert_eq!(table[0].at_least_probability(), Probability::one());
+    }
+
+    #[test]
+    fn test_bet_distribution_matches_simple_bet() {
+        assert_eq!(
+            match_distribution_for_bet(60, 6, 6).unwrap(),
+            match_distribution(60, 6).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bet_distribution_mega_sena_15() {
+        let table = match_distribution_for_bet(60, 15, 6).unwrap();
+        // C(15, 5) * C(45, 1) draws leave exactly one drawn ball uncovered
+        assert_eq!(table[5].favorable().to_u128(), Some(3_003 * 45));
+        let sum = table
+            .iter()
+            .fold(BigUint::zero(), |sum, row| &sum + row.favorable());
+        assert_eq!(&sum, table[0].total());
+    }
+
+    #[test]
+    fn test_bet_probability_invalid_match_count() {
+        let result = calculate_probability_for_bet(60, 10, 6, 7);
+        assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
+    }
 }
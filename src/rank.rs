@@ -0,0 +1,272 @@
+//! Combinatorial ranking of tickets.
+//!
+//! Every ticket of a game maps to a unique number between zero and the
+//! number of combinations, so a ticket can be stored or shared as a single
+//! integer. Ranks follow the colexicographic order of the balls' offsets
+//! from the start of the range: the ticket of the lowest balls is rank 0
+//! and adding the highest ball to the range only appends new ranks.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+use crate::probability::combination_big;
+
+/// Get the colexicographic rank of a ticket within a range.
+///
+/// # Arguments
+///
+/// * `ticket` - The ticket to rank
+/// * `range` - The range the ticket's balls are drawn from
+///
+/// # Returns
+///
+/// The sum of C(offset, i + 1) over the ticket's sorted ball offsets.
+///
+/// # Errors
+///
+/// Returns `BallOutOfRange` if a ball falls outside the range.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::rank::rank;
+/// use lotto_quick_pick::{BallNumber, BallRange, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let first = Ticket::new((1..=6).map(BallNumber::new).collect());
+/// let last = Ticket::new((55..=60).map(BallNumber::new).collect());
+///
+/// assert_eq!(rank(&first, &range).unwrap().to_u128(), Some(0));
+/// assert_eq!(rank(&last, &range).unwrap().to_u128(), Some(50_063_859));
+/// ```
+pub fn rank(ticket: &Ticket, range: &BallRange) -> Result<BigUint> {
+    let start = range.start().value();
+    let mut rank = BigUint::zero();
+    for (i, ball) in ticket.balls().iter().enumerate() {
+        if !range.contains(*ball) {
+            return Err(LottoError::BallOutOfRange {
+                value: ball.value(),
+                start,
+                end: range.end().value(),
+            });
+        }
+        rank = &rank + &combination_big(usize::from(ball.value() - start), i + 1);
+    }
+    Ok(rank)
+}
+
+/// Get the ticket with the given colexicographic rank.
+///
+/// This is the inverse of [`rank`].
+///
+/// # Arguments
+///
+/// * `rank` - The rank of the ticket, below C(range size, pick)
+/// * `range` - The range the ticket's balls are drawn from
+/// * `pick` - Number of balls in the ticket
+///
+/// # Errors
+///
+/// Returns `RankOutOfRange` if the rank is not below the number of
+/// combinations.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::rank::{rank, unrank};
+/// use lotto_quick_pick::{BallRange, BigUint, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let ticket = unrank(&BigUint::from(123_456u32), &range, &pick).unwrap();
+///
+/// assert_eq!(rank(&ticket, &range).unwrap(), BigUint::from(123_456u32));
+/// ```
+pub fn unrank(rank: &BigUint, range: &BallRange, pick: &PickCount) -> Result<Ticket> {
+    let total = combination_big(range.size(), pick.value());
+    if *rank >= total {
+        return Err(LottoError::RankOutOfRange {
+            rank: rank.to_string(),
+            total: total.to_string(),
+        });
+    }
+
+    let start = range.start().value();
+    let mut remaining = rank.clone();
+    let mut balls = Vec::with_capacity(pick.value());
+    let mut offset = range.size();
+    for i in (1..=pick.value()).rev() {
+        // Largest offset whose C(offset, i) still fits in the remaining rank
+        let mut count;
+        loop {
+            offset -= 1;
+            count = combination_big(offset, i);
+            if count <= remaining {
+                break;
+            }
+        }
+        remaining = &remaining - &count;
+        balls.push(BallNumber::new(start + offset as u8));
+    }
+    balls.reverse();
+    Ok(Ticket::from_sorted(balls))
+}
+
+/// Iterator over the tickets of a game in rank order.
+///
+/// Created by [`combinations`]. Each step moves to the next ticket in
+/// colexicographic order without recomputing ranks, so listing a stretch of
+/// consecutive tickets costs one [`unrank`] and then constant work per ball.
+#[derive(Debug, Clone)]
+pub struct Combinations {
+    range: BallRange,
+    offsets: Option<Vec<usize>>,
+}
+
+/// List the tickets of a game in rank order, starting at a given rank.
+///
+/// # Arguments
+///
+/// * `range` - The range the tickets' balls are drawn from
+/// * `pick` - Number of balls in each ticket
+/// * `from` - Rank of the first ticket listed
+///
+/// # Errors
+///
+/// Returns `RankOutOfRange` if `from` is not below the number of
+/// combinations.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::rank::combinations;
+/// use lotto_quick_pick::{BallNumber, BallRange, BigUint, PickCount};
+///
+/// let range = BallRange::new(BallNumber::new(1), BallNumber::new(4)).unwrap();
+/// let pick = PickCount::new(2, &range).unwrap();
+/// let tickets: Vec<String> = combinations(&range, &pick, &BigUint::from(2u32))
+///     .unwrap()
+///     .map(|ticket| ticket.to_string())
+///     .collect();
+///
+/// assert_eq!(tickets, ["02 03", "01 04", "02 04", "03 04"]);
+/// ```
+pub fn combinations(range: &BallRange, pick: &PickCount, from: &BigUint) -> Result<Combinations> {
+    let start = range.start().value();
+    let first = unrank(from, range, pick)?;
+    let offsets = first
+        .balls()
+        .iter()
+        .map(|ball| usize::from(ball.value() - start))
+        .collect();
+    Ok(Combinations {
+        range: *range,
+        offsets: Some(offsets),
+    })
+}
+
+impl Iterator for Combinations {
+    type Item = Ticket;
+
+    fn next(&mut self) -> Option<Ticket> {
+        let offsets = self.offsets.as_mut()?;
+        let start = self.range.start().value();
+        let ticket = Ticket::from_sorted(
+            offsets
+                .iter()
+                .map(|&offset| BallNumber::new(start + offset as u8))
+                .collect(),
+        );
+
+        // Advance the lowest offset that has room, resetting those below it
+        let size = self.range.size();
+        let pick = offsets.len();
+        match (0..pick).find(|&i| offsets[i] + 1 < offsets.get(i + 1).copied().unwrap_or(size)) {
+            Some(i) => {
+                offsets[i] += 1;
+                for (j, offset) in offsets[..i].iter_mut().enumerate() {
+                    *offset = j;
+                }
+            }
+            None => self.offsets = None,
+        }
+        Some(ticket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(balls: &[u8]) -> Ticket {
+        Ticket::new(balls.iter().copied().map(BallNumber::new).collect())
+    }
+
+    fn range(start: u8, end: u8) -> BallRange {
+        BallRange::new(BallNumber::new(start), BallNumber::new(end)).unwrap()
+    }
+
+    #[test]
+    fn test_rank_enumerates_colexicographic_order() {
+        let range = range(1, 5);
+        let ranks: Vec<u128> = [[1, 2], [1, 3], [2, 3], [1, 4], [2, 4], [3, 4], [1, 5]]
+            .iter()
+            .map(|balls| rank(&ticket(balls), &range).unwrap().to_u128().unwrap())
+            .collect();
+
+        assert_eq!(ranks, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_rank_rejects_balls_outside_range() {
+        assert!(rank(&ticket(&[1, 61]), &BallRange::mega_sena()).is_err());
+    }
+
+    #[test]
+    fn test_unrank_round_trips_every_ticket() {
+        let range = range(10, 19);
+        let pick = PickCount::new(4, &range).unwrap();
+        for value in 0..210u32 {
+            let ticket = unrank(&BigUint::from(value), &range, &pick).unwrap();
+            assert_eq!(ticket.len(), 4);
+            assert_eq!(rank(&ticket, &range).unwrap(), BigUint::from(value));
+        }
+    }
+
+    #[test]
+    fn test_unrank_rejects_rank_past_last_ticket() {
+        let range = range(10, 19);
+        let pick = PickCount::new(4, &range).unwrap();
+
+        assert!(matches!(
+            unrank(&BigUint::from(210u32), &range, &pick),
+            Err(LottoError::RankOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_combinations_follow_rank_order_to_the_end() {
+        let range = range(10, 19);
+        let pick = PickCount::new(4, &range).unwrap();
+        let tickets: Vec<Ticket> = combinations(&range, &pick, &BigUint::from(5u32))
+            .unwrap()
+            .collect();
+
+        assert_eq!(tickets.len(), 205);
+        for (value, ticket) in (5..210u32).zip(&tickets) {
+            assert_eq!(rank(ticket, &range).unwrap(), BigUint::from(value));
+        }
+        assert!(combinations(&range, &pick, &BigUint::from(210u32)).is_err());
+    }
+
+    #[test]
+    fn test_rank_beyond_u128() {
+        let range = range(0, 255);
+        let pick = PickCount::new(100, &range).unwrap();
+        let last = ticket(&(156..=255).collect::<Vec<u8>>());
+        let last_rank = rank(&last, &range).unwrap();
+
+        assert_eq!(&last_rank + &BigUint::one(), combination_big(256, 100));
+        assert_eq!(unrank(&last_rank, &range, &pick).unwrap(), last);
+    }
+}
@@ -0,0 +1,318 @@
+//! Serde support behind the `serde` feature.
+//!
+//! Covered are [`Config`] (with its [`PatternFilter`]), [`Ticket`],
+//! [`TicketKey`], [`BallRange`], [`BallNumber`], [`PickCount`],
+//! [`GameCount`] and [`LottoError`](crate::LottoError); results such as
+//! probabilities, check summaries and prize tables are not serializable.
+//!
+//! Types are serialized in their natural shape: balls as numbers, tickets
+//! as arrays of balls, ranges and configurations as objects. Deserialization
+//! always goes through the validating constructors, so data that the API
+//! would reject (a reversed range, a duplicate ball, zero games...) cannot
+//! be built from a serialized form either.
+//!
+//! A ticket or pick count on its own carries no game, so it is validated
+//! against the widest range, 0-255. A ticket key carries no range either:
+//! each width stands for the widest range it can hold, where every bit is a
+//! ball, so a key is only checked to hold at least one ball.
+
+use crate::betslip::PatternFilter;
+use crate::newtypes::{BallNumber, BallRange, GameCount, PickCount, Ticket};
+use crate::ticket_key::TicketKey;
+use crate::{Config, ConfigBuilder, OutputOrder};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Widest range a ball can belong to.
+fn full_range() -> BallRange {
+    BallRange::new(BallNumber::new(0), BallNumber::new(255)).expect("0 is less than 255")
+}
+
+impl Serialize for BallNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for BallNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(BallNumber::new)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawBallRange {
+    start: u8,
+    end: u8,
+}
+
+impl Serialize for BallRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawBallRange {
+            start: self.start().value(),
+            end: self.end().value(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BallRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawBallRange::deserialize(deserializer)?;
+        BallRange::new(BallNumber::new(raw.start), BallNumber::new(raw.end))
+            .map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for PickCount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.value() as u64)
+    }
+}
+
+impl<'de> Deserialize<'de> for PickCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = usize::deserialize(deserializer)?;
+        PickCount::new(value, &full_range()).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for GameCount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.value() as u64)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = usize::deserialize(deserializer)?;
+        GameCount::new(value).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for Ticket {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.balls().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ticket {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let balls = Vec::<BallNumber>::deserialize(deserializer)?;
+        let range = full_range();
+        let pick = PickCount::new(balls.len(), &range).map_err(D::Error::custom)?;
+        Ticket::try_new(balls, &range, &pick).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum RawTicketKey {
+    U64(u64),
+    U128(u128),
+    U256([u64; 4]),
+}
+
+impl Serialize for TicketKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TicketKey::U64(bitmap) => RawTicketKey::U64(*bitmap),
+            TicketKey::U128(bitmap) => RawTicketKey::U128(*bitmap),
+            TicketKey::U256(bitmap) => RawTicketKey::U256(*bitmap),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TicketKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = match RawTicketKey::deserialize(deserializer)? {
+            RawTicketKey::U64(bitmap) => TicketKey::U64(bitmap),
+            RawTicketKey::U128(bitmap) => TicketKey::U128(bitmap),
+            RawTicketKey::U256(bitmap) => TicketKey::U256(bitmap),
+        };
+        if key.is_empty() {
+            return Err(D::Error::custom("ticket key has no balls"));
+        }
+        Ok(key)
+    }
+}
+
+/// Options left at their defaults are omitted.
+#[derive(Serialize, Deserialize)]
+struct RawConfig {
+    game_count: usize,
+    range: RawBallRange,
+    pick: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    draw_size: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    order: Option<String>,
+    #[serde(default, skip_serializing_if = "PatternFilter::is_empty")]
+    filter: PatternFilter,
+}
+
+impl Serialize for Config {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawConfig {
+            game_count: self.game_count().value(),
+            range: RawBallRange {
+                start: self.range().start().value(),
+                end: self.range().end().value(),
+            },
+            pick: self.pick().value(),
+            draw_size: Some(self.draw_size().value()).filter(|&draw| draw != self.pick().value()),
+            seed: self.seed(),
+            unique: Some(false).filter(|_| !self.is_unique()),
+            order: Some(self.order())
+                .filter(|&order| order != OutputOrder::Generation)
+                .map(|order| order.to_string()),
+            filter: *self.filter(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawConfig::deserialize(deserializer)?;
+        let mut builder = ConfigBuilder::new(full_range(), raw.pick)
+            .with_games(raw.game_count)
+            .with_range(raw.range.start, raw.range.end)
+            .with_unique(raw.unique.unwrap_or(true))
+            .with_filter(raw.filter)
+            .strict();
+        if let Some(draw) = raw.draw_size {
+            builder = builder.with_draw_size(draw);
+        }
+        if let Some(seed) = raw.seed {
+            builder = builder.with_seed(seed);
+        }
+        if let Some(order) = raw.order {
+            builder = builder.with_order(order.parse().map_err(D::Error::custom)?);
+        }
+        builder.build().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LottoError;
+
+    #[test]
+    fn test_config_round_trip() {
+        let config = Config::new(10, 1, 60, 6).unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"game_count":10,"range":{"start":1,"end":60},"pick":6}"#
+        );
+        let back: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.game_count(), config.game_count());
+        assert_eq!(back.range(), config.range());
+        assert_eq!(back.pick(), config.pick());
+    }
+
+    #[test]
+    fn test_builder_options_round_trip() {
+        let config = ConfigBuilder::from_preset(crate::GamePreset::MegaSena)
+            .with_pick(8)
+            .with_seed(42)
+            .with_order(OutputOrder::Rank)
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"game_count":1,"range":{"start":1,"end":60},"pick":8,"draw_size":6,"seed":42,"order":"rank"}"#
+        );
+        let back: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.draw_size().value(), 6);
+        assert_eq!(back.seed(), Some(42));
+        assert_eq!(back.order(), OutputOrder::Rank);
+        assert!(back.is_unique());
+    }
+
+    #[test]
+    fn test_filter_round_trip() {
+        let filter = PatternFilter::new()
+            .with_max_per_row(2)
+            .with_no_straight_line(3);
+        let config = ConfigBuilder::from_preset(crate::GamePreset::MegaSena)
+            .with_filter(filter)
+            .build()
+            .unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"game_count":1,"range":{"start":1,"end":60},"pick":6,"filter":{"max_per_row":2,"max_line_length":2}}"#
+        );
+        let back: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(*back.filter(), filter);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let zero_games = r#"{"game_count":0,"range":{"start":1,"end":60},"pick":6}"#;
+        let pick_too_big = r#"{"game_count":1,"range":{"start":1,"end":10},"pick":11}"#;
+
+        assert!(serde_json::from_str::<Config>(zero_games).is_err());
+        assert!(serde_json::from_str::<Config>(pick_too_big).is_err());
+    }
+
+    #[test]
+    fn test_reversed_range_is_rejected() {
+        let config = r#"{"game_count":1,"range":{"start":60,"end":1},"pick":6}"#;
+        let range = r#"{"start":60,"end":1}"#;
+
+        assert!(serde_json::from_str::<Config>(config).is_err());
+        assert!(serde_json::from_str::<BallRange>(range).is_err());
+    }
+
+    #[test]
+    fn test_range_and_counts_are_validated() {
+        assert!(serde_json::from_str::<BallRange>(r#"{"start":5,"end":5}"#).is_err());
+        assert!(serde_json::from_str::<GameCount>("0").is_err());
+        assert!(serde_json::from_str::<PickCount>("0").is_err());
+        assert_eq!(serde_json::from_str::<PickCount>("6").unwrap().value(), 6);
+    }
+
+    #[test]
+    fn test_ticket_is_sorted_and_rejects_duplicates() {
+        let ticket: Ticket = serde_json::from_str("[42, 4, 15]").unwrap();
+
+        assert_eq!(serde_json::to_string(&ticket).unwrap(), "[4,15,42]");
+        assert!(serde_json::from_str::<Ticket>("[4, 4, 15]").is_err());
+        assert!(serde_json::from_str::<Ticket>("[]").is_err());
+    }
+
+    #[test]
+    fn test_ticket_key_round_trip() {
+        let range = BallRange::new(BallNumber::new(0), BallNumber::new(199)).unwrap();
+        let key = TicketKey::from_balls(&[BallNumber::new(3), BallNumber::new(150)], &range);
+        let json = serde_json::to_string(&key).unwrap();
+
+        assert_eq!(serde_json::from_str::<TicketKey>(&json).unwrap(), key);
+        assert!(serde_json::from_str::<TicketKey>(r#"{"U64":0}"#).is_err());
+        assert!(serde_json::from_str::<TicketKey>(r#"{"U128":0}"#).is_err());
+    }
+
+    #[test]
+    fn test_error_round_trip() {
+        let error = LottoError::BallOutOfRange {
+            value: 61,
+            start: 1,
+            end: 60,
+        };
+        let json = serde_json::to_string(&error).unwrap();
+
+        assert_eq!(serde_json::from_str::<LottoError>(&json).unwrap(), error);
+    }
+}
@@ -0,0 +1,723 @@
+//! Local HTTP server exposing generation, odds and checking as a JSON API.
+//!
+//! Every endpoint but `/health` takes a JSON object as its `POST` body and
+//! answers with a JSON document. The game is given by the `start`, `end`
+//! and `pick` members and validated with [`Config::new`]; results and
+//! played tickets are validated with [`Ticket::try_new`].
+//!
+//! | Endpoint         | Members besides the game                                     |
+//! |------------------|--------------------------------------------------------------|
+//! | `POST /generate` | `tickets`, `seed`, `order`, `ranks`, `codes`, `matched`      |
+//! | `POST /odds`     | `draw_size`                                                  |
+//! | `POST /check`    | `draw_size`, `result`, `tickets` (an array of ball arrays)   |
+//! | `GET /health`    | none                                                         |
+//!
+//! `/generate` answers with the same document as `--format json`, and a
+//! `seed` reproduces the tickets the command line generates from it.
+//! Failures are answered with the status from [`status_code`] and a body of
+//! `{"error": ...}`, the error as rendered by [`LottoError::to_json`].
+//!
+//! The server speaks just enough HTTP/1.1 for local clients: one request
+//! per connection, with the body sized by `Content-Length`.
+
+use crate::Config;
+use crate::bigint::BigUint;
+use crate::check::{CheckSummary, check_tickets};
+use crate::error::{ErrorCategory, LottoError, Result};
+use crate::json::Json;
+use crate::newtypes::{BallNumber, PickCount, Ticket};
+use crate::output::{Report, odds_record};
+use crate::probability::match_distribution_for_bet;
+use crate::ticket::{OutputOrder, generate_unique_tickets_ordered};
+use rand::{SeedableRng, rngs::StdRng};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+/// Largest request body accepted, in bytes.
+const MAX_BODY: usize = 1 << 20;
+
+/// Largest request line or header line accepted, in bytes.
+const MAX_LINE: usize = 8 << 10;
+
+/// Most tickets `/generate` returns in one response.
+pub const MAX_TICKETS: usize = 100_000;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An HTTP response: a status code and a JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    status: u16,
+    body: String,
+    allow: Option<&'static str>,
+}
+
+impl Response {
+    /// A successful response.
+    fn ok(body: String) -> Self {
+        Self {
+            status: 200,
+            body,
+            allow: None,
+        }
+    }
+
+    /// A failure that is not a [`LottoError`], such as an unknown path.
+    fn message(status: u16, message: String) -> Self {
+        let error = Json::object([("message", Json::from(message))]);
+        Self {
+            status,
+            body: format!("{}\n", Json::object([("error", error)])),
+            allow: None,
+        }
+    }
+
+    /// The answer to a request with the wrong method.
+    fn method_not_allowed(allow: &'static str) -> Self {
+        Self {
+            allow: Some(allow),
+            ..Self::message(405, format!("use {}", allow))
+        }
+    }
+
+    /// A failed request, with the error's status and JSON rendering.
+    fn error(error: &LottoError) -> Self {
+        Self {
+            status: status_code(error),
+            body: format!("{}\n", Json::object([("error", error.to_json())])),
+            allow: None,
+        }
+    }
+
+    /// Get the HTTP status code.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Get the JSON body.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Write the response as HTTP/1.1, closing the connection after it.
+    fn write_to(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            self.status,
+            reason(self.status),
+            self.body.len()
+        )?;
+        if let Some(allow) = self.allow {
+            write!(out, "Allow: {}\r\n", allow)?;
+        }
+        write!(out, "Connection: close\r\n\r\n{}", self.body)?;
+        out.flush()
+    }
+}
+
+/// The HTTP status for an error.
+///
+/// A body that is not the JSON an endpoint expects is a `400 Bad Request`;
+/// a well-formed request the library rejects, such as an invalid game or
+/// an impossible number of unique tickets, is a `422 Unprocessable
+/// Content`; I/O failures are a `500 Internal Server Error`.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::LottoError;
+/// use lotto_quick_pick::server::status_code;
+///
+/// assert_eq!(status_code(&LottoError::ParseError("not JSON".to_string())), 400);
+/// assert_eq!(status_code(&LottoError::InvalidRange { start: 60, end: 1 }), 422);
+/// assert_eq!(status_code(&LottoError::IoError("disk full".to_string())), 500);
+/// ```
+pub fn status_code(error: &LottoError) -> u16 {
+    match (error, error.category()) {
+        (LottoError::ParseError(_), _) => 400,
+        (_, ErrorCategory::Io) => 500,
+        _ => 422,
+    }
+}
+
+/// Reason phrase of a status code.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        501 => "Not Implemented",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Answer one request.
+///
+/// This is the whole API without the network, which makes it easy to
+/// test or to embed in another server.
+///
+/// # Arguments
+///
+/// * `method` - The HTTP method, e.g. `POST`
+/// * `path` - The request target; a query string is ignored
+/// * `body` - The request body
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::server::handle;
+///
+/// let response = handle("POST", "/odds", r#"{"start": 1, "end": 10, "pick": 2}"#);
+/// assert_eq!(response.status(), 200);
+/// assert!(response.body().contains(r#""total":45"#));
+///
+/// let response = handle("POST", "/odds", r#"{"start": 1, "end": 10, "pick": 20}"#);
+/// assert_eq!(response.status(), 422);
+/// assert!(response.body().contains("PickExceedsRange"));
+/// ```
+pub fn handle(method: &str, path: &str, body: &str) -> Response {
+    let path = path.split('?').next().unwrap_or(path);
+    let endpoint: fn(&Json) -> Result<String> = match path {
+        "/generate" => generate,
+        "/odds" => odds,
+        "/check" => check,
+        "/health" if method == "GET" => {
+            return Response::ok(format!(
+                "{}\n",
+                Json::object([("status", Json::from("ok"))])
+            ));
+        }
+        "/health" => return Response::method_not_allowed("GET"),
+        _ => return Response::message(404, format!("no endpoint {}", path)),
+    };
+    if method != "POST" {
+        return Response::method_not_allowed("POST");
+    }
+
+    let result = Json::parse(body).and_then(|request| match request {
+        Json::Object(_) => endpoint(&request),
+        _ => Err(LottoError::ParseError(
+            "the request body must be a JSON object".to_string(),
+        )),
+    });
+    match result {
+        Ok(body) => Response::ok(body),
+        Err(e) => Response::error(&e),
+    }
+}
+
+/// Serve the API on a bound listener, one thread per connection.
+///
+/// Runs until the process ends. Failed connections are dropped without
+/// stopping the server.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::net::TcpListener;
+///
+/// let listener = TcpListener::bind("127.0.0.1:8080").unwrap();
+/// lotto_quick_pick::server::serve(listener);
+/// ```
+pub fn serve(listener: TcpListener) {
+    for stream in listener.incoming().flatten() {
+        std::thread::spawn(move || {
+            let _ = handle_connection(stream);
+        });
+    }
+}
+
+/// Read one request from the connection and write the answer.
+fn handle_connection(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let limit = (MAX_BODY + 64 * MAX_LINE) as u64;
+    let mut reader = BufReader::new((&stream).take(limit));
+    let response = match read_request(&mut reader) {
+        Ok((method, path, body)) => handle(&method, &path, &body),
+        Err(response) => response,
+    };
+    response.write_to(&mut stream)
+}
+
+/// Read the method, target and body of a request.
+fn read_request(
+    reader: &mut impl BufRead,
+) -> std::result::Result<(String, String, String), Response> {
+    let bad = |message: &str| Response::message(400, message.to_string());
+
+    let request_line = read_line(reader).ok_or_else(|| bad("malformed request line"))?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad("malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(bad("only HTTP/1.x is supported"));
+    }
+
+    let mut length = 0;
+    loop {
+        let line = read_line(reader).ok_or_else(|| bad("malformed header"))?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad("malformed header"))?;
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            length = value.parse().map_err(|_| bad("invalid Content-Length"))?;
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::message(
+                501,
+                "send the body with a Content-Length".to_string(),
+            ));
+        }
+    }
+    if length > MAX_BODY {
+        return Err(Response::message(
+            413,
+            format!("the body is limited to {} bytes", MAX_BODY),
+        ));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad("the body is shorter than its Content-Length"))?;
+    let body = String::from_utf8(body).map_err(|_| bad("the body is not UTF-8"))?;
+    Ok((method.to_string(), path.to_string(), body))
+}
+
+/// Read one CRLF-terminated line, or `None` if it is missing or too long.
+fn read_line(reader: &mut impl BufRead) -> Option<String> {
+    let mut line = String::new();
+    let read = reader.take(MAX_LINE as u64).read_line(&mut line).ok()?;
+    if read == 0 || !line.ends_with('\n') {
+        return None;
+    }
+    Some(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Read an optional non-negative integer member that fits `T`.
+fn optional<T: TryFrom<u64>>(request: &Json, key: &str) -> Result<Option<T>> {
+    match request.get(key) {
+        None | Some(Json::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|value| T::try_from(value).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                LottoError::ParseError(format!(
+                    "'{}' must be a non-negative integer in range, got {}",
+                    key, value
+                ))
+            }),
+    }
+}
+
+/// Read an integer member that must be present.
+fn required<T: TryFrom<u64>>(request: &Json, key: &str) -> Result<T> {
+    optional(request, key)?
+        .ok_or_else(|| LottoError::ParseError(format!("missing member '{}'", key)))
+}
+
+/// Read an optional boolean member, `false` when absent.
+fn flag(request: &Json, key: &str) -> Result<bool> {
+    match request.get(key) {
+        None | Some(Json::Null) => Ok(false),
+        Some(value) => value
+            .as_bool()
+            .ok_or_else(|| LottoError::ParseError(format!("'{}' must be true or false", key))),
+    }
+}
+
+/// Read an array of ball numbers.
+fn balls(value: &Json, name: &str) -> Result<Vec<BallNumber>> {
+    let not_balls =
+        || LottoError::ParseError(format!("'{}' must be an array of ball numbers", name));
+    value
+        .as_array()
+        .ok_or_else(not_balls)?
+        .iter()
+        .map(|ball| {
+            ball.as_u64()
+                .and_then(|ball| u8::try_from(ball).ok())
+                .map(BallNumber::new)
+                .ok_or_else(not_balls)
+        })
+        .collect()
+}
+
+/// Read and validate the game of a request.
+fn game(request: &Json, games: usize) -> Result<Config> {
+    Config::new(
+        games,
+        required(request, "start")?,
+        required(request, "end")?,
+        required(request, "pick")?,
+    )
+}
+
+/// Read the number of balls drawn, which defaults to the pick size.
+fn draw_size(request: &Json, config: &Config) -> Result<PickCount> {
+    let Some(draw_size) = optional(request, "draw_size")? else {
+        return Ok(*config.pick());
+    };
+    let draw = PickCount::new(draw_size, config.range())?;
+    if draw.value() > config.pick().value() {
+        return Err(LottoError::BetTooSmall {
+            bet_size: config.pick().value(),
+            draw_size: draw.value(),
+        });
+    }
+    Ok(draw)
+}
+
+/// The game members of a response, as in the reports.
+fn game_record(config: &Config, draw: PickCount) -> Json {
+    Json::object([
+        ("start", Json::from(config.range().start().value())),
+        ("end", Json::from(config.range().end().value())),
+        ("pick", Json::from(config.pick().value())),
+        ("draw_size", Json::from(draw.value())),
+    ])
+}
+
+/// `POST /generate`: unique tickets as a JSON report.
+fn generate(request: &Json) -> Result<String> {
+    let games = optional(request, "tickets")?.unwrap_or(1);
+    if games > MAX_TICKETS {
+        return Err(LottoError::ParseError(format!(
+            "at most {} tickets can be generated per request",
+            MAX_TICKETS
+        )));
+    }
+    let config = game(request, games)?;
+    let order = match request.get("order") {
+        None | Some(Json::Null) => OutputOrder::default(),
+        Some(order) => order
+            .as_str()
+            .ok_or_else(|| LottoError::ParseError("'order' must be a string".to_string()))?
+            .parse()?,
+    };
+
+    let seed = optional(request, "seed")?.unwrap_or_else(rand::random);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let tickets = generate_unique_tickets_ordered(
+        &mut rng,
+        config.range(),
+        config.pick(),
+        config.game_count(),
+        order,
+    )?;
+
+    let mut report = Report::new(&config, &tickets).with_seed(seed);
+    if flag(request, "ranks")? {
+        report = report.with_ranks();
+    }
+    if flag(request, "codes")? {
+        report = report.with_codes();
+    }
+    if let Some(matched) = optional(request, "matched")? {
+        report = report.with_matched(matched);
+    }
+    report.to_json()
+}
+
+/// `POST /odds`: the probability of every match count.
+fn odds(request: &Json) -> Result<String> {
+    let config = game(request, 1)?;
+    let draw = draw_size(request, &config)?;
+    let table =
+        match_distribution_for_bet(config.range().size(), config.pick().value(), draw.value())?;
+
+    let rows = table.iter().map(|row| Json::Object(odds_record(row)));
+    let response = Json::object([
+        ("game", game_record(&config, draw)),
+        ("table", Json::array(rows)),
+    ]);
+    Ok(format!("{}\n", response))
+}
+
+/// `POST /check`: the hits of each ticket against a result.
+///
+/// An invalid ticket is reported as `InvalidTicketLine`, with its
+/// 1-based position in `tickets` as the line.
+fn check(request: &Json) -> Result<String> {
+    let config = game(request, 1)?;
+    let draw = draw_size(request, &config)?;
+    let missing = |key: &str| LottoError::ParseError(format!("missing member '{}'", key));
+
+    let result = request.get("result").ok_or_else(|| missing("result"))?;
+    let result = Ticket::try_new(balls(result, "result")?, config.range(), &draw)?;
+    let tickets = request
+        .get("tickets")
+        .ok_or_else(|| missing("tickets"))?
+        .as_array()
+        .ok_or_else(|| LottoError::ParseError("'tickets' must be an array".to_string()))?
+        .iter()
+        .enumerate()
+        .map(|(i, ticket)| {
+            balls(ticket, "tickets")
+                .and_then(|balls| Ticket::try_new(balls, config.range(), config.pick()))
+                .map_err(|e| LottoError::InvalidTicketLine {
+                    line: i + 1,
+                    source: Box::new(e),
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if tickets.is_empty() {
+        return Err(LottoError::ZeroGames);
+    }
+
+    let checked = check_tickets(&tickets, &result);
+    let summary = CheckSummary::new(&checked, draw.value());
+    let ball_array =
+        |balls: &[BallNumber]| Json::array(balls.iter().map(|b| Json::from(b.value())));
+    // Simple bets per number of matches, highest first; a multi-number bet has several
+    let prizes = |bets: Vec<BigUint>| {
+        let tiers = bets
+            .into_iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, bets)| !bets.is_zero())
+            .map(|(matches, bets)| {
+                Json::object([
+                    ("matches", Json::from(matches)),
+                    ("bets", Json::from(&bets)),
+                ])
+            });
+        Json::array(tiers)
+    };
+    let records = checked.iter().enumerate().map(|(i, ticket)| {
+        Json::object([
+            ("index", Json::from(i + 1)),
+            ("balls", ball_array(ticket.ticket().balls())),
+            ("hits", ball_array(ticket.hits())),
+            ("matches", Json::from(ticket.matches())),
+            ("prizes", prizes(ticket.simple_bets(draw.value()))),
+        ])
+    });
+    let counts = (0..=draw.value())
+        .rev()
+        .filter(|&matches| summary.count(matches) > 0)
+        .map(|matches| {
+            Json::object([
+                ("matches", Json::from(matches)),
+                ("tickets", Json::from(summary.count(matches))),
+            ])
+        });
+
+    let response = Json::object([
+        ("game", game_record(&config, draw)),
+        ("result", ball_array(result.balls())),
+        ("tickets", Json::array(records)),
+        (
+            "summary",
+            Json::object([
+                ("tickets", Json::from(summary.tickets())),
+                ("best", Json::from(summary.best())),
+                ("counts", Json::array(counts)),
+                (
+                    "prizes",
+                    prizes(
+                        (0..=draw.value())
+                            .map(|matches| summary.simple_bets(matches))
+                            .collect(),
+                    ),
+                ),
+            ]),
+        ),
+    ]);
+    Ok(format!("{}\n", response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(response: &Response) -> Json {
+        Json::parse(response.body()).unwrap()
+    }
+
+    #[test]
+    fn test_generate_is_reproducible_from_seed() {
+        let request =
+            r#"{"start": 1, "end": 60, "pick": 6, "tickets": 3, "seed": 42, "order": "lex"}"#;
+        let first = handle("POST", "/generate", request);
+        assert_eq!(first.status(), 200);
+        assert_eq!(first, handle("POST", "/generate", request));
+
+        let body = parse(&first);
+        assert_eq!(body.get("seed").and_then(Json::as_u64), Some(42));
+        let tickets = body.get("tickets").and_then(Json::as_array).unwrap();
+        assert_eq!(tickets.len(), 3);
+        let balls: Vec<Vec<u64>> = tickets
+            .iter()
+            .map(|t| {
+                t.get("balls")
+                    .and_then(Json::as_array)
+                    .unwrap()
+                    .iter()
+                    .map(|b| b.as_u64().unwrap())
+                    .collect()
+            })
+            .collect();
+        assert!(balls.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(balls.iter().flatten().all(|&ball| (1..=60).contains(&ball)));
+    }
+
+    #[test]
+    fn test_errors_map_to_statuses() {
+        let status = |method, path, body| handle(method, path, body).status();
+        assert_eq!(status("POST", "/generate", "{"), 400);
+        assert_eq!(status("POST", "/generate", "[1]"), 400);
+        assert_eq!(
+            status("POST", "/generate", r#"{"start": 1, "end": 60}"#),
+            400
+        );
+        assert_eq!(
+            status(
+                "POST",
+                "/generate",
+                r#"{"start": 1, "end": 300, "pick": 6}"#
+            ),
+            400
+        );
+        assert_eq!(
+            status(
+                "POST",
+                "/generate",
+                r#"{"start": 1, "end": 5, "pick": 2, "tickets": 11}"#
+            ),
+            422
+        );
+        assert_eq!(status("GET", "/generate", ""), 405);
+        assert_eq!(status("POST", "/health", ""), 405);
+        assert_eq!(status("GET", "/health?verbose", ""), 200);
+        assert_eq!(status("GET", "/nowhere", ""), 404);
+
+        let response = handle("POST", "/odds", r#"{"start": 60, "end": 60, "pick": 1}"#);
+        let error = parse(&response);
+        let error = error.get("error").unwrap();
+        assert_eq!(response.status(), 422);
+        assert_eq!(
+            error.get("variant").and_then(Json::as_str),
+            Some("InvalidRange")
+        );
+        assert_eq!(
+            error.get("category").and_then(Json::as_str),
+            Some("invalid_config")
+        );
+    }
+
+    #[test]
+    fn test_odds_table() {
+        let response = handle(
+            "POST",
+            "/odds",
+            r#"{"start": 1, "end": 10, "pick": 3, "draw_size": 2}"#,
+        );
+        assert_eq!(response.status(), 200);
+        let body = parse(&response);
+        assert_eq!(
+            body.get("game").unwrap().to_string(),
+            r#"{"start":1,"end":10,"pick":3,"draw_size":2}"#
+        );
+        let table = body.get("table").and_then(Json::as_array).unwrap();
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[0].get("total").and_then(Json::as_u64), Some(45));
+    }
+
+    #[test]
+    fn test_check_counts_hits() {
+        let request = r#"{
+            "start": 1, "end": 25, "pick": 5,
+            "result": [1, 2, 3, 4, 5],
+            "tickets": [[1, 2, 3, 9, 10], [5, 4, 3, 2, 1], [20, 21, 22, 23, 24]]
+        }"#;
+        let response = handle("POST", "/check", request);
+        assert_eq!(response.status(), 200);
+        let body = parse(&response);
+        let tickets = body.get("tickets").and_then(Json::as_array).unwrap();
+        let matches: Vec<u64> = tickets
+            .iter()
+            .map(|t| t.get("matches").and_then(Json::as_u64).unwrap())
+            .collect();
+        assert_eq!(matches, [3, 5, 0]);
+        assert_eq!(tickets[0].get("hits").unwrap().to_string(), "[1,2,3]");
+        assert_eq!(
+            body.get("summary").unwrap().to_string(),
+            concat!(
+                r#"{"tickets":3,"best":5,"counts":[{"matches":5,"tickets":1},{"matches":3,"tickets":1},{"matches":0,"tickets":1}],"#,
+                r#""prizes":[{"matches":5,"bets":1},{"matches":3,"bets":1},{"matches":0,"bets":1}]}"#
+            )
+        );
+
+        // A multi-number bet reports every simple bet it contains
+        let request = r#"{"start": 1, "end": 25, "pick": 7, "draw_size": 5,
+            "result": [1, 2, 3, 4, 5], "tickets": [[1, 2, 3, 4, 5, 6, 7]]}"#;
+        let response = handle("POST", "/check", request);
+        assert_eq!(response.status(), 200);
+        let body = parse(&response);
+        let ticket = &body.get("tickets").and_then(Json::as_array).unwrap()[0];
+        assert_eq!(
+            ticket.get("prizes").unwrap().to_string(),
+            r#"[{"matches":5,"bets":1},{"matches":4,"bets":10},{"matches":3,"bets":10}]"#
+        );
+
+        let request = r#"{"start": 1, "end": 25, "pick": 5, "result": [1, 2, 3, 4, 5],
+            "tickets": [[1, 2, 3, 4, 5], [1, 1, 2, 3, 4]]}"#;
+        let response = handle("POST", "/check", request);
+        assert_eq!(response.status(), 422);
+        let error = parse(&response);
+        let fields = error.get("error").and_then(|e| e.get("fields")).unwrap();
+        assert_eq!(fields.get("line").and_then(Json::as_u64), Some(2));
+    }
+
+    #[test]
+    fn test_serves_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve(listener));
+
+        let exchange = |request: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let body = r#"{"start":1,"end":10,"pick":2}"#;
+        let response = exchange(&format!(
+            "POST /odds HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.ends_with("}\n"));
+
+        let response = exchange("GET /odds HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        assert!(response.contains("Allow: POST\r\n"));
+
+        let response = exchange(&format!(
+            "POST /odds HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        ));
+        assert!(response.starts_with("HTTP/1.1 413 "));
+
+        let response = exchange("nonsense\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+}
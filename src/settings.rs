@@ -0,0 +1,455 @@
+//! User settings file with named profiles.
+//!
+//! Flags people type on every run (the game, how many tickets, the output
+//! format, colors, the language and the RNG seed) can be kept in a settings file instead.
+//! Top-level keys are the defaults; each `[profile.NAME]` section overrides
+//! them when that profile is selected. Command-line flags override both.
+//!
+//! # File format
+//!
+//! A small subset of TOML: one `key = value` per line, strings optionally
+//! quoted, blank lines and `#` comments ignored.
+//!
+//! ```text
+//! game = "mega-sena"   # a known game, or start/end/pick
+//! tickets = 5
+//! format = "text"      # text, json, csv or ndjson
+//! color = "auto"       # auto, always or never
+//! lang = "pt-BR"       # en or pt-BR, instead of the LANG environment variable
+//!
+//! [profile.office-pool]
+//! game = "lotofacil"
+//! tickets = 20
+//! seed = 2024          # fixes the RNG seed, so every run is the same
+//!
+//! [profile.custom]
+//! start = 1
+//! end = 50
+//! pick = 6
+//! ```
+//!
+//! The file lives at `$XDG_CONFIG_HOME/lotto-quick-pick/config.toml`, or
+//! `~/.config/lotto-quick-pick/config.toml` when `XDG_CONFIG_HOME` is unset.
+
+use crate::Config;
+use crate::error::{LottoError, Result};
+use crate::locale::Locale;
+use crate::newtypes::GameCount;
+use crate::output::OutputFormat;
+use std::path::{Path, PathBuf};
+
+/// Color choices accepted by the `color` key.
+const COLORS: [&str; 3] = ["auto", "always", "never"];
+
+/// Settings from one section of a settings file, or several merged.
+///
+/// Every value is optional; unset values fall back to the next layer.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::settings::SettingsFile;
+///
+/// let file = SettingsFile::parse("tickets = 3\n[profile.pool]\ntickets = 20").unwrap();
+/// assert_eq!(file.profile(None).unwrap().tickets(), Some(3));
+/// assert_eq!(file.profile(Some("pool")).unwrap().tickets(), Some(20));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Settings {
+    game: Option<String>,
+    start: Option<u8>,
+    end: Option<u8>,
+    pick: Option<usize>,
+    tickets: Option<usize>,
+    format: Option<OutputFormat>,
+    color: Option<String>,
+    lang: Option<Locale>,
+    seed: Option<u64>,
+}
+
+impl Settings {
+    /// Get the name of the known game, such as `mega-sena`.
+    pub fn game(&self) -> Option<&str> {
+        self.game.as_deref()
+    }
+
+    /// Get the starting number of the game.
+    pub fn start(&self) -> Option<u8> {
+        self.start
+    }
+
+    /// Get the ending number of the game.
+    pub fn end(&self) -> Option<u8> {
+        self.end
+    }
+
+    /// Get the number of balls picked for each ticket.
+    pub fn pick(&self) -> Option<usize> {
+        self.pick
+    }
+
+    /// Get the number of tickets to generate.
+    pub fn tickets(&self) -> Option<usize> {
+        self.tickets
+    }
+
+    /// Get the output format.
+    pub fn format(&self) -> Option<OutputFormat> {
+        self.format
+    }
+
+    /// Get the color choice: `auto`, `always` or `never`.
+    pub fn color(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+
+    /// Get the language of the output.
+    pub fn lang(&self) -> Option<Locale> {
+        self.lang
+    }
+
+    /// Get the seed of the random generator.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Build the game's Config when start, end and pick are all set.
+    ///
+    /// # Returns
+    ///
+    /// `None` if part of the game is missing (it may come from `game` or
+    /// the command line), otherwise the result of [`Config::new`].
+    pub fn config(&self) -> Option<Result<Config>> {
+        let (start, end, pick) = (self.start?, self.end?, self.pick?);
+        Some(Config::new(self.tickets.unwrap_or(1), start, end, pick))
+    }
+
+    /// Layer `other` on top of these settings: its values win where set.
+    ///
+    /// A `game` in `other` replaces the whole game below it, so a profile
+    /// naming a game is not mixed with start, end or pick from the defaults.
+    pub fn merge(mut self, other: &Settings) -> Self {
+        if other.game.is_some() {
+            self.game = other.game.clone();
+            self.start = None;
+            self.end = None;
+            self.pick = None;
+        }
+        self.start = other.start.or(self.start);
+        self.end = other.end.or(self.end);
+        self.pick = other.pick.or(self.pick);
+        self.tickets = other.tickets.or(self.tickets);
+        self.format = other.format.or(self.format);
+        self.color = other.color.clone().or(self.color);
+        self.lang = other.lang.or(self.lang);
+        self.seed = other.seed.or(self.seed);
+        self
+    }
+
+    /// Set one key from its text value.
+    fn set(&mut self, key: &str, value: &str) -> std::result::Result<(), String> {
+        fn number<T: std::str::FromStr>(value: &str) -> std::result::Result<T, String>
+        where
+            T::Err: std::fmt::Display,
+        {
+            value
+                .parse()
+                .map_err(|e| format!("invalid number '{}': {}", value, e))
+        }
+
+        match key {
+            "game" => self.game = Some(value.to_ascii_lowercase()),
+            "start" => self.start = Some(number(value)?),
+            "end" => self.end = Some(number(value)?),
+            "pick" => self.pick = Some(number(value)?),
+            "tickets" => {
+                let tickets = number(value)?;
+                GameCount::new(tickets).map_err(|e| e.to_string())?;
+                self.tickets = Some(tickets);
+            }
+            "format" => self.format = Some(value.parse().map_err(|e: LottoError| e.to_string())?),
+            "color" => {
+                let color = value.to_ascii_lowercase();
+                if !COLORS.contains(&color.as_str()) {
+                    return Err(format!(
+                        "unknown color '{}' (expected auto, always or never)",
+                        value
+                    ));
+                }
+                self.color = Some(color);
+            }
+            "lang" => self.lang = Some(value.parse().map_err(|e: LottoError| e.to_string())?),
+            "seed" => self.seed = Some(number(value)?),
+            _ => return Err(format!("unknown key '{}'", key)),
+        }
+        Ok(())
+    }
+}
+
+/// A parsed settings file: the defaults and the named profiles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SettingsFile {
+    defaults: Settings,
+    profiles: Vec<(String, Settings)>,
+}
+
+impl SettingsFile {
+    /// Get the standard location of the settings file.
+    ///
+    /// # Returns
+    ///
+    /// `$XDG_CONFIG_HOME/lotto-quick-pick/config.toml`, falling back to
+    /// `$HOME/.config`, or `None` if neither variable is set.
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .filter(|dir| !dir.is_empty())
+                    .map(|home| PathBuf::from(home).join(".config"))
+            })?;
+        Some(base.join("lotto-quick-pick").join("config.toml"))
+    }
+
+    /// Load a settings file (see the module documentation for the format).
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the file cannot be read and `ParseError` if a
+    /// line is malformed or a section's game is invalid.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| LottoError::IoError(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text).map_err(|e| match e {
+            LottoError::ParseError(message) => {
+                LottoError::ParseError(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })
+    }
+
+    /// Parse settings from text (see the module documentation for the format).
+    ///
+    /// Every section, merged over the defaults, is validated with
+    /// [`Config::new`] when it sets a complete game.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` naming the line of a malformed entry, or the
+    /// profile whose game is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::settings::SettingsFile;
+    ///
+    /// assert!(SettingsFile::parse("start = 1\nend = 60\npick = 6").is_ok());
+    /// assert!(SettingsFile::parse("start = 1\nend = 60\npick = 61").is_err());
+    /// assert!(SettingsFile::parse("tickets = many").is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut file = Self::default();
+        let mut section: Option<usize> = None;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| {
+                LottoError::ParseError(format!("line {}: {}", index + 1, message))
+            };
+
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header
+                    .strip_suffix(']')
+                    .and_then(|header| header.trim().strip_prefix("profile."))
+                    .map(|name| unquote(name.trim()))
+                    .filter(|name| !name.is_empty())
+                    .ok_or_else(|| {
+                        error(format!("expected a [profile.NAME] section, got '{}'", line))
+                    })?;
+                if file.profiles.iter().any(|(existing, _)| existing == name) {
+                    return Err(error(format!("profile '{}' is defined twice", name)));
+                }
+                file.profiles.push((name.to_string(), Settings::default()));
+                section = Some(file.profiles.len() - 1);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error(format!("expected KEY = VALUE, got '{}'", line)))?;
+            let settings = match section {
+                Some(index) => &mut file.profiles[index].1,
+                None => &mut file.defaults,
+            };
+            settings
+                .set(key.trim(), unquote(value.trim()))
+                .map_err(error)?;
+        }
+
+        file.validate()?;
+        Ok(file)
+    }
+
+    /// Get the names of the profiles, in file order.
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Get the defaults, with a profile merged on top when one is named.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the profile is not in the file.
+    pub fn profile(&self, name: Option<&str>) -> Result<Settings> {
+        let Some(name) = name else {
+            return Ok(self.defaults.clone());
+        };
+        self.profiles
+            .iter()
+            .find(|(profile, _)| profile == name)
+            .map(|(_, profile)| self.defaults.clone().merge(profile))
+            .ok_or_else(|| {
+                let known: Vec<&str> = self.profile_names().collect();
+                LottoError::ParseError(format!(
+                    "unknown profile '{}' (known profiles: {})",
+                    name,
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                ))
+            })
+    }
+
+    /// Check the game of the defaults and of every profile.
+    fn validate(&self) -> Result<()> {
+        let sections = std::iter::once((None, &self.defaults)).chain(
+            self.profiles
+                .iter()
+                .map(|(name, profile)| (Some(name.as_str()), profile)),
+        );
+        for (name, section) in sections {
+            let merged = match name {
+                Some(_) => self.defaults.clone().merge(section),
+                None => section.clone(),
+            };
+            if let Some(Err(e)) = merged.config() {
+                let section =
+                    name.map_or("defaults".to_string(), |name| format!("profile '{}'", name));
+                return Err(LottoError::ParseError(format!("{}: {}", section, e)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Strip one pair of matching quotes from a value.
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| {
+            value
+                .strip_prefix(quote)
+                .and_then(|value| value.strip_suffix(quote))
+        })
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+# team defaults
+game = "mega-sena"
+tickets = 5
+color = 'never'
+
+[profile.office-pool]
+game = "lotofacil"
+tickets = 20
+seed = 2024
+lang = pt_BR
+
+[profile.custom]
+start = 1
+end = 50
+pick = 6
+format = json
+"#;
+
+    #[test]
+    fn test_profiles_merge_over_defaults() {
+        let file = SettingsFile::parse(SAMPLE).unwrap();
+        assert_eq!(
+            file.profile_names().collect::<Vec<_>>(),
+            ["office-pool", "custom"]
+        );
+
+        let defaults = file.profile(None).unwrap();
+        assert_eq!(defaults.game(), Some("mega-sena"));
+        assert_eq!(defaults.tickets(), Some(5));
+        assert_eq!(defaults.color(), Some("never"));
+        assert_eq!(defaults.seed(), None);
+        assert_eq!(defaults.lang(), None);
+
+        let pool = file.profile(Some("office-pool")).unwrap();
+        assert_eq!(pool.game(), Some("lotofacil"));
+        assert_eq!(pool.tickets(), Some(20));
+        assert_eq!(pool.color(), Some("never"));
+        assert_eq!(pool.seed(), Some(2024));
+        assert_eq!(pool.lang(), Some(Locale::Portuguese));
+
+        let custom = file.profile(Some("custom")).unwrap();
+        assert_eq!(custom.game(), Some("mega-sena"));
+        assert_eq!(custom.format(), Some(OutputFormat::Json));
+        let config = custom.config().unwrap().unwrap();
+        assert_eq!(config.range().end().value(), 50);
+        assert_eq!(config.game_count().value(), 5);
+    }
+
+    #[test]
+    fn test_game_replaces_numbers_from_lower_layer() {
+        let file = SettingsFile::parse("start = 1\nend = 50\npick = 6\n[profile.p]\ngame = quina")
+            .unwrap();
+        let profile = file.profile(Some("p")).unwrap();
+
+        assert_eq!(profile.game(), Some("quina"));
+        assert_eq!(profile.start(), None);
+        assert!(profile.config().is_none());
+    }
+
+    #[test]
+    fn test_errors_name_the_line_or_profile() {
+        let error = |text| match SettingsFile::parse(text) {
+            Err(LottoError::ParseError(message)) => message,
+            other => panic!("expected a parse error, got {:?}", other),
+        };
+
+        assert!(error("tickets = 5\nsize = 3").starts_with("line 2: unknown key"));
+        assert!(error("tickets = 0").starts_with("line 1:"));
+        assert!(error("color = blue").contains("unknown color"));
+        assert!(error("lang = klingon").contains("unsupported language"));
+        assert!(error("[pool]").contains("[profile.NAME]"));
+        assert!(error("[profile.a]\n[profile.a]").contains("defined twice"));
+        assert!(
+            error("start = 1\nend = 60\n[profile.big]\npick = 61").starts_with("profile 'big'")
+        );
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_known_ones() {
+        let file = SettingsFile::parse(SAMPLE).unwrap();
+        match file.profile(Some("home")) {
+            Err(LottoError::ParseError(message)) => {
+                assert!(message.contains("office-pool, custom"))
+            }
+            other => panic!("expected an unknown profile error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,809 @@
+//! Compact binary storage of tickets.
+//!
+//! Text takes about three bytes per ball; a store takes a fixed-width
+//! record per ticket instead, either its [`TicketKey`] bitmap or its
+//! combinatorial [rank](crate::rank). A Mega-Sena ticket is 8 bytes as a
+//! bitmap and 4 bytes as a rank.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! header   "LQPK"  magic
+//!          u8      format version (1)
+//!          u8      encoding: 0 = bitmap, 1 = rank
+//!          u8, u8  range start and end
+//!          u16     pick
+//!          u8      record width in bytes
+//!          u8      flags: bit 0 set when a seed is recorded
+//!          u64     seed (0 when absent)
+//!          u8, ..  game name length and UTF-8 bytes
+//!          u8, ..  generator length and UTF-8 bytes
+//! records  width bytes per ticket
+//!          u16     checksum of the record: its FNV-1a hash folded to 16 bits
+//! trailer  u64     number of records
+//!          u64     FNV-1a checksum of the header and records
+//! ```
+//!
+//! The reader checks each record's checksum before yielding its ticket, so
+//! a corrupt record is never returned as a valid one. It recognizes the
+//! trailer as the last 16 bytes of the stream, so the writer never seeks.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+use crate::probability::combination_big;
+use crate::rank::{rank, unrank};
+use crate::ticket_key::TicketKey;
+use std::fmt;
+use std::io::{ErrorKind, Read, Write};
+use std::str::FromStr;
+
+const MAGIC: &[u8; 4] = b"LQPK";
+const VERSION: u8 = 1;
+const FLAG_SEED: u8 = 1;
+const TRAILER_LEN: usize = 16;
+const RECORD_CHECKSUM_LEN: usize = 2;
+
+/// How each ticket is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoreEncoding {
+    /// The ticket's bitmap, one bit per ball of the range.
+    #[default]
+    Bitmap,
+    /// The ticket's colexicographic rank, in as few bytes as the game needs.
+    Rank,
+}
+
+impl FromStr for StoreEncoding {
+    type Err = LottoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bitmap" => Ok(StoreEncoding::Bitmap),
+            "rank" => Ok(StoreEncoding::Rank),
+            _ => Err(LottoError::ParseError(format!(
+                "unknown store encoding '{}' (expected bitmap or rank)",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for StoreEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StoreEncoding::Bitmap => "bitmap",
+            StoreEncoding::Rank => "rank",
+        })
+    }
+}
+
+/// Check whether bytes begin like a ticket store, to tell stores from text files.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::store::is_store;
+///
+/// assert!(is_store(b"LQPK\x01"));
+/// assert!(!is_store(b"04 08 15 16 23 42\n"));
+/// ```
+pub fn is_store(prefix: &[u8]) -> bool {
+    prefix.starts_with(MAGIC)
+}
+
+/// Game and generator metadata at the start of a store.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::store::{StoreEncoding, StoreHeader};
+/// use lotto_quick_pick::{BallRange, PickCount};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+///
+/// let bitmap = StoreHeader::new(&range, &pick, StoreEncoding::Bitmap);
+/// let rank = StoreHeader::new(&range, &pick, StoreEncoding::Rank).with_seed(42);
+/// assert_eq!(bitmap.record_width(), 8);
+/// assert_eq!(rank.record_width(), 4);
+/// assert_eq!(rank.seed(), Some(42));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoreHeader {
+    range: BallRange,
+    pick: PickCount,
+    encoding: StoreEncoding,
+    name: String,
+    generator: String,
+    seed: Option<u64>,
+}
+
+impl StoreHeader {
+    /// Create a new StoreHeader with no name, generator or seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The range of ball numbers of the game
+    /// * `pick` - Number of balls per ticket
+    /// * `encoding` - How each ticket is stored
+    pub fn new(range: &BallRange, pick: &PickCount, encoding: StoreEncoding) -> Self {
+        Self {
+            range: *range,
+            pick: *pick,
+            encoding,
+            name: String::new(),
+            generator: String::new(),
+            seed: None,
+        }
+    }
+
+    /// Set the name of the game (at most 255 bytes).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Describe the generator that produced the tickets (at most 255 bytes).
+    pub fn with_generator(mut self, generator: impl Into<String>) -> Self {
+        self.generator = generator.into();
+        self
+    }
+
+    /// Record the seed the tickets were generated from.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Get the range of ball numbers.
+    pub fn range(&self) -> &BallRange {
+        &self.range
+    }
+
+    /// Get the number of balls per ticket.
+    pub fn pick(&self) -> &PickCount {
+        &self.pick
+    }
+
+    /// Get the record encoding.
+    pub fn encoding(&self) -> StoreEncoding {
+        self.encoding
+    }
+
+    /// Get the game name, empty if none was given.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the generator description, empty if none was given.
+    pub fn generator(&self) -> &str {
+        &self.generator
+    }
+
+    /// Get the seed the tickets were generated from, if recorded.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Get the number of bytes of each record.
+    pub fn record_width(&self) -> usize {
+        match self.encoding {
+            StoreEncoding::Bitmap => match self.range.size() {
+                0..=64 => 8,
+                65..=128 => 16,
+                size => size.div_ceil(64) * 8,
+            },
+            StoreEncoding::Rank => {
+                let last = combination_big(self.range.size(), self.pick.value())
+                    .checked_sub(&BigUint::one())
+                    .unwrap_or_default();
+                last.bits().div_ceil(8).max(1)
+            }
+        }
+    }
+
+    /// Serialize the header.
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(24 + self.name.len() + self.generator.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(match self.encoding {
+            StoreEncoding::Bitmap => 0,
+            StoreEncoding::Rank => 1,
+        });
+        bytes.push(self.range.start().value());
+        bytes.push(self.range.end().value());
+        bytes.extend_from_slice(&(self.pick.value() as u16).to_le_bytes());
+        bytes.push(self.record_width() as u8);
+        bytes.push(if self.seed.is_some() { FLAG_SEED } else { 0 });
+        bytes.extend_from_slice(&self.seed.unwrap_or(0).to_le_bytes());
+        for (field, text) in [("name", &self.name), ("generator", &self.generator)] {
+            let len = u8::try_from(text.len()).map_err(|_| LottoError::InvalidStore {
+                reason: format!("{} is longer than 255 bytes", field),
+            })?;
+            bytes.push(len);
+            bytes.extend_from_slice(text.as_bytes());
+        }
+        Ok(bytes)
+    }
+
+    /// Read and validate a header, returning it with its raw bytes.
+    fn read_from(reader: &mut impl Read) -> Result<(Self, Vec<u8>)> {
+        let mut fixed = [0u8; 20];
+        read_exact(reader, &mut fixed, "header")?;
+        if &fixed[..4] != MAGIC {
+            return Err(invalid("not a ticket store"));
+        }
+        if fixed[4] != VERSION {
+            return Err(invalid(format!("unsupported format version {}", fixed[4])));
+        }
+        let encoding = match fixed[5] {
+            0 => StoreEncoding::Bitmap,
+            1 => StoreEncoding::Rank,
+            code => return Err(invalid(format!("unknown encoding {}", code))),
+        };
+        let range = BallRange::new(BallNumber::new(fixed[6]), BallNumber::new(fixed[7]))?;
+        let pick = PickCount::new(u16::from_le_bytes([fixed[8], fixed[9]]) as usize, &range)?;
+        let seed = (fixed[11] & FLAG_SEED != 0)
+            .then(|| u64::from_le_bytes(fixed[12..20].try_into().expect("8 bytes")));
+
+        let mut bytes = fixed.to_vec();
+        let mut texts = Vec::with_capacity(2);
+        for field in ["name", "generator"] {
+            let mut len = [0u8; 1];
+            read_exact(reader, &mut len, "header")?;
+            let mut text = vec![0u8; len[0] as usize];
+            read_exact(reader, &mut text, "header")?;
+            bytes.push(len[0]);
+            bytes.extend_from_slice(&text);
+            texts.push(
+                String::from_utf8(text)
+                    .map_err(|_| invalid(format!("{} is not valid UTF-8", field)))?,
+            );
+        }
+        let generator = texts.pop().expect("two texts read");
+        let name = texts.pop().expect("two texts read");
+
+        let header = Self {
+            range,
+            pick,
+            encoding,
+            name,
+            generator,
+            seed,
+        };
+        if fixed[10] as usize != header.record_width() {
+            return Err(invalid(format!(
+                "record width {} does not match the game ({} expected)",
+                fixed[10],
+                header.record_width()
+            )));
+        }
+        Ok((header, bytes))
+    }
+}
+
+/// Streaming writer of a ticket store.
+///
+/// The trailer is only written by [`StoreWriter::finish`]; a store that was
+/// not finished is rejected by the reader.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::store::{StoreEncoding, StoreHeader, StoreReader, StoreWriter};
+/// use lotto_quick_pick::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let ticket = Ticket::new([4, 8, 15, 16, 23, 42].map(BallNumber::new).to_vec());
+///
+/// let header = StoreHeader::new(&range, &pick, StoreEncoding::Rank).with_name("Mega-Sena");
+/// let mut writer = StoreWriter::new(Vec::new(), header).unwrap();
+/// writer.write_ticket(&ticket).unwrap();
+/// let bytes = writer.finish().unwrap();
+///
+/// let reader = StoreReader::new(bytes.as_slice()).unwrap();
+/// assert_eq!(reader.header().name(), "Mega-Sena");
+/// let tickets: Vec<Ticket> = reader.collect::<Result<_, _>>().unwrap();
+/// assert_eq!(tickets, vec![ticket]);
+/// ```
+#[derive(Debug)]
+pub struct StoreWriter<W: Write> {
+    writer: W,
+    header: StoreHeader,
+    width: usize,
+    count: u64,
+    checksum: Fnv1a,
+}
+
+impl<W: Write> StoreWriter<W> {
+    /// Create a new StoreWriter, writing the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidStore` if the name or generator is too long, or
+    /// `IoError` if writing fails.
+    pub fn new(mut writer: W, header: StoreHeader) -> Result<Self> {
+        let bytes = header.to_bytes()?;
+        writer.write_all(&bytes).map_err(io_error)?;
+        let mut checksum = Fnv1a::new();
+        checksum.update(&bytes);
+        Ok(Self {
+            writer,
+            width: header.record_width(),
+            header,
+            count: 0,
+            checksum,
+        })
+    }
+
+    /// Get the header written at the start of the store.
+    pub fn header(&self) -> &StoreHeader {
+        &self.header
+    }
+
+    /// Append a ticket.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidTicketSize`, `BallOutOfRange` or `DuplicateBall` if
+    /// the ticket does not belong to the store's game, or `IoError` if
+    /// writing fails.
+    pub fn write_ticket(&mut self, ticket: &Ticket) -> Result<()> {
+        validate(ticket, &self.header.range, &self.header.pick)?;
+
+        let mut record = match self.header.encoding {
+            StoreEncoding::Bitmap => {
+                match TicketKey::from_balls(ticket.balls(), &self.header.range) {
+                    TicketKey::U64(bitmap) => bitmap.to_le_bytes().to_vec(),
+                    TicketKey::U128(bitmap) => bitmap.to_le_bytes().to_vec(),
+                    // Words beyond the range are zero and cut off by the record width
+                    TicketKey::U256(words) => words.iter().flat_map(|w| w.to_le_bytes()).collect(),
+                }
+            }
+            StoreEncoding::Rank => rank(ticket, &self.header.range)?.to_le_bytes(),
+        };
+        record.resize(self.width, 0);
+        record.extend_from_slice(&record_checksum(&record));
+
+        self.writer.write_all(&record).map_err(io_error)?;
+        self.checksum.update(&record);
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Write the trailer and return the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if writing or flushing fails.
+    pub fn finish(mut self) -> Result<W> {
+        let mut trailer = [0u8; TRAILER_LEN];
+        trailer[..8].copy_from_slice(&self.count.to_le_bytes());
+        trailer[8..].copy_from_slice(&self.checksum.value().to_le_bytes());
+        self.writer.write_all(&trailer).map_err(io_error)?;
+        self.writer.flush().map_err(io_error)?;
+        Ok(self.writer)
+    }
+}
+
+/// Streaming reader of a ticket store, yielding one `Result` per ticket.
+///
+/// Each record's checksum is verified before its ticket is yielded. The
+/// record count and store checksum are verified when the trailer is
+/// reached; a mismatch is reported as the last item.
+#[derive(Debug)]
+pub struct StoreReader<R: Read> {
+    reader: R,
+    header: StoreHeader,
+    width: usize,
+    buffer: Vec<u8>,
+    position: usize,
+    count: u64,
+    checksum: Fnv1a,
+    done: bool,
+}
+
+impl<R: Read> StoreReader<R> {
+    /// Create a new StoreReader, reading and validating the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidStore` if the stream is not a ticket store of a
+    /// supported version, or `IoError` if reading fails.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let (header, bytes) = StoreHeader::read_from(&mut reader)?;
+        let mut checksum = Fnv1a::new();
+        checksum.update(&bytes);
+        Ok(Self {
+            reader,
+            width: header.record_width(),
+            header,
+            buffer: Vec::new(),
+            position: 0,
+            count: 0,
+            checksum,
+            done: false,
+        })
+    }
+
+    /// Get the header read from the start of the store.
+    pub fn header(&self) -> &StoreHeader {
+        &self.header
+    }
+
+    /// Buffer at least `needed` unread bytes, or every byte left at the end.
+    fn fill(&mut self, needed: usize) -> Result<()> {
+        while self.buffer.len() - self.position < needed {
+            self.buffer.drain(..self.position);
+            self.position = 0;
+            let start = self.buffer.len();
+            self.buffer.resize(start + needed.max(8192), 0);
+            let read = loop {
+                match self.reader.read(&mut self.buffer[start..]) {
+                    Ok(read) => break read,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => {
+                        self.buffer.truncate(start);
+                        return Err(io_error(e));
+                    }
+                }
+            };
+            self.buffer.truncate(start + read);
+            if read == 0 {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode the record at the current position.
+    fn decode(&self, record: &[u8]) -> Result<Ticket> {
+        let range = &self.header.range;
+        let balls = match self.header.encoding {
+            StoreEncoding::Bitmap => {
+                let key = match record.len() {
+                    8 => TicketKey::U64(u64::from_le_bytes(record.try_into().expect("8 bytes"))),
+                    16 => {
+                        TicketKey::U128(u128::from_le_bytes(record.try_into().expect("16 bytes")))
+                    }
+                    _ => {
+                        let mut words = [0u64; 4];
+                        for (word, bytes) in words.iter_mut().zip(record.chunks(8)) {
+                            *word = u64::from_le_bytes(bytes.try_into().expect("8 bytes"));
+                        }
+                        TicketKey::U256(words)
+                    }
+                };
+                key.to_balls(range)
+            }
+            StoreEncoding::Rank => {
+                let rank = BigUint::from_le_bytes(record);
+                return unrank(&rank, range, &self.header.pick)
+                    .map_err(|e| invalid(format!("record {}: {}", self.count + 1, e)));
+            }
+        };
+
+        // Bits beyond the range are dropped by to_balls and show up here
+        if balls.len() != self.header.pick.value() {
+            return Err(invalid(format!(
+                "record {} has {} balls in range instead of {}",
+                self.count + 1,
+                balls.len(),
+                self.header.pick.value()
+            )));
+        }
+        Ok(Ticket::from_sorted(balls))
+    }
+
+    /// Check the trailer against the records read.
+    fn verify_trailer(&self, trailer: &[u8]) -> Result<()> {
+        let count = u64::from_le_bytes(trailer[..8].try_into().expect("8 bytes"));
+        let checksum = u64::from_le_bytes(trailer[8..].try_into().expect("8 bytes"));
+        if count != self.count {
+            return Err(invalid(format!(
+                "trailer counts {} records but {} were read",
+                count, self.count
+            )));
+        }
+        if checksum != self.checksum.value() {
+            return Err(invalid("checksum mismatch"));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for StoreReader<R> {
+    type Item = Result<Ticket>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let frame = self.width + RECORD_CHECKSUM_LEN;
+        if let Err(e) = self.fill(frame + TRAILER_LEN) {
+            self.done = true;
+            return Some(Err(e));
+        }
+
+        let available = self.buffer.len() - self.position;
+        if available >= frame + TRAILER_LEN {
+            let bytes = &self.buffer[self.position..self.position + frame];
+            let (record, stored) = bytes.split_at(self.width);
+            self.checksum.update(bytes);
+            let ticket = if stored == record_checksum(record) {
+                self.decode(record)
+            } else {
+                Err(invalid(format!(
+                    "record {} checksum mismatch",
+                    self.count + 1
+                )))
+            };
+            self.position += frame;
+            self.count += 1;
+            if ticket.is_err() {
+                self.done = true;
+            }
+            return Some(ticket);
+        }
+
+        self.done = true;
+        if available == TRAILER_LEN {
+            let trailer = &self.buffer[self.position..];
+            self.verify_trailer(trailer).err().map(Err)
+        } else {
+            Some(Err(invalid("truncated store")))
+        }
+    }
+}
+
+/// Check that a ticket belongs to the game without allocating.
+fn validate(ticket: &Ticket, range: &BallRange, pick: &PickCount) -> Result<()> {
+    let balls = ticket.balls();
+    if balls.len() != pick.value() {
+        return Err(LottoError::InvalidTicketSize {
+            expected: pick.value(),
+            got: balls.len(),
+        });
+    }
+    if let Some(&ball) = balls.iter().find(|ball| !range.contains(**ball)) {
+        return Err(LottoError::BallOutOfRange {
+            value: ball.value(),
+            start: range.start().value(),
+            end: range.end().value(),
+        });
+    }
+    if let Some(pair) = balls.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(LottoError::DuplicateBall {
+            value: pair[0].value(),
+        });
+    }
+    Ok(())
+}
+
+/// Read exactly `buffer.len()` bytes, reporting a short read as truncation.
+fn read_exact(reader: &mut impl Read, buffer: &mut [u8], part: &str) -> Result<()> {
+    reader.read_exact(buffer).map_err(|e| {
+        if e.kind() == ErrorKind::UnexpectedEof {
+            invalid(format!("truncated {}", part))
+        } else {
+            io_error(e)
+        }
+    })
+}
+
+fn invalid(reason: impl Into<String>) -> LottoError {
+    LottoError::InvalidStore {
+        reason: reason.into(),
+    }
+}
+
+fn io_error(e: std::io::Error) -> LottoError {
+    LottoError::IoError(e.to_string())
+}
+
+/// Checksum of one record: its FNV-1a hash folded to 16 bits.
+fn record_checksum(record: &[u8]) -> [u8; RECORD_CHECKSUM_LEN] {
+    let mut hash = Fnv1a::new();
+    hash.update(record);
+    let value = hash.value();
+    ((value ^ value >> 16 ^ value >> 32 ^ value >> 48) as u16).to_le_bytes()
+}
+
+/// 64-bit FNV-1a hash, used as the store checksum.
+#[derive(Debug, Clone, Copy)]
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::newtypes::GameCount;
+    use crate::ticket::generate_unique_tickets;
+
+    fn game(start: u8, end: u8, pick: usize) -> (BallRange, PickCount) {
+        let range = BallRange::new(BallNumber::new(start), BallNumber::new(end)).unwrap();
+        let pick = PickCount::new(pick, &range).unwrap();
+        (range, pick)
+    }
+
+    fn round_trip(header: StoreHeader, tickets: &[Ticket]) -> (StoreHeader, Vec<Ticket>, usize) {
+        let mut writer = StoreWriter::new(Vec::new(), header).unwrap();
+        for ticket in tickets {
+            writer.write_ticket(ticket).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+        let reader = StoreReader::new(bytes.as_slice()).unwrap();
+        let header = reader.header().clone();
+        let read = reader.collect::<Result<Vec<_>>>().unwrap();
+        (header, read, bytes.len())
+    }
+
+    fn store_bytes(encoding: StoreEncoding) -> Vec<u8> {
+        let (range, pick) = game(1, 60, 6);
+        let tickets = generate_unique_tickets(
+            &mut rand::rng(),
+            &range,
+            &pick,
+            &GameCount::new(10).unwrap(),
+        )
+        .unwrap();
+        let mut writer =
+            StoreWriter::new(Vec::new(), StoreHeader::new(&range, &pick, encoding)).unwrap();
+        for ticket in &tickets {
+            writer.write_ticket(ticket).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_every_width() {
+        for (start, end, pick) in [
+            (1, 25, 15),
+            (1, 60, 6),
+            (0, 99, 50),
+            (1, 200, 10),
+            (0, 255, 128),
+        ] {
+            let (range, pick) = game(start, end, pick);
+            let tickets = generate_unique_tickets(
+                &mut rand::rng(),
+                &range,
+                &pick,
+                &GameCount::new(20).unwrap(),
+            )
+            .unwrap();
+            for encoding in [StoreEncoding::Bitmap, StoreEncoding::Rank] {
+                let header = StoreHeader::new(&range, &pick, encoding)
+                    .with_name("test")
+                    .with_generator("StdRng")
+                    .with_seed(7);
+                let (read_header, read, _) = round_trip(header.clone(), &tickets);
+                assert_eq!(read_header, header);
+                assert_eq!(read, tickets);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mega_sena_record_sizes() {
+        let (range, pick) = game(1, 60, 6);
+        let ticket = Ticket::new((1..=6).map(BallNumber::new).collect());
+        let tickets = vec![ticket; 100];
+
+        let (_, _, bitmap) = round_trip(
+            StoreHeader::new(&range, &pick, StoreEncoding::Bitmap),
+            &tickets,
+        );
+        let (_, _, ranked) = round_trip(
+            StoreHeader::new(&range, &pick, StoreEncoding::Rank),
+            &tickets,
+        );
+        // 22-byte header without name or generator, 2-byte record checksums, 16-byte trailer
+        assert_eq!(bitmap, 22 + 100 * (8 + 2) + 16);
+        assert_eq!(ranked, 22 + 100 * (4 + 2) + 16);
+    }
+
+    #[test]
+    fn test_writer_rejects_tickets_of_another_game() {
+        let (range, pick) = game(1, 60, 6);
+        let mut writer = StoreWriter::new(
+            Vec::new(),
+            StoreHeader::new(&range, &pick, StoreEncoding::Bitmap),
+        )
+        .unwrap();
+        let short = Ticket::new((1..=5).map(BallNumber::new).collect());
+        let outside = Ticket::new((56..=61).map(BallNumber::new).collect());
+
+        assert!(matches!(
+            writer.write_ticket(&short),
+            Err(LottoError::InvalidTicketSize { .. })
+        ));
+        assert!(matches!(
+            writer.write_ticket(&outside),
+            Err(LottoError::BallOutOfRange { value: 61, .. })
+        ));
+    }
+
+    #[test]
+    fn test_corruption_is_detected() {
+        for encoding in [StoreEncoding::Bitmap, StoreEncoding::Rank] {
+            let bytes = store_bytes(encoding);
+
+            let mut flipped = bytes.clone();
+            let last_record = bytes.len() - TRAILER_LEN - 1;
+            flipped[last_record] ^= 0x01;
+            let results: Vec<_> = StoreReader::new(flipped.as_slice()).unwrap().collect();
+            assert!(results.iter().any(Result::is_err), "{:?}", encoding);
+
+            // A corrupt first record is reported before any ticket is yielded
+            let mut flipped = bytes.clone();
+            flipped[22] ^= 0x10;
+            let first = StoreReader::new(flipped.as_slice()).unwrap().next();
+            assert!(
+                matches!(first, Some(Err(LottoError::InvalidStore { ref reason })) if reason.contains("record 1")),
+                "{:?}",
+                first
+            );
+
+            let truncated = &bytes[..bytes.len() - 3];
+            let results: Vec<_> = StoreReader::new(truncated).unwrap().collect();
+            assert!(matches!(
+                results.last(),
+                Some(Err(LottoError::InvalidStore { .. }))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_header_validation() {
+        assert!(matches!(
+            StoreReader::new(&b"LQPX"[..]),
+            Err(LottoError::InvalidStore { .. })
+        ));
+
+        let mut bytes = store_bytes(StoreEncoding::Bitmap);
+        bytes[4] = 2;
+        assert!(matches!(
+            StoreReader::new(bytes.as_slice()),
+            Err(LottoError::InvalidStore { .. })
+        ));
+
+        assert!(is_store(&store_bytes(StoreEncoding::Rank)));
+        assert!(!is_store(b"LQP"));
+
+        let (range, pick) = game(1, 60, 6);
+        let header =
+            StoreHeader::new(&range, &pick, StoreEncoding::Bitmap).with_name("x".repeat(256));
+        assert!(StoreWriter::new(Vec::new(), header).is_err());
+    }
+
+    #[test]
+    fn test_encoding_parsing() {
+        assert_eq!(
+            "RANK".parse::<StoreEncoding>().unwrap(),
+            StoreEncoding::Rank
+        );
+        assert!("zip".parse::<StoreEncoding>().is_err());
+    }
+}
@@ -5,7 +5,48 @@
 
 use crate::newtypes::{BallNumber, BallRange, GameCount, PickCount, Ticket};
 use crate::rng::RandomNumberGenerator;
+use crate::ticket_key::TicketKey;
 use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// The order in which a batch of tickets is returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OutputOrder {
+    /// The order the tickets were drawn in, reproducible from a seed.
+    #[default]
+    Generation,
+    /// Ascending by the sorted balls, as in a printed list.
+    Lexicographic,
+    /// Ascending by combinatorial [rank](crate::rank).
+    Rank,
+}
+
+impl FromStr for OutputOrder {
+    type Err = crate::error::LottoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "generation" => Ok(OutputOrder::Generation),
+            "lexicographic" | "lex" => Ok(OutputOrder::Lexicographic),
+            "rank" => Ok(OutputOrder::Rank),
+            other => Err(crate::error::LottoError::ParseError(format!(
+                "unknown order '{other}' (expected generation, lexicographic or rank)"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OutputOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputOrder::Generation => "generation",
+            OutputOrder::Lexicographic => "lexicographic",
+            OutputOrder::Rank => "rank",
+        };
+        f.write_str(name)
+    }
+}
 
 /// Generate a single lottery ticket with unique random ball numbers.
 ///
@@ -162,74 +203,272 @@ pub fn generate_unique_tickets<R: RandomNumberGenerator>(
     pick: &PickCount,
     game_count: &GameCount,
 ) -> crate::error::Result<Vec<Ticket>> {
+    generate_unique_tickets_ordered(rng, range, pick, game_count, OutputOrder::Generation)
+}
+
+/// Generate multiple unique tickets in the given output order.
+///
+/// Generation order keeps a list of keys alongside the uniqueness set so a
+/// seeded RNG reproduces the same sequence. Sorted orders need only the set,
+/// which is drained and sorted once generation finishes.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator implementing the RandomNumberGenerator trait
+/// * `range` - The range of ball numbers to choose from
+/// * `pick` - The number of balls to pick per ticket
+/// * `game_count` - The number of unique tickets to generate
+/// * `order` - The order of the returned tickets
+///
+/// # Returns
+///
+/// A Result containing a vector of unique Ticket instances, or an error.
+///
+/// # Errors
+///
+/// Same as [`generate_unique_tickets`].
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::ticket::{generate_unique_tickets_ordered, OutputOrder};
+/// use lotto_quick_pick::newtypes::{BallRange, GameCount, PickCount};
+///
+/// let mut rng = rand::rng();
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let count = GameCount::new(10).unwrap();
+///
+/// let tickets =
+///     generate_unique_tickets_ordered(&mut rng, &range, &pick, &count, OutputOrder::Lexicographic)
+///         .unwrap();
+/// assert!(tickets.windows(2).all(|pair| pair[0] < pair[1]));
+/// ```
+pub fn generate_unique_tickets_ordered<R: RandomNumberGenerator>(
+    rng: &mut R,
+    range: &BallRange,
+    pick: &PickCount,
+    game_count: &GameCount,
+    order: OutputOrder,
+) -> crate::error::Result<Vec<Ticket>> {
+    // Only generation order needs a second structure to remember the sequence
+    let keep_sequence = order == OutputOrder::Generation;
+    let mut keys = generate_unique_keys(
+        rng,
+        range,
+        pick,
+        game_count,
+        keep_sequence,
+        None::<fn(&_) -> bool>,
+    )?;
+
+    if !keep_sequence {
+        // Key order is rank order, so sorting keys avoids building tickets twice
+        keys.sort_unstable();
+    }
+
+    // Convert TicketKey back to Ticket only at the end
+    // Use from_sorted since to_balls() returns pre-sorted Vec
+    let mut tickets: Vec<Ticket> = keys
+        .into_iter()
+        .map(|key| Ticket::from_sorted(key.to_balls(range)))
+        .collect();
+    if order == OutputOrder::Lexicographic {
+        tickets.sort_unstable();
+    }
+    Ok(tickets)
+}
+
+/// Sort tickets into the given output order.
+///
+/// Generation order leaves the tickets as they are. Rank order sorts by
+/// [`TicketKey`](crate::ticket_key::TicketKey), so every ticket must fit `range`.
+///
+/// # Arguments
+///
+/// * `tickets` - The tickets to sort in place
+/// * `order` - The order to sort into
+/// * `range` - The range of ball numbers the tickets were drawn from
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::ticket::{sort_tickets, OutputOrder};
+/// use lotto_quick_pick::newtypes::{BallNumber, BallRange, PickCount, Ticket};
+///
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(3, &range).unwrap();
+/// let ticket = |balls: [u8; 3]| {
+///     Ticket::try_new(balls.map(BallNumber::new).to_vec(), &range, &pick).unwrap()
+/// };
+/// let mut tickets = vec![ticket([1, 2, 60]), ticket([3, 4, 5])];
+///
+/// sort_tickets(&mut tickets, OutputOrder::Rank, &range);
+/// assert_eq!(tickets[0].balls()[0].value(), 3);
+///
+/// sort_tickets(&mut tickets, OutputOrder::Lexicographic, &range);
+/// assert_eq!(tickets[0].balls()[0].value(), 1);
+/// ```
+pub fn sort_tickets(tickets: &mut [Ticket], order: OutputOrder, range: &BallRange) {
+    match order {
+        OutputOrder::Generation => {}
+        OutputOrder::Lexicographic => tickets.sort_unstable(),
+        OutputOrder::Rank => {
+            tickets.sort_by_cached_key(|ticket| TicketKey::from_balls(ticket.balls(), range))
+        }
+    }
+}
+
+/// Generate multiple unique tickets that satisfy a filter.
+///
+/// Candidates are drawn uniformly and rejected when `filter` returns false,
+/// so the accepted tickets are uniform over the tickets the filter allows.
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator implementing the RandomNumberGenerator trait
+/// * `range` - The range of ball numbers to choose from
+/// * `pick` - The number of balls to pick per ticket
+/// * `game_count` - The number of unique tickets to generate
+/// * `filter` - Predicate every returned ticket satisfies
+///
+/// # Returns
+///
+/// A vector of unique tickets in generation order, or `UniqueGenerationFailed`
+/// if the filter rejects too many candidates.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::ticket::generate_unique_tickets_filtered;
+/// use lotto_quick_pick::newtypes::{BallRange, GameCount, PickCount};
+///
+/// let mut rng = rand::rng();
+/// let range = BallRange::mega_sena();
+/// let pick = PickCount::new(6, &range).unwrap();
+/// let count = GameCount::new(5).unwrap();
+///
+/// // Only tickets with at least three balls above 30
+/// let tickets = generate_unique_tickets_filtered(&mut rng, &range, &pick, &count, |t| {
+///     t.balls().iter().filter(|b| b.value() > 30).count() >= 3
+/// })
+/// .unwrap();
+/// assert_eq!(tickets.len(), 5);
+/// ```
+pub fn generate_unique_tickets_filtered<R, F>(
+    rng: &mut R,
+    range: &BallRange,
+    pick: &PickCount,
+    game_count: &GameCount,
+    filter: F,
+) -> crate::error::Result<Vec<Ticket>>
+where
+    R: RandomNumberGenerator,
+    F: Fn(&Ticket) -> bool,
+{
+    let accept = |key: &TicketKey| filter(&Ticket::from_sorted(key.to_balls(range)));
+    let keys = generate_unique_keys(rng, range, pick, game_count, true, Some(accept))?;
+
+    Ok(keys
+        .into_iter()
+        .map(|key| Ticket::from_sorted(key.to_balls(range)))
+        .collect())
+}
+
+/// Draw `game_count` distinct keys, the part shared by the unique generators.
+///
+/// Checks that enough combinations exist, then draws keys until there are
+/// enough or the attempt budget runs out. The budget grows as the request
+/// approaches C(n, k), and is always the largest when `accept` is given,
+/// since a filter may reject most candidates.
+///
+/// With `keep_sequence` the keys come back in generation order, so a seeded
+/// RNG reproduces the output; otherwise in no particular order.
+///
+/// # Errors
+///
+/// Returns `LottoError::TooManyUniqueGames` if the request exceeds C(n, k),
+/// or `LottoError::UniqueGenerationFailed` once the budget is spent.
+fn generate_unique_keys<R, F>(
+    rng: &mut R,
+    range: &BallRange,
+    pick: &PickCount,
+    game_count: &GameCount,
+    keep_sequence: bool,
+    accept: Option<F>,
+) -> crate::error::Result<Vec<TicketKey>>
+where
+    R: RandomNumberGenerator,
+    F: Fn(&TicketKey) -> bool,
+{
+    use crate::error::LottoError;
     use crate::probability::combination;
+    use crate::ticket_bitwise::BitwiseStrategy;
 
-    // Check if the requested number of unique tickets is mathematically possible
-    let max_possible = combination(range.size(), pick.value())?;
+    // Check if the requested number of unique tickets is mathematically possible.
+    // Beyond u128 every request is feasible (game_count is a usize).
+    let max_possible = match combination(range.size(), pick.value()) {
+        Ok(max_possible) => max_possible,
+        Err(LottoError::CalculationOverflow { .. }) => u128::MAX,
+        Err(e) => return Err(e),
+    };
 
     if (game_count.value() as u128) > max_possible {
-        return Err(crate::error::LottoError::TooManyUniqueGames {
+        return Err(LottoError::TooManyUniqueGames {
             requested: game_count.value(),
             maximum: max_possible,
         });
     }
 
-    // Use TicketKey for efficient uniqueness checking (smaller, faster hashing)
-    let mut ticket_keys = HashSet::with_capacity(game_count.value());
-
     // Calculate a reasonable maximum number of attempts
     // For small ratios (requested/possible), this is generous
     // For large ratios (approaching maximum), we need many more attempts
     let requested = game_count.value() as u128;
-    let max_attempts = if requested * 2 < max_possible {
+    let max_attempts = if accept.is_none() && requested * 2 < max_possible {
         // ratio < 0.5
         game_count.value() * 100
-    } else if requested * 10 < max_possible * 8 {
+    } else if accept.is_none() && requested * 10 < max_possible * 8 {
         // ratio < 0.8
         game_count.value() * 1000
     } else {
         game_count.value() * 10000
     };
 
+    // Use TicketKey for efficient uniqueness checking (smaller, faster hashing)
+    let mut ticket_keys = HashSet::with_capacity(game_count.value());
+    let mut keys = Vec::with_capacity(if keep_sequence { game_count.value() } else { 0 });
     let mut attempts = 0;
 
     // Hoist strategy selection outside the loop (doesn't change per iteration)
-    use crate::ticket_bitwise::BitwiseStrategy;
     let strategy = BitwiseStrategy::select(range)?;
 
     while ticket_keys.len() < game_count.value() {
         if attempts >= max_attempts {
-            return Err(crate::error::LottoError::UniqueGenerationFailed {
+            return Err(LottoError::UniqueGenerationFailed {
                 requested: game_count.value(),
                 generated: ticket_keys.len(),
             });
         }
+        attempts += 1;
 
-        // Generate TicketKey using selected strategy
         let key = strategy.generate(range, *pick, rng)?;
-        ticket_keys.insert(key);
-        attempts += 1;
+        if let Some(accept) = &accept {
+            // Skip the filter for keys already taken
+            if ticket_keys.contains(&key) || !accept(&key) {
+                continue;
+            }
+        }
+        if ticket_keys.insert(key) && keep_sequence {
+            keys.push(key);
+        }
     }
 
-    // Convert TicketKey back to Ticket only at the end
-    // Use from_sorted since to_balls() returns pre-sorted Vec
-    Ok(ticket_keys
-        .into_iter()
-        .map(|key| Ticket::from_sorted(key.to_balls(range)))
-        .collect())
+    if !keep_sequence {
+        keys = ticket_keys.into_iter().collect();
+    }
+    Ok(keys)
 }
 
-/// Generate multiple unique tickets using HashSet<Ticket> directly (old implementation).
-///
-/// This function is provided for benchmarking comparison purposes only.
-/// The main implementation uses TicketKey for better performance.
-///
-/// # Performance
-///
-/// This approach is slower because:
-/// - Ticket contains Vec<BallNumber> which requires heap allocation
-/// - Hashing a Vec is slower than hashing u64/u128
-/// - Worse cache locality due to pointer indirection
 #[doc(hidden)]
 pub fn generate_unique_tickets_with_ticket_hashset<R: RandomNumberGenerator>(
     rng: &mut R,
@@ -402,6 +641,68 @@ mod tests {
         assert_eq!(unique_tickets.len(), 20);
     }
 
+    #[test]
+    fn test_generate_unique_tickets_is_reproducible_from_seed() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(60)).unwrap();
+        let pick = PickCount::new(6, &range).unwrap();
+        let count = GameCount::new(50).unwrap();
+
+        let first = generate_unique_tickets(&mut StdRng::seed_from_u64(7), &range, &pick, &count);
+        let second = generate_unique_tickets(&mut StdRng::seed_from_u64(7), &range, &pick, &count);
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn test_generate_unique_tickets_ordered_sorts_same_tickets() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(60)).unwrap();
+        let pick = PickCount::new(6, &range).unwrap();
+        let count = GameCount::new(50).unwrap();
+        let generate = |order| {
+            let mut rng = StdRng::seed_from_u64(11);
+            generate_unique_tickets_ordered(&mut rng, &range, &pick, &count, order).unwrap()
+        };
+
+        let generated = generate(OutputOrder::Generation);
+        let lexicographic = generate(OutputOrder::Lexicographic);
+        let ranked = generate(OutputOrder::Rank);
+
+        assert!(lexicographic.windows(2).all(|pair| pair[0] < pair[1]));
+        let ranks: Vec<_> = ranked
+            .iter()
+            .map(|ticket| crate::rank::rank(ticket, &range).unwrap())
+            .collect();
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut sorted = generated.clone();
+        sort_tickets(&mut sorted, OutputOrder::Lexicographic, &range);
+        assert_eq!(sorted, lexicographic);
+        sort_tickets(&mut sorted, OutputOrder::Rank, &range);
+        assert_eq!(sorted, ranked);
+    }
+
+    #[test]
+    fn test_output_order_parses_and_displays() {
+        for order in [
+            OutputOrder::Generation,
+            OutputOrder::Lexicographic,
+            OutputOrder::Rank,
+        ] {
+            assert_eq!(order.to_string().parse::<OutputOrder>().unwrap(), order);
+        }
+        assert_eq!(
+            "LEX".parse::<OutputOrder>().unwrap(),
+            OutputOrder::Lexicographic
+        );
+        assert!(matches!(
+            "random".parse::<OutputOrder>(),
+            Err(crate::error::LottoError::ParseError(_))
+        ));
+    }
+
     #[test]
     fn test_generate_unique_tickets_small_range() {
         let mut rng = rand::rng();
@@ -426,6 +727,18 @@ mod tests {
         assert_eq!(result.unwrap().len(), 10);
     }
 
+    #[test]
+    fn test_generate_unique_tickets_beyond_u128_combinations() {
+        let mut rng = rand::rng();
+        let range = BallRange::new(BallNumber::new(0), BallNumber::new(255)).unwrap();
+        let pick = PickCount::new(127, &range).unwrap();
+        let count = GameCount::new(3).unwrap();
+
+        let tickets = generate_unique_tickets(&mut rng, &range, &pick, &count).unwrap();
+        assert_eq!(tickets.len(), 3);
+        assert!(tickets.iter().all(|t| t.len() == 127));
+    }
+
     #[test]
     fn test_generate_unique_tickets_too_many() {
         let mut rng = rand::rng();
@@ -440,4 +753,39 @@ mod tests {
             Err(crate::error::LottoError::TooManyUniqueGames { .. })
         ));
     }
+
+    #[test]
+    fn test_generate_unique_tickets_filtered_applies_filter() {
+        let mut rng = rand::rng();
+        let range = BallRange::mega_sena();
+        let pick = PickCount::new(6, &range).unwrap();
+        let count = GameCount::new(50).unwrap();
+
+        let tickets = generate_unique_tickets_filtered(&mut rng, &range, &pick, &count, |t| {
+            t.balls().iter().all(|b| b.value() % 2 == 0)
+        })
+        .unwrap();
+        assert_eq!(tickets.len(), 50);
+        assert_eq!(tickets.iter().collect::<HashSet<_>>().len(), 50);
+        assert!(
+            tickets
+                .iter()
+                .flat_map(|t| t.balls())
+                .all(|b| b.value() % 2 == 0)
+        );
+    }
+
+    #[test]
+    fn test_generate_unique_tickets_filtered_impossible_filter() {
+        let mut rng = rand::rng();
+        let range = BallRange::new(BallNumber::new(1), BallNumber::new(10)).unwrap();
+        let pick = PickCount::new(3, &range).unwrap();
+        let count = GameCount::new(2).unwrap();
+
+        let result = generate_unique_tickets_filtered(&mut rng, &range, &pick, &count, |_| false);
+        assert!(matches!(
+            result,
+            Err(crate::error::LottoError::UniqueGenerationFailed { generated: 0, .. })
+        ));
+    }
 }
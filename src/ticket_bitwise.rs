@@ -12,8 +12,10 @@
 //!   - Example: Range 200-255 has 56 values → uses u64
 //! - **u128 bitmap**: For ranges with 65-128 values
 //!   - Example: Lotomania (0-99) has 100 values → uses u128
-//! - **Vec<u64> bitmap**: For ranges with > 128 values
-//!   - Example: Range 0-255 has 256 values → uses Vec<u64>
+//! - **[u64; 4] bitmap**: For ranges with > 128 values
+//!   - Example: Range 0-255 has 256 values → uses [u64; 4]
+//!
+//! Every strategy keeps its bitmap inline, so no strategy allocates.
 //!
 //! # Performance
 //!
@@ -35,8 +37,8 @@ pub enum BitwiseStrategy {
     U64,
     /// Use u128 bitmap for ranges ≤ 128
     U128,
-    /// Use Vec<u64> bitmap for ranges ≤ 512
-    VecU64,
+    /// Use [u64; 4] bitmap for ranges ≤ 256
+    U256,
 }
 
 impl BitwiseStrategy {
@@ -57,8 +59,8 @@ impl BitwiseStrategy {
         } else if size <= 128 {
             Ok(Self::U128)
         } else {
-            // For ranges larger than 128 values
-            Ok(Self::VecU64)
+            // For ranges larger than 128 values (u8 balls never exceed 256)
+            Ok(Self::U256)
         }
     }
 
@@ -82,7 +84,7 @@ impl BitwiseStrategy {
         match self {
             Self::U64 => generate_ticketkey_u64_bitmap(range, count, rng),
             Self::U128 => generate_ticketkey_u128_bitmap(range, count, rng),
-            Self::VecU64 => generate_ticketkey_vec_bitmap(range, count, rng),
+            Self::U256 => generate_ticketkey_u256_bitmap(range, count, rng),
         }
     }
 }
@@ -222,7 +224,7 @@ pub fn generate_ticket_u128_bitmap<R: RandomNumberGenerator>(
 /// A vector of unique ball numbers or an error
 #[deprecated(
     since = "1.2.0",
-    note = "Use generate_ticketkey_vec_bitmap() instead for better performance"
+    note = "Use generate_ticketkey_u256_bitmap() instead for better performance"
 )]
 pub fn generate_ticket_vec_bitmap<R: RandomNumberGenerator>(
     range: &BallRange,
@@ -262,7 +264,7 @@ pub fn generate_ticket_vec_bitmap<R: RandomNumberGenerator>(
 /// Automatically selects the best bitmap implementation based on the range size:
 /// - u64 for ranges ≤ 64
 /// - u128 for ranges ≤ 128
-/// - Vec<u64> for ranges ≤ 512
+/// - [u64; 4] for ranges ≤ 256
 ///
 /// # Arguments
 ///
@@ -272,7 +274,7 @@ pub fn generate_ticket_vec_bitmap<R: RandomNumberGenerator>(
 ///
 /// # Returns
 ///
-/// A vector of unique ball numbers or an error
+/// A vector of unique ball numbers in ascending order, or an error
 ///
 /// # Examples
 ///
@@ -292,19 +294,12 @@ pub fn generate_ticket_vec_bitmap<R: RandomNumberGenerator>(
     since = "1.2.0",
     note = "Use generate_ticketkey_bitwise() instead for better performance"
 )]
-#[allow(deprecated)]
 pub fn generate_ticket_bitwise<R: RandomNumberGenerator>(
     range: &BallRange,
     count: PickCount,
     rng: &mut R,
 ) -> Result<Vec<BallNumber>, LottoError> {
-    let strategy = BitwiseStrategy::select(range)?;
-
-    match strategy {
-        BitwiseStrategy::U64 => generate_ticket_u64_bitmap(range, count, rng),
-        BitwiseStrategy::U128 => generate_ticket_u128_bitmap(range, count, rng),
-        BitwiseStrategy::VecU64 => generate_ticket_vec_bitmap(range, count, rng),
-    }
+    generate_ticketkey_bitwise(range, count, rng).map(|key| key.to_balls(range))
 }
 
 /// Generates a lottery ticket using u64 bitmap, returning TicketKey directly.
@@ -447,10 +442,11 @@ pub fn generate_ticketkey_u128_bitmap<R: RandomNumberGenerator>(
     Ok(TicketKey::U128(bitmap))
 }
 
-/// Generates a lottery ticket using Vec<u64> bitmap, returning TicketKey directly.
+/// Generates a lottery ticket using a [u64; 4] bitmap, returning TicketKey directly.
 ///
 /// This is the optimized version that avoids creating intermediate Vec<BallNumber>.
 /// Use this when you need TicketKey for HashSet-based uniqueness checking.
+/// The bitmap lives on the stack, so wide ranges allocate no more than Mega-Sena.
 ///
 /// # Arguments
 ///
@@ -460,8 +456,8 @@ pub fn generate_ticketkey_u128_bitmap<R: RandomNumberGenerator>(
 ///
 /// # Returns
 ///
-/// A TicketKey::VecU64 containing the bitmap
-pub fn generate_ticketkey_vec_bitmap<R: RandomNumberGenerator>(
+/// A TicketKey::U256 containing the bitmap
+pub fn generate_ticketkey_u256_bitmap<R: RandomNumberGenerator>(
     range: &BallRange,
     count: PickCount,
     rng: &mut R,
@@ -471,9 +467,7 @@ pub fn generate_ticketkey_vec_bitmap<R: RandomNumberGenerator>(
     let picks = count.value();
     let range_size = range.size();
 
-    // Calculate bitmap size
-    let words_needed = range_size.div_ceil(64);
-    let mut bitmap: Vec<u64> = vec![0; words_needed];
+    let mut bitmap = [0u64; 4];
     let mut picked_count = 0;
 
     while picked_count < picks {
@@ -497,7 +491,8 @@ pub fn generate_ticketkey_vec_bitmap<R: RandomNumberGenerator>(
         picks, actual_count
     );
 
-    // Validate no bits outside range in last word
+    // Validate no bits outside range: in the last used word and beyond it
+    let words_needed = range_size.div_ceil(64);
     let remaining_bits = range_size % 64;
     if remaining_bits > 0 {
         let last_word = bitmap[words_needed - 1];
@@ -508,13 +503,30 @@ pub fn generate_ticketkey_vec_bitmap<R: RandomNumberGenerator>(
             "bitmap has bits set outside valid range in last word"
         );
     }
+    debug_assert!(
+        bitmap[words_needed..].iter().all(|&word| word == 0),
+        "bitmap has bits set beyond the range"
+    );
+
+    Ok(TicketKey::U256(bitmap))
+}
 
-    Ok(TicketKey::VecU64(bitmap))
+/// Generates a lottery ticket using a wide bitmap, returning TicketKey directly.
+///
+/// **DEPRECATED**: Use [`generate_ticketkey_u256_bitmap`] instead; wide keys
+/// are no longer backed by a `Vec<u64>`.
+#[deprecated(since = "1.5.0", note = "Use generate_ticketkey_u256_bitmap() instead")]
+pub fn generate_ticketkey_vec_bitmap<R: RandomNumberGenerator>(
+    range: &BallRange,
+    count: PickCount,
+    rng: &mut R,
+) -> Result<TicketKey, LottoError> {
+    generate_ticketkey_u256_bitmap(range, count, rng)
 }
 
 /// Unified wrapper that generates TicketKey using optimal bitwise strategy.
 ///
-/// Automatically selects U64, U128, or VecU64 based on range size.
+/// Automatically selects U64, U128, or U256 based on range size.
 /// This is the preferred function for generating tickets with TicketKey.
 ///
 /// # Example
@@ -542,7 +554,7 @@ pub fn generate_ticketkey_bitwise<R: RandomNumberGenerator>(
     match strategy {
         BitwiseStrategy::U64 => generate_ticketkey_u64_bitmap(range, count, rng),
         BitwiseStrategy::U128 => generate_ticketkey_u128_bitmap(range, count, rng),
-        BitwiseStrategy::VecU64 => generate_ticketkey_vec_bitmap(range, count, rng),
+        BitwiseStrategy::U256 => generate_ticketkey_u256_bitmap(range, count, rng),
     }
 }
 
@@ -566,10 +578,10 @@ mod tests {
     }
 
     #[test]
-    fn test_strategy_selection_vec_u64() {
+    fn test_strategy_selection_u256() {
         let range = BallRange::new(BallNumber::new(1), BallNumber::new(200)).unwrap();
         let strategy = BitwiseStrategy::select(&range).unwrap();
-        assert_eq!(strategy, BitwiseStrategy::VecU64);
+        assert_eq!(strategy, BitwiseStrategy::U256);
     }
 
     #[test]
@@ -578,7 +590,7 @@ mod tests {
         let range = BallRange::new(BallNumber::new(1), BallNumber::new(255)).unwrap();
         let strategy = BitwiseStrategy::select(&range);
         assert!(strategy.is_ok());
-        assert_eq!(strategy.unwrap(), BitwiseStrategy::VecU64);
+        assert_eq!(strategy.unwrap(), BitwiseStrategy::U256);
     }
 
     #[test]
@@ -630,12 +642,12 @@ mod tests {
     }
 
     #[test]
-    fn test_vec_bitmap_large_range() {
+    fn test_u256_bitmap_large_range() {
         let range = BallRange::new(BallNumber::new(1), BallNumber::new(200)).unwrap();
         let count = PickCount::new(10, &range).unwrap();
         let mut rng = rand::rng();
 
-        let key = generate_ticketkey_vec_bitmap(&range, count, &mut rng).unwrap();
+        let key = generate_ticketkey_u256_bitmap(&range, count, &mut rng).unwrap();
         let ticket = key.to_balls(&range);
 
         assert_eq!(ticket.len(), 10);
@@ -671,7 +683,7 @@ mod tests {
                 .unwrap();
         assert_eq!(key2.count_balls(), 50);
 
-        // Test Vec<u64> strategy
+        // Test [u64; 4] strategy
         let range3 = BallRange::new(BallNumber::new(1), BallNumber::new(200)).unwrap();
         let key3 =
             generate_ticketkey_bitwise(&range3, PickCount::new(10, &range3).unwrap(), &mut rng)
@@ -855,12 +867,12 @@ mod tests {
     }
 
     #[test]
-    fn test_ticketkey_vec_validates_bit_count() {
+    fn test_ticketkey_u256_validates_bit_count() {
         let range = BallRange::new(BallNumber::new(0), BallNumber::new(200)).unwrap();
         let pick = PickCount::new(15, &range).unwrap();
         let mut rng = rand::rng();
 
-        let key = generate_ticketkey_vec_bitmap(&range, pick, &mut rng).unwrap();
+        let key = generate_ticketkey_u256_bitmap(&range, pick, &mut rng).unwrap();
         assert_eq!(key.count_balls(), 15);
     }
 
@@ -6,11 +6,12 @@
 //!
 //! # Performance
 //!
-//! - **Smaller memory footprint**: u64/u128 vs Vec allocations
+//! - **Smaller memory footprint**: u64/u128/[u64; 4] inline, never heap-allocated
 //! - **Faster hashing**: Direct integer hash vs hashing Vec contents
 //! - **Better cache locality**: Contiguous bits vs scattered heap allocations
 
 use crate::newtypes::{BallNumber, BallRange};
+use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 
 /// Compact bitmap representation of a lottery ticket.
@@ -18,15 +19,19 @@ use std::hash::{Hash, Hasher};
 /// Uses the most efficient storage based on range size:
 /// - `U64`: For ranges with ≤ 64 values
 /// - `U128`: For ranges with 65-128 values  
-/// - `VecU64`: For ranges with > 128 values
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// - `U256`: For ranges with > 128 values (balls are u8, so 256 bits suffice)
+///
+/// Keys order by their bitmap read as one number, which for tickets of the
+/// same game and size is the order of their combinatorial
+/// [rank](crate::rank).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TicketKey {
     /// Bitmap using single u64 (up to 64 values)
     U64(u64),
     /// Bitmap using single u128 (up to 128 values)
     U128(u128),
-    /// Bitmap using Vec<u64> for larger ranges
-    VecU64(Vec<u64>),
+    /// Bitmap using four little-endian u64 words for larger ranges
+    U256([u64; 4]),
 }
 
 impl TicketKey {
@@ -59,15 +64,14 @@ impl TicketKey {
             }
             TicketKey::U128(bitmap)
         } else {
-            let vec_size = size.div_ceil(64);
-            let mut bitmap = vec![0u64; vec_size];
+            let mut bitmap = [0u64; 4];
             for &ball in balls {
                 let offset = (ball.value() - min) as usize;
                 let idx = offset / 64;
                 let bit = offset % 64;
                 bitmap[idx] |= 1u64 << bit;
             }
-            TicketKey::VecU64(bitmap)
+            TicketKey::U256(bitmap)
         }
     }
 
@@ -110,7 +114,7 @@ impl TicketKey {
                     bits &= bits - 1; // Clear lowest set bit
                 }
             }
-            TicketKey::VecU64(bitmap) => {
+            TicketKey::U256(bitmap) => {
                 // Iterate through each word, using trailing_zeros for set bits
                 for (idx, &word) in bitmap.iter().enumerate() {
                     let mut bits = word;
@@ -130,12 +134,184 @@ impl TicketKey {
         balls
     }
 
+    /// Get the index of the representation, for ordering keys of different ranges.
+    fn discriminant(&self) -> u8 {
+        match self {
+            TicketKey::U64(_) => 0,
+            TicketKey::U128(_) => 1,
+            TicketKey::U256(_) => 2,
+        }
+    }
+
     /// Count the number of set bits (balls in the ticket).
     pub fn count_balls(&self) -> usize {
         match self {
             TicketKey::U64(bitmap) => bitmap.count_ones() as usize,
             TicketKey::U128(bitmap) => bitmap.count_ones() as usize,
-            TicketKey::VecU64(bitmap) => bitmap.iter().map(|w| w.count_ones() as usize).sum(),
+            TicketKey::U256(bitmap) => bitmap.iter().map(|w| w.count_ones() as usize).sum(),
+        }
+    }
+
+    /// Check if the key has no balls.
+    pub fn is_empty(&self) -> bool {
+        self.words().iter().all(|&word| word == 0)
+    }
+
+    /// Check if a ball is in the ticket.
+    ///
+    /// # Arguments
+    ///
+    /// * `ball` - The ball to look up
+    /// * `range` - The range the key was built with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange};
+    /// use lotto_quick_pick::ticket_key::TicketKey;
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let key = TicketKey::from_balls(&[BallNumber::new(4), BallNumber::new(60)], &range);
+    /// assert!(key.contains(BallNumber::new(60), &range));
+    /// assert!(!key.contains(BallNumber::new(5), &range));
+    /// assert!(!key.contains(BallNumber::new(0), &range));
+    /// ```
+    pub fn contains(&self, ball: BallNumber, range: &BallRange) -> bool {
+        if !range.contains(ball) {
+            return false;
+        }
+        let offset = (ball.value() - range.start().value()) as usize;
+        match self {
+            TicketKey::U64(bitmap) => offset < 64 && bitmap & (1u64 << offset) != 0,
+            TicketKey::U128(bitmap) => offset < 128 && bitmap & (1u128 << offset) != 0,
+            TicketKey::U256(bitmap) => bitmap[offset / 64] & (1u64 << (offset % 64)) != 0,
+        }
+    }
+
+    /// Count the balls shared with another key, such as a draw.
+    ///
+    /// Both keys must come from the same range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange};
+    /// use lotto_quick_pick::ticket_key::TicketKey;
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let key = |balls: [u8; 6]| TicketKey::from_balls(&balls.map(BallNumber::new), &range);
+    /// let ticket = key([4, 8, 15, 16, 23, 42]);
+    /// let draw = key([1, 8, 15, 30, 42, 60]);
+    /// assert_eq!(ticket.matches(&draw), 3);
+    /// ```
+    pub fn matches(&self, other: &Self) -> usize {
+        match (self, other) {
+            (TicketKey::U64(a), TicketKey::U64(b)) => (a & b).count_ones() as usize,
+            (TicketKey::U128(a), TicketKey::U128(b)) => (a & b).count_ones() as usize,
+            _ => self
+                .words()
+                .iter()
+                .zip(other.words())
+                .map(|(a, b)| (a & b).count_ones() as usize)
+                .sum(),
+        }
+    }
+
+    /// Get the balls in both keys.
+    ///
+    /// Both keys must come from the same range.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Get the balls in either key.
+    ///
+    /// Both keys must come from the same range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lotto_quick_pick::newtypes::{BallNumber, BallRange};
+    /// use lotto_quick_pick::ticket_key::TicketKey;
+    ///
+    /// let range = BallRange::mega_sena();
+    /// let key = |balls: &[u8]| {
+    ///     TicketKey::from_balls(&balls.iter().map(|&b| BallNumber::new(b)).collect::<Vec<_>>(), &range)
+    /// };
+    /// let (a, b) = (key(&[1, 2, 3]), key(&[3, 4]));
+    /// assert_eq!(a.union(&b), key(&[1, 2, 3, 4]));
+    /// assert_eq!(a.intersection(&b), key(&[3]));
+    /// assert_eq!(a.difference(&b), key(&[1, 2]));
+    /// assert!(key(&[1, 3]).is_subset(&a));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Get the balls in this key but not in `other`.
+    ///
+    /// Both keys must come from the same range.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Check if every ball of this key is also in `other`.
+    ///
+    /// Both keys must come from the same range.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TicketKey::U64(a), TicketKey::U64(b)) => a & !b == 0,
+            (TicketKey::U128(a), TicketKey::U128(b)) => a & !b == 0,
+            _ => self
+                .words()
+                .iter()
+                .zip(other.words())
+                .all(|(a, b)| a & !b == 0),
+        }
+    }
+
+    /// Check if every ball of `other` is also in this key.
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Check if the keys share no ball.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.matches(other) == 0
+    }
+
+    /// Spread the bitmap over four 64-bit words (balls are u8, so 256 bits suffice).
+    fn words(&self) -> [u64; 4] {
+        match self {
+            TicketKey::U64(bitmap) => [*bitmap, 0, 0, 0],
+            TicketKey::U128(bitmap) => [*bitmap as u64, (*bitmap >> 64) as u64, 0, 0],
+            TicketKey::U256(bitmap) => *bitmap,
+        }
+    }
+
+    /// Apply a bitwise operation word by word.
+    ///
+    /// Keys of different representations give a key of the wider one.
+    fn combine(&self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Self {
+        match (self, other) {
+            (TicketKey::U64(a), TicketKey::U64(b)) => TicketKey::U64(op(*a, *b)),
+            _ => {
+                let (a, b) = (self.words(), other.words());
+                let words: [u64; 4] = std::array::from_fn(|i| op(a[i], b[i]));
+                match self.discriminant().max(other.discriminant()) {
+                    1 => TicketKey::U128(words[0] as u128 | (words[1] as u128) << 64),
+                    _ => TicketKey::U256(words),
+                }
+            }
+        }
+    }
+
+    /// Flip the bit at an offset from the start of the range.
+    pub(crate) fn toggle(&mut self, offset: usize) {
+        match self {
+            TicketKey::U64(bitmap) => *bitmap ^= 1u64 << offset,
+            TicketKey::U128(bitmap) => *bitmap ^= 1u128 << offset,
+            TicketKey::U256(bitmap) => bitmap[offset / 64] ^= 1u64 << (offset % 64),
         }
     }
 }
@@ -151,7 +327,7 @@ impl Hash for TicketKey {
                 1u8.hash(state); // Discriminant
                 bitmap.hash(state);
             }
-            TicketKey::VecU64(bitmap) => {
+            TicketKey::U256(bitmap) => {
                 2u8.hash(state); // Discriminant
                 bitmap.hash(state);
             }
@@ -159,6 +335,25 @@ impl Hash for TicketKey {
     }
 }
 
+impl Ord for TicketKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (TicketKey::U64(a), TicketKey::U64(b)) => a.cmp(b),
+            (TicketKey::U128(a), TicketKey::U128(b)) => a.cmp(b),
+            // Words are little-endian, so compare from the most significant
+            (TicketKey::U256(a), TicketKey::U256(b)) => a.iter().rev().cmp(b.iter().rev()),
+            // Keys of different ranges: order by representation
+            _ => self.discriminant().cmp(&other.discriminant()),
+        }
+    }
+}
+
+impl PartialOrd for TicketKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,7 +398,7 @@ mod tests {
     }
 
     #[test]
-    fn test_ticket_key_vec_u64_round_trip() {
+    fn test_ticket_key_u256_round_trip() {
         let range = BallRange::new(BallNumber::new(0), BallNumber::new(200)).unwrap();
         let balls = vec![
             BallNumber::new(0),
@@ -214,7 +409,7 @@ mod tests {
         ];
 
         let key = TicketKey::from_balls(&balls, &range);
-        assert!(matches!(key, TicketKey::VecU64(_)));
+        assert!(matches!(key, TicketKey::U256(_)));
 
         let recovered = key.to_balls(&range);
         assert_eq!(recovered, balls);
@@ -289,4 +484,62 @@ mod tests {
         assert_eq!(recovered[1].value(), 30);
         assert_eq!(recovered[2].value(), 60);
     }
+
+    #[test]
+    fn test_set_algebra_in_every_representation() {
+        for (start, end) in [(1u8, 60u8), (0, 99), (0, 255)] {
+            let range = BallRange::new(BallNumber::new(start), BallNumber::new(end)).unwrap();
+            let key = |balls: &[u8]| {
+                let balls: Vec<_> = balls.iter().map(|&b| BallNumber::new(b)).collect();
+                TicketKey::from_balls(&balls, &range)
+            };
+            let (a, b) = (key(&[1, 5, 9, 60]), key(&[5, 9, 30]));
+
+            assert_eq!(a.matches(&b), 2);
+            assert_eq!(a.intersection(&b), key(&[5, 9]));
+            assert_eq!(a.union(&b), key(&[1, 5, 9, 30, 60]));
+            assert_eq!(a.difference(&b), key(&[1, 60]));
+            assert!(key(&[5, 60]).is_subset(&a));
+            assert!(!b.is_subset(&a));
+            assert!(a.is_superset(&key(&[1])));
+            assert!(a.difference(&a).is_empty());
+            assert!(a.is_disjoint(&key(&[2, 3])));
+            assert!(a.contains(BallNumber::new(60), &range));
+            assert!(!a.contains(BallNumber::new(30), &range));
+        }
+    }
+
+    #[test]
+    fn test_mixed_representations_widen() {
+        let narrow = TicketKey::U64(0b1010);
+        let wide = TicketKey::U256([0b0110, 0, 1, 0]);
+        assert_eq!(narrow.matches(&wide), 1);
+        assert_eq!(narrow.union(&wide), TicketKey::U256([0b1110, 0, 1, 0]));
+        assert_eq!(
+            TicketKey::U128(1 << 100).intersection(&TicketKey::U64(1)),
+            TicketKey::U128(0)
+        );
+    }
+
+    #[test]
+    fn test_ticket_key_order_matches_rank() {
+        use crate::newtypes::{GameCount, PickCount};
+        use crate::rank::rank;
+
+        for (start, end) in [(1u8, 60u8), (0, 99), (0, 255)] {
+            let range = BallRange::new(BallNumber::new(start), BallNumber::new(end)).unwrap();
+            let pick = PickCount::new(6, &range).unwrap();
+            let mut tickets = crate::ticket::generate_unique_tickets(
+                &mut rand::rng(),
+                &range,
+                &pick,
+                &GameCount::new(50).unwrap(),
+            )
+            .unwrap();
+            tickets.sort_by_key(|ticket| TicketKey::from_balls(ticket.balls(), &range));
+
+            let ranks: Vec<_> = tickets.iter().map(|t| rank(t, &range).unwrap()).collect();
+            assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
 }
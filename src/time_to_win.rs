@@ -0,0 +1,376 @@
+//! Time-to-win modeling.
+//!
+//! Each draw is an independent trial, so the number of draws until the
+//! first win follows a geometric distribution. This module turns the exact
+//! odds from [`crate::probability`] into expected and median waiting times,
+//! measured in draws and, given a [`DrawSchedule`], in calendar time.
+
+use crate::bigint::BigUint;
+use crate::error::{LottoError, Result};
+use crate::probability::{Probability, calculate_probability_for_bet};
+
+/// Average number of weeks in a year (365.25 days).
+pub const WEEKS_PER_YEAR: f64 = 365.25 / 7.0;
+
+/// How often a player takes part in a game.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::time_to_win::DrawSchedule;
+///
+/// // Three draws a week, two tickets per draw
+/// let schedule = DrawSchedule::new(3.0, 2).unwrap();
+/// assert!((schedule.draws_per_year() - 156.54).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawSchedule {
+    draws_per_week: f64,
+    tickets_per_draw: usize,
+}
+
+impl DrawSchedule {
+    /// Create a new DrawSchedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `draws_per_week` - Number of draws the player enters each week
+    /// * `tickets_per_draw` - Number of distinct tickets played in each draw
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidAmount` if `draws_per_week` is not a positive finite
+    /// number, or `ZeroGames` if `tickets_per_draw` is zero.
+    pub fn new(draws_per_week: f64, tickets_per_draw: usize) -> Result<Self> {
+        if !draws_per_week.is_finite() || draws_per_week <= 0.0 {
+            return Err(LottoError::InvalidAmount {
+                field: "draws per week".to_string(),
+                value: draws_per_week,
+            });
+        }
+        if tickets_per_draw == 0 {
+            return Err(LottoError::ZeroGames);
+        }
+        Ok(Self {
+            draws_per_week,
+            tickets_per_draw,
+        })
+    }
+
+    /// Get the number of draws per week.
+    pub fn draws_per_week(&self) -> f64 {
+        self.draws_per_week
+    }
+
+    /// Get the number of tickets played per draw.
+    pub fn tickets_per_draw(&self) -> usize {
+        self.tickets_per_draw
+    }
+
+    /// Get the average number of draws per year.
+    pub fn draws_per_year(&self) -> f64 {
+        self.draws_per_week * WEEKS_PER_YEAR
+    }
+}
+
+/// Waiting time until the first win, for a fixed chance of winning each draw.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::time_to_win::TimeToWin;
+///
+/// let coin = TimeToWin::new(0.5, 1.0);
+/// assert_eq!(coin.expected_draws(), 2.0);
+/// assert_eq!(coin.median_draws(), 1.0);
+/// assert_eq!(coin.probability_within_draws(2.0), 0.75);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeToWin {
+    draw_probability: f64,
+    draws_per_week: f64,
+}
+
+impl TimeToWin {
+    /// Create a new TimeToWin.
+    ///
+    /// # Arguments
+    ///
+    /// * `draw_probability` - Chance of winning in a single draw (clamped to 0..=1)
+    /// * `draws_per_week` - Number of draws entered each week
+    pub fn new(draw_probability: f64, draws_per_week: f64) -> Self {
+        Self {
+            draw_probability: draw_probability.clamp(0.0, 1.0),
+            draws_per_week,
+        }
+    }
+
+    /// Get the chance of winning in a single draw.
+    pub fn draw_probability(&self) -> f64 {
+        self.draw_probability
+    }
+
+    /// Get the number of draws per week.
+    pub fn draws_per_week(&self) -> f64 {
+        self.draws_per_week
+    }
+
+    /// Expected number of draws up to and including the first win.
+    ///
+    /// Returns infinity when winning is impossible.
+    pub fn expected_draws(&self) -> f64 {
+        1.0 / self.draw_probability
+    }
+
+    /// Number of draws after which a win is at least 50% likely.
+    ///
+    /// Returns infinity when winning is impossible.
+    pub fn median_draws(&self) -> f64 {
+        self.draws_for_probability(0.5)
+    }
+
+    /// Smallest number of draws whose chance of containing a win is at
+    /// least `target` (clamped to 0..=1).
+    ///
+    /// Returns infinity when the target cannot be reached.
+    pub fn draws_for_probability(&self, target: f64) -> f64 {
+        let target = target.clamp(0.0, 1.0);
+        if target == 0.0 {
+            return 0.0;
+        }
+        if self.draw_probability == 1.0 {
+            return 1.0;
+        }
+        // ln_1p keeps tiny per-draw probabilities from rounding to zero
+        ((-target).ln_1p() / (-self.draw_probability).ln_1p())
+            .ceil()
+            .max(1.0)
+    }
+
+    /// Chance of at least one win within `draws` draws.
+    pub fn probability_within_draws(&self, draws: f64) -> f64 {
+        if draws <= 0.0 {
+            return 0.0;
+        }
+        -(draws * (-self.draw_probability).ln_1p()).exp_m1()
+    }
+
+    /// Expected waiting time until the first win, in years.
+    pub fn expected_years(&self) -> f64 {
+        self.expected_draws() / self.draws_per_year()
+    }
+
+    /// Median waiting time until the first win, in years.
+    pub fn median_years(&self) -> f64 {
+        self.median_draws() / self.draws_per_year()
+    }
+
+    /// Chance of at least one win within `years` years.
+    pub fn probability_within_years(&self, years: f64) -> f64 {
+        self.probability_within_draws((years * self.draws_per_year()).floor())
+    }
+
+    fn draws_per_year(&self) -> f64 {
+        self.draws_per_week * WEEKS_PER_YEAR
+    }
+}
+
+/// Model the waiting time until matching exactly `match_count` balls.
+///
+/// With several tickets per draw, winning the jackpot with any of them are
+/// mutually exclusive events, so their chances add up exactly. For lower
+/// tiers the tickets are treated as independent, which slightly
+/// overestimates the waiting time for tickets that share balls.
+///
+/// # Arguments
+///
+/// * `total_balls` - Total number of balls in the lottery
+/// * `pick_count` - Number of balls picked per game
+/// * `match_count` - Number of balls to match
+/// * `schedule` - How often and with how many tickets the player plays
+///
+/// # Returns
+///
+/// The waiting time model, or an error if `match_count` exceeds `pick_count`.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::time_to_win::{DrawSchedule, time_to_win};
+///
+/// // Mega-Sena jackpot, one ticket in each of three weekly draws
+/// let schedule = DrawSchedule::new(3.0, 1).unwrap();
+/// let wait = time_to_win(60, 6, 6, &schedule).unwrap();
+/// assert!((wait.expected_draws() - 50_063_860.0).abs() < 1.0);
+/// assert!(wait.expected_years() > 300_000.0);
+/// ```
+pub fn time_to_win(
+    total_balls: usize,
+    pick_count: usize,
+    match_count: usize,
+    schedule: &DrawSchedule,
+) -> Result<TimeToWin> {
+    time_to_win_for_bet(total_balls, pick_count, pick_count, match_count, schedule)
+}
+
+/// Model the waiting time until a bet of `bet_size` balls covers exactly
+/// `match_count` of the `draw_size` drawn balls.
+///
+/// Multi-number bets can overlap even when they are distinct, so several
+/// of them are always treated as independent.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::time_to_win::{DrawSchedule, time_to_win_for_bet};
+///
+/// // A 15-number Mega-Sena bet covers C(15, 6) = 5005 simple bets
+/// let schedule = DrawSchedule::new(3.0, 1).unwrap();
+/// let wait = time_to_win_for_bet(60, 15, 6, 6, &schedule).unwrap();
+/// assert!((wait.expected_draws() - 50_063_860.0 / 5_005.0).abs() < 1e-6);
+/// ```
+pub fn time_to_win_for_bet(
+    total_balls: usize,
+    bet_size: usize,
+    draw_size: usize,
+    match_count: usize,
+    schedule: &DrawSchedule,
+) -> Result<TimeToWin> {
+    let (favorable, total) =
+        calculate_probability_for_bet(total_balls, bet_size, draw_size, match_count)?;
+    let tickets = schedule.tickets_per_draw;
+
+    let draw_probability = if match_count == draw_size && bet_size == draw_size {
+        let favorable = &favorable * &BigUint::from(tickets);
+        if favorable >= total {
+            1.0
+        } else {
+            Probability::reduced(favorable, total).to_f64()
+        }
+    } else {
+        let ticket = Probability::reduced(favorable, total).to_f64();
+        -(tickets as f64 * (-ticket).ln_1p()).exp_m1()
+    };
+
+    Ok(TimeToWin::new(draw_probability, schedule.draws_per_week))
+}
+
+/// Model the waiting time using Config object.
+///
+/// # Examples
+///
+/// ```
+/// use lotto_quick_pick::{Config, time_to_win::{DrawSchedule, time_to_win_for_config}};
+///
+/// let config = Config::new(1, 1, 60, 6).unwrap();
+/// let schedule = DrawSchedule::new(2.0, 1).unwrap();
+/// let wait = time_to_win_for_config(&config, 4, &schedule).unwrap();
+/// assert!(wait.median_draws() < wait.expected_draws());
+/// ```
+pub fn time_to_win_for_config(
+    config: &crate::Config,
+    match_count: usize,
+    schedule: &DrawSchedule,
+) -> Result<TimeToWin> {
+    time_to_win(
+        config.range().size(),
+        config.pick().value(),
+        match_count,
+        schedule,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometric_moments() {
+        let wait = TimeToWin::new(0.25, 1.0);
+        assert_eq!(wait.expected_draws(), 4.0);
+        // 1 - 0.75^2 = 0.4375 < 0.5 <= 1 - 0.75^3 = 0.578125
+        assert_eq!(wait.median_draws(), 3.0);
+        assert!((wait.probability_within_draws(3.0) - 0.578125).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_impossible_and_certain_wins() {
+        let never = TimeToWin::new(0.0, 1.0);
+        assert!(never.expected_draws().is_infinite());
+        assert!(never.median_draws().is_infinite());
+        assert_eq!(never.probability_within_draws(1e9), 0.0);
+
+        let always = TimeToWin::new(1.0, 1.0);
+        assert_eq!(always.expected_draws(), 1.0);
+        assert_eq!(always.median_draws(), 1.0);
+        assert_eq!(always.probability_within_draws(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_tiny_probability_keeps_precision() {
+        let wait = TimeToWin::new(1e-20, 1.0);
+        assert!((wait.median_draws() / 6.931_471_805_599_453e19 - 1.0).abs() < 1e-9);
+        assert!((wait.probability_within_draws(1e20) - (1.0 - (-1.0f64).exp())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_calendar_conversion() {
+        let schedule = DrawSchedule::new(2.0, 1).unwrap();
+        let wait = time_to_win(10, 3, 3, &schedule).unwrap(); // 1 in 120
+        assert!((wait.expected_years() - 120.0 / (2.0 * WEEKS_PER_YEAR)).abs() < 1e-9);
+        let one_year = wait.probability_within_years(1.0);
+        assert!((one_year - wait.probability_within_draws(104.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_jackpot_tickets_add_up_exactly() {
+        let schedule = DrawSchedule::new(1.0, 10).unwrap();
+        let wait = time_to_win(60, 6, 6, &schedule).unwrap();
+        assert!((wait.expected_draws() - 5_006_386.0).abs() < 1e-6);
+
+        // More tickets than combinations always wins
+        let schedule = DrawSchedule::new(1.0, 200).unwrap();
+        let wait = time_to_win(10, 3, 3, &schedule).unwrap();
+        assert_eq!(wait.draw_probability(), 1.0);
+    }
+
+    #[test]
+    fn test_lower_tier_tickets_are_independent() {
+        let schedule = DrawSchedule::new(1.0, 2).unwrap();
+        let wait = time_to_win(10, 3, 2, &schedule).unwrap(); // 21/120 per ticket
+        let expected = 1.0 - (99.0f64 / 120.0).powi(2);
+        assert!((wait.draw_probability() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_schedule() {
+        assert!(matches!(
+            DrawSchedule::new(0.0, 1),
+            Err(LottoError::InvalidAmount { .. })
+        ));
+        assert!(matches!(
+            DrawSchedule::new(f64::NAN, 1),
+            Err(LottoError::InvalidAmount { .. })
+        ));
+        assert!(matches!(
+            DrawSchedule::new(1.0, 0),
+            Err(LottoError::ZeroGames)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_match_count() {
+        let schedule = DrawSchedule::new(1.0, 1).unwrap();
+        let result = time_to_win(60, 6, 7, &schedule);
+        assert!(matches!(result, Err(LottoError::InvalidMatchCount { .. })));
+    }
+
+    #[test]
+    fn test_multi_bet_tickets_are_independent() {
+        let schedule = DrawSchedule::new(1.0, 2).unwrap();
+        let wait = time_to_win_for_bet(60, 7, 6, 6, &schedule).unwrap();
+        let bet = 7.0 / 50_063_860.0;
+        let expected = 2.0 * bet - bet * bet;
+        assert!((wait.draw_probability() / expected - 1.0).abs() < 1e-12);
+    }
+}